@@ -418,6 +418,56 @@ impl ComprehensiveTestSuite {
             .has(&DataKey::LastSuccessfulRun(suite)))
     }
 
+    /// Seconds elapsed since the run that currently gates `suite` was
+    /// recorded, or `None` if the suite has no successful run yet.
+    ///
+    /// Consumers can combine this with `is_release_ready` to reject stale gates.
+    pub fn last_successful_run_age(env: Env, suite: Symbol) -> Result<Option<u64>, Error> {
+        require_initialized(&env)?;
+
+        let Some(build_id) = env
+            .storage()
+            .instance()
+            .get::<_, BytesN<32>>(&DataKey::LastSuccessfulRun(suite.clone()))
+        else {
+            return Ok(None);
+        };
+
+        let record: Option<RunRecord> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Run(RunKey { suite, build_id }));
+
+        Ok(record.map(|r| env.ledger().timestamp().saturating_sub(r.timestamp)))
+    }
+
+    /// The build id and full `RunRecord` of the run that currently gates
+    /// `suite`, or `None` if the suite has no successful run yet.
+    ///
+    /// Answers "which run gated this release" for audits without knowing the
+    /// build id up front.
+    pub fn gate_evidence(
+        env: Env,
+        suite: Symbol,
+    ) -> Result<Option<(BytesN<32>, RunRecord)>, Error> {
+        require_initialized(&env)?;
+
+        let Some(build_id) = env
+            .storage()
+            .instance()
+            .get::<_, BytesN<32>>(&DataKey::LastSuccessfulRun(suite.clone()))
+        else {
+            return Ok(None);
+        };
+
+        let record: Option<RunRecord> = env.storage().persistent().get(&DataKey::Run(RunKey {
+            suite,
+            build_id: build_id.clone(),
+        }));
+
+        Ok(record.map(|r| (build_id, r)))
+    }
+
     pub fn get_suite(env: Env, suite: Symbol) -> Result<Option<SuiteConfig>, Error> {
         require_initialized(&env)?;
         Ok(env.storage().instance().get(&DataKey::Suite(suite)))
@@ -539,7 +589,7 @@ fn validate_suite_input(_suite: &Symbol, min_cases: u32) -> Result<(), Error> {
 mod tests {
     use super::*;
     use soroban_sdk::{
-        testutils::{Address as _, BytesN as _, Events as _},
+        testutils::{Address as _, BytesN as _, Events as _, Ledger as _},
         Address, Env,
     };
 
@@ -587,6 +637,83 @@ mod tests {
         assert_eq!(state.total_runs, 1);
     }
 
+    #[test]
+    fn last_successful_run_age_tracks_gating_run() {
+        let env = Env::default();
+        let (client, admin, reporter) = setup(&env);
+        let suite = Symbol::new(&env, "age_suite");
+
+        env.mock_all_auths();
+        client.register_suite(&admin, &suite, &1u32, &false, &false);
+        assert_eq!(client.last_successful_run_age(&suite), None);
+
+        env.ledger().set_timestamp(1_000);
+        client.record_run(
+            &reporter,
+            &suite,
+            &random_hash(&env),
+            &1u32,
+            &0u32,
+            &9_000u32,
+            &false,
+            &false,
+        );
+        assert_eq!(client.last_successful_run_age(&suite), Some(0));
+
+        // A failing run does not move the gating run.
+        env.ledger().set_timestamp(1_500);
+        client.record_run(
+            &reporter,
+            &suite,
+            &random_hash(&env),
+            &0u32,
+            &1u32,
+            &9_000u32,
+            &false,
+            &false,
+        );
+        assert_eq!(client.last_successful_run_age(&suite), Some(500));
+    }
+
+    #[test]
+    fn gate_evidence_returns_gating_build_and_record() {
+        let env = Env::default();
+        let (client, admin, reporter) = setup(&env);
+        let suite = Symbol::new(&env, "audit_suite");
+
+        env.mock_all_auths();
+        client.register_suite(&admin, &suite, &1u32, &false, &false);
+        assert_eq!(client.gate_evidence(&suite), None);
+
+        let gating_build = random_hash(&env);
+        client.record_run(
+            &reporter,
+            &suite,
+            &gating_build,
+            &3u32,
+            &0u32,
+            &9_000u32,
+            &false,
+            &false,
+        );
+        client.record_run(
+            &reporter,
+            &suite,
+            &random_hash(&env),
+            &0u32,
+            &3u32,
+            &9_000u32,
+            &false,
+            &false,
+        );
+
+        let (build_id, record) = client.gate_evidence(&suite).unwrap();
+        assert_eq!(build_id, gating_build);
+        assert_eq!(record, client.get_run(&suite, &gating_build).unwrap());
+        assert!(record.gate_passed);
+        assert_eq!(record.passed_cases, 3);
+    }
+
     #[test]
     fn unauthorized_reporter_is_rejected() {
         let env = Env::default();
@@ -2,7 +2,7 @@
 
 use soroban_sdk::{
     contract, contractimpl, vec, Address, BytesN, Env, IntoVal, Symbol,
-    testutils::Address as _,
+    testutils::{Address as _, Ledger as _},
 };
 use stellarcade_comprehensive_test_suite::ComprehensiveTestSuite;
 
@@ -18,6 +18,14 @@ impl GateConsumer {
             vec![&env, suite.into_val(&env)],
         )
     }
+
+    pub fn read_gate_age(env: Env, gate_contract: Address, suite: Symbol) -> Option<u64> {
+        env.invoke_contract::<Option<u64>>(
+            &gate_contract,
+            &Symbol::new(&env, "last_successful_run_age"),
+            vec![&env, suite.into_val(&env)],
+        )
+    }
 }
 
 #[test]
@@ -58,3 +66,46 @@ fn integration_gate_read_flow() {
     let after = consumer.read_gate(&gate_contract_id, &suite);
     assert!(after);
 }
+
+#[test]
+fn integration_gate_age_read_flow() {
+    let env = Env::default();
+
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+
+    let gate_contract_id = env.register(ComprehensiveTestSuite, ());
+    let gate = stellarcade_comprehensive_test_suite::ComprehensiveTestSuiteClient::new(
+        &env,
+        &gate_contract_id,
+    );
+
+    let consumer_id = env.register(GateConsumer, ());
+    let consumer = GateConsumerClient::new(&env, &consumer_id);
+
+    let suite = Symbol::new(&env, "core_suite");
+
+    env.mock_all_auths();
+    gate.init(&admin, &reporter, &8_000u32);
+    gate.register_suite(&admin, &suite, &1u32, &false, &false);
+    assert_eq!(consumer.read_gate_age(&gate_contract_id, &suite), None);
+
+    env.ledger().set_timestamp(10_000);
+    gate.record_run(
+        &reporter,
+        &suite,
+        &BytesN::from_array(&env, &[9u8; 32]),
+        &1u32,
+        &0u32,
+        &9_000u32,
+        &false,
+        &false,
+    );
+
+    env.ledger().set_timestamp(10_000 + 86_400);
+    assert!(consumer.read_gate(&gate_contract_id, &suite));
+    assert_eq!(
+        consumer.read_gate_age(&gate_contract_id, &suite),
+        Some(86_400)
+    );
+}
@@ -26,7 +26,7 @@
 //! let address = registry.resolve(&env, "prize-pool");
 //!
 //! // Update to new version
-//! registry.update(&env, "prize-pool", &new_address, 2);
+//! registry.update(&env, "prize-pool", &new_address, 2, None, false);
 //!
 //! // Query history
 //! let history = registry.history(&env, "prize-pool");
@@ -35,7 +35,10 @@
 #![no_std]
 #![allow(unexpected_cfgs)]
 
-use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contracterror, contractevent, contractimpl, contracttype, vec, Address, Env,
+    IntoVal, InvokeError, String, Symbol, Vec,
+};
 
 // ---------------------------------------------------------------------------
 // Constants
@@ -50,6 +53,16 @@ const MAX_NAME_LENGTH: u32 = 64;
 /// Minimum contract name length
 const MIN_NAME_LENGTH: u32 = 1;
 
+/// Maximum description length in bytes for contract metadata
+const MAX_DESCRIPTION_LENGTH: u32 = 256;
+
+/// Length of an account or contract strkey (G... / C...)
+const STRKEY_LENGTH: u32 = 56;
+
+/// Smallest non-zero history limit: the current record plus the one before
+/// it, so `rollback` keeps working on pruned names
+const MIN_HISTORY: u32 = 2;
+
 // ---------------------------------------------------------------------------
 // Error Types
 // ---------------------------------------------------------------------------
@@ -74,6 +87,30 @@ pub enum Error {
     InvalidVersion = 7,
     /// Invalid contract name format
     InvalidName = 8,
+    /// Description exceeds the maximum metadata length
+    DescriptionTooLong = 9,
+    /// Name has been frozen and can no longer be changed
+    NameFrozen = 10,
+    /// Update would point the name at the address it already resolves to
+    NoChange = 11,
+    /// Registry is paused; all mutations are blocked
+    RegistryPaused = 12,
+    /// Registry is already paused
+    AlreadyPaused = 13,
+    /// Registry is not paused
+    NotPaused = 14,
+    /// Another name already resolves to this address
+    AddressAlreadyMapped = 15,
+    /// Name has been frozen by a guardian and cannot be resolved
+    ContractFrozen = 16,
+    /// Name is not guardian-frozen
+    NotGuardianFrozen = 17,
+    /// Address is already on the subscriber list
+    AlreadySubscribed = 18,
+    /// Address is not on the subscriber list
+    NotSubscribed = 19,
+    /// History limit is non-zero but below the minimum window
+    InvalidHistoryLimit = 20,
 }
 
 // ---------------------------------------------------------------------------
@@ -95,6 +132,26 @@ pub enum DataKey {
     Initialized,
     /// Vector of all registered contract names
     AllNames,
+    /// Human-readable description: name -> String
+    ContractMeta(String),
+    /// Versions recorded for a name, in ascending order: name -> Vec<u32>
+    VersionList(String),
+    /// Highest version pruned under `max_history`: name -> u32
+    PrunedThrough(String),
+    /// Freeze flag preventing further changes: name -> bool
+    Frozen(String),
+    /// Global pause flag blocking all mutations
+    Paused,
+    /// When set, no two names may resolve to the same address
+    EnforceUniqueAddresses,
+    /// Guardian flag: address -> bool
+    Guardian(Address),
+    /// Incident freeze set by a guardian, blocking resolution: name -> bool
+    GuardianFrozen(String),
+    /// Contracts notified via `on_registry_update(name)` on every change
+    Subscribers,
+    /// History records kept per name; absent or 0 means unlimited
+    MaxHistory,
 }
 
 #[contracttype]
@@ -138,8 +195,105 @@ pub struct ValidationReport {
 // Events
 // ---------------------------------------------------------------------------
 
-// Events are emitted via topics in the environment
-// No struct definitions needed - we'll use simple topic-based events
+#[contractevent]
+pub struct Initialized {
+    #[topic]
+    pub admin: Address,
+}
+
+#[contractevent]
+pub struct Paused {
+    #[topic]
+    pub admin: Address,
+}
+
+#[contractevent]
+pub struct Unpaused {
+    #[topic]
+    pub admin: Address,
+}
+
+#[contractevent]
+pub struct ContractRegistered {
+    #[topic]
+    pub name: String,
+    pub address: Address,
+    pub version: u32,
+    pub admin: Address,
+}
+
+#[contractevent]
+pub struct ContractUpdated {
+    #[topic]
+    pub name: String,
+    pub old_address: Address,
+    pub new_address: Address,
+    pub old_version: u32,
+    pub new_version: u32,
+    pub admin: Address,
+}
+
+#[contractevent]
+pub struct ContractRolledBack {
+    #[topic]
+    pub name: String,
+    pub from_version: u32,
+    pub to_version: u32,
+    pub address: Address,
+    pub admin: Address,
+}
+
+#[contractevent]
+pub struct ContractDeregistered {
+    #[topic]
+    pub name: String,
+    pub address: Address,
+    pub version: u32,
+    pub admin: Address,
+}
+
+#[contractevent]
+pub struct ContractNameFrozen {
+    #[topic]
+    pub name: String,
+    pub admin: Address,
+}
+
+#[contractevent]
+pub struct GuardianUpdated {
+    #[topic]
+    pub guardian: Address,
+    pub enabled: bool,
+    pub admin: Address,
+}
+
+#[contractevent]
+pub struct GuardianFrozen {
+    #[topic]
+    pub name: String,
+    pub guardian: Address,
+}
+
+#[contractevent]
+pub struct GuardianUnfrozen {
+    #[topic]
+    pub name: String,
+    pub admin: Address,
+}
+
+#[contractevent]
+pub struct HistoryPruned {
+    #[topic]
+    pub name: String,
+    pub version: u32,
+}
+
+#[contractevent]
+pub struct SubscriberNotifyFailed {
+    #[topic]
+    pub name: String,
+    pub subscriber: Address,
+}
 
 // ---------------------------------------------------------------------------
 // Contract Implementation
@@ -173,36 +327,221 @@ impl ContractAddressRegistry {
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::Initialized, &true);
 
+        Initialized { admin }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Initialize the registry and register a batch of genesis contracts in a
+    /// single invocation.
+    ///
+    /// Every entry is validated before anything is written, so a bad name,
+    /// version or duplicate leaves the registry uninitialized.
+    ///
+    /// # Arguments
+    /// * `admin` - Address that will have full control over the registry
+    /// * `entries` - `(name, address, version)` tuples to register
+    ///
+    /// # Errors
+    /// * `AlreadyInitialized` - If registry has already been initialized
+    /// * `InvalidName` - If any name fails validation
+    /// * `InvalidAddress` - If any address is not a contract address
+    /// * `InvalidVersion` - If any version is zero
+    /// * `DuplicateRegistration` - If a name appears more than once
+    ///
+    /// # Events
+    /// Emits `Initialized`, then `ContractRegistered` for each entry
+    pub fn init_with_contracts(
+        env: Env,
+        admin: Address,
+        entries: Vec<(String, Address, u32)>,
+    ) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Initialized) {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        admin.require_auth();
+
+        let mut seen: Vec<String> = Vec::new(&env);
+        for (name, address, version) in entries.iter() {
+            Self::validate_name(&env, &name)?;
+            Self::validate_address(&address)?;
+            Self::validate_version(version)?;
+            if seen.contains(&name) {
+                return Err(Error::DuplicateRegistration);
+            }
+            seen.push_back(name);
+        }
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Initialized, &true);
+
+        Initialized {
+            admin: admin.clone(),
+        }
+        .publish(&env);
+
+        for (name, address, version) in entries.iter() {
+            Self::write_registration(&env, admin.clone(), name, address, version, None);
+        }
+
+        Ok(())
+    }
+
+    /// Pause the registry, blocking every mutation until `unpause`.
+    ///
+    /// Reads (`resolve`, `history`, `get_version`, ...) keep working.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `NotAuthorized` - If caller is not the admin
+    /// * `AlreadyPaused` - If the registry is already paused
+    ///
+    /// # Events
+    /// Emits `Paused` event on success
+    pub fn pause(env: Env) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        let admin = Self::require_admin(&env)?;
+
+        if Self::is_paused(env.clone()) {
+            return Err(Error::AlreadyPaused);
+        }
+
+        env.storage().instance().set(&DataKey::Paused, &true);
+        Paused { admin }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Lift a pause set by `pause`.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `NotAuthorized` - If caller is not the admin
+    /// * `NotPaused` - If the registry is not paused
+    ///
+    /// # Events
+    /// Emits `Unpaused` event on success
+    pub fn unpause(env: Env) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        let admin = Self::require_admin(&env)?;
+
+        if !Self::is_paused(env.clone()) {
+            return Err(Error::NotPaused);
+        }
+
+        env.storage().instance().set(&DataKey::Paused, &false);
+        Unpaused { admin }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Check whether the registry is currently paused.
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
+    /// Toggle unique-address enforcement.
+    ///
+    /// While enabled, `register` and `update` reject an address that another
+    /// name already resolves to, catching copy-paste mistakes. Existing
+    /// duplicates are not touched; `validation_report` still flags them.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `RegistryPaused` - If the registry is paused
+    /// * `NotAuthorized` - If caller is not the admin
+    pub fn set_unique_address_enforcement(env: Env, enabled: bool) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+        Self::require_admin(&env)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::EnforceUniqueAddresses, &enabled);
+
+        Ok(())
+    }
+
+    /// Cap how many history records are kept per name.
+    ///
+    /// Once set, each `update` prunes the oldest records of that name beyond
+    /// the limit, emitting `HistoryPruned` for each. 0 (the default) keeps
+    /// every record. Lowering the limit does not prune until the next
+    /// `update` of each name.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `RegistryPaused` - If the registry is paused
+    /// * `NotAuthorized` - If caller is not the admin
+    /// * `InvalidHistoryLimit` - If `max_history` is 1
+    pub fn set_max_history(env: Env, max_history: u32) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+        Self::require_admin(&env)?;
+
+        if max_history != 0 && max_history < MIN_HISTORY {
+            return Err(Error::InvalidHistoryLimit);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxHistory, &max_history);
+
         Ok(())
     }
 
+    /// Get the per-name history limit; 0 means unlimited.
+    pub fn max_history(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxHistory)
+            .unwrap_or(0)
+    }
+
     /// Register a new contract in the registry.
     ///
     /// # Arguments
     /// * `name` - Unique identifier for the contract (e.g., "prize-pool")
     /// * `address` - Contract address (must start with 'C')
     /// * `version` - Initial version number (typically 1)
+    /// * `description` - Optional human-readable description (max 256 bytes)
     ///
     /// # Errors
     /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `RegistryPaused` - If the registry is paused
     /// * `NotAuthorized` - If caller is not the admin
     /// * `InvalidName` - If name is empty, too long, or invalid format
-    /// * `InvalidAddress` - If address format is invalid
+    /// * `InvalidAddress` - If address is not a contract (C...) address
     /// * `DuplicateRegistration` - If contract name already exists
+    /// * `AddressAlreadyMapped` - If unique addresses are enforced and another
+    ///   name already resolves to `address`
     /// * `InvalidVersion` - If version is 0
+    /// * `DescriptionTooLong` - If description exceeds 256 bytes
     ///
     /// # Events
     /// Emits `ContractRegistered` event on success
-    pub fn register(env: Env, name: String, address: Address, version: u32) -> Result<(), Error> {
+    pub fn register(
+        env: Env,
+        name: String,
+        address: Address,
+        version: u32,
+        description: Option<String>,
+    ) -> Result<(), Error> {
         // Check initialization
         Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
 
         // Check authorization
         let admin = Self::require_admin(&env)?;
 
         // Validate inputs
         Self::validate_name(&env, &name)?;
+        Self::validate_address(&address)?;
         Self::validate_version(version)?;
+        Self::validate_description(&description)?;
 
         // Check for duplicate registration
         if env
@@ -213,52 +552,9 @@ impl ContractAddressRegistry {
             return Err(Error::DuplicateRegistration);
         }
 
-        // Create contract record
-        let record = ContractRecord {
-            address: address.clone(),
-            version,
-            registered_at: env.ledger().sequence(),
-            registered_by: admin.clone(),
-        };
-
-        // Store current record
-        env.storage()
-            .persistent()
-            .set(&DataKey::Contract(name.clone()), &record);
-        env.storage().persistent().extend_ttl(
-            &DataKey::Contract(name.clone()),
-            PERSISTENT_BUMP_LEDGERS,
-            PERSISTENT_BUMP_LEDGERS,
-        );
-
-        // Store in history
-        env.storage()
-            .persistent()
-            .set(&DataKey::ContractHistory(name.clone(), version), &record);
-        env.storage().persistent().extend_ttl(
-            &DataKey::ContractHistory(name.clone(), version),
-            PERSISTENT_BUMP_LEDGERS,
-            PERSISTENT_BUMP_LEDGERS,
-        );
-
-        // Store latest version
-        env.storage()
-            .persistent()
-            .set(&DataKey::LatestVersion(name.clone()), &version);
-        env.storage().persistent().extend_ttl(
-            &DataKey::LatestVersion(name.clone()),
-            PERSISTENT_BUMP_LEDGERS,
-            PERSISTENT_BUMP_LEDGERS,
-        );
+        Self::ensure_address_unmapped(&env, &address, &name)?;
 
-        // Update AllNames list
-        let mut names: Vec<String> = env
-            .storage()
-            .instance()
-            .get(&DataKey::AllNames)
-            .unwrap_or_else(|| Vec::new(&env));
-        names.push_back(name);
-        env.storage().instance().set(&DataKey::AllNames, &names);
+        Self::write_registration(&env, admin, name, address, version, description);
 
         Ok(())
     }
@@ -269,25 +565,43 @@ impl ContractAddressRegistry {
     /// * `name` - Contract name to update
     /// * `address` - New contract address
     /// * `version` - New version number (must be greater than current)
+    /// * `description` - New description; `None` keeps the existing one
+    /// * `force` - Allow re-recording the current address under a new version
     ///
     /// # Errors
     /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `RegistryPaused` - If the registry is paused
     /// * `NotAuthorized` - If caller is not the admin
     /// * `ContractNotFound` - If contract name doesn't exist
-    /// * `InvalidAddress` - If address format is invalid
+    /// * `NameFrozen` - If the name has been frozen
+    /// * `InvalidAddress` - If address is not a contract (C...) address
     /// * `InvalidVersion` - If version is not greater than current version
+    /// * `NoChange` - If address is unchanged and `force` is not set
+    /// * `AddressAlreadyMapped` - If unique addresses are enforced and another
+    ///   name already resolves to `address`
+    /// * `DescriptionTooLong` - If description exceeds 256 bytes
     ///
     /// # Events
     /// Emits `ContractUpdated` event on success
-    pub fn update(env: Env, name: String, address: Address, version: u32) -> Result<(), Error> {
+    pub fn update(
+        env: Env,
+        name: String,
+        address: Address,
+        version: u32,
+        description: Option<String>,
+        force: bool,
+    ) -> Result<(), Error> {
         // Check initialization
         Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
 
         // Check authorization
         let admin = Self::require_admin(&env)?;
 
-        // Validate version
+        // Validate inputs
+        Self::validate_address(&address)?;
         Self::validate_version(version)?;
+        Self::validate_description(&description)?;
 
         // Get existing record
         let old_record: ContractRecord = env
@@ -296,11 +610,20 @@ impl ContractAddressRegistry {
             .get(&DataKey::Contract(name.clone()))
             .ok_or(Error::ContractNotFound)?;
 
+        Self::require_not_frozen(&env, &name)?;
+
         // Verify new version is greater than old version
         if version <= old_record.version {
             return Err(Error::InvalidVersion);
         }
 
+        // Reject no-op updates so history only records real changes
+        if !force && address == old_record.address {
+            return Err(Error::NoChange);
+        }
+
+        Self::ensure_address_unmapped(&env, &address, &name)?;
+
         // Create new record
         let new_record = ContractRecord {
             address: address.clone(),
@@ -340,157 +663,210 @@ impl ContractAddressRegistry {
             PERSISTENT_BUMP_LEDGERS,
         );
 
+        // Record version in the version list, dropping the oldest beyond the limit
+        Self::append_version(&env, &name, version);
+        Self::prune_history(&env, &name);
+
+        // Replace metadata only when a new description is supplied
+        Self::store_description(&env, &name, description);
+
+        ContractUpdated {
+            name: name.clone(),
+            old_address: old_record.address,
+            new_address: address,
+            old_version: old_record.version,
+            new_version: version,
+            admin,
+        }
+        .publish(&env);
+
+        Self::notify_subscribers(&env, &name);
+
         Ok(())
     }
 
-    /// Performs a validation report of the registry.
+    /// Revert a contract name to its previously recorded version.
     ///
-    /// Flags missing required contracts, duplicate addresses across different aliases,
-    /// and placeholder records.
+    /// The prior history record becomes current again and `LatestVersion`
+    /// steps back to it. The rolled-back record stays readable through
+    /// `get_record_at` but is dropped from `history`.
     ///
-    /// # Returns
-    /// A structured report (`ValidationReport`) flagging:
-    /// - **Missing**: Required core contracts (e.g., `prize-pool`) not registered.
-    /// - **Duplicate**: Multiple aliases pointing to the identical address.
-    /// - **Placeholder**: Address matches the zero-address placeholder (`CAAA...`).
+    /// # Arguments
+    /// * `name` - Contract name to roll back
     ///
-    /// # Operator Guidance
-    /// - **Missing** records: Deploy the missing contract and register it.
-    /// - **Duplicate** records: Investigate alias misconfigurations.
-    /// - **Placeholder** records: Replace with real addresses before production use.
-    pub fn validation_report(env: Env) -> Result<ValidationReport, Error> {
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `RegistryPaused` - If the registry is paused
+    /// * `NotAuthorized` - If caller is not the admin
+    /// * `ContractNotFound` - If contract name doesn't exist
+    /// * `NameFrozen` - If the name has been frozen
+    /// * `InvalidVersion` - If only one version has been recorded
+    ///
+    /// # Events
+    /// Emits `ContractRolledBack` event on success
+    pub fn rollback(env: Env, name: String) -> Result<(), Error> {
         Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+        let admin = Self::require_admin(&env)?;
 
-        let mut issues = Vec::new(&env);
-        let names_in_storage: Vec<String> = env
+        if !env
             .storage()
-            .instance()
-            .get(&DataKey::AllNames)
-            .unwrap_or_else(|| Vec::new(&env));
-
-        let mut addresses = Vec::new(&env);
-        let mut total_checked = 0;
+            .persistent()
+            .has(&DataKey::Contract(name.clone()))
+        {
+            return Err(Error::ContractNotFound);
+        }
 
-        // 1. Check for missing core contracts
-        let core_contracts = Vec::from_array(
-            &env,
-            [
-                String::from_str(&env, "prize-pool"),
-                String::from_str(&env, "random-generator"),
-                String::from_str(&env, "coin-flip"),
-            ],
-        );
+        Self::require_not_frozen(&env, &name)?;
 
-        for core_name in core_contracts.iter() {
-            if !env
-                .storage()
-                .persistent()
-                .has(&DataKey::Contract(core_name.clone()))
-            {
-                issues.push_back(RegistryIssue {
-                    contract_name: core_name,
-                    issue_type: IssueType::Missing,
-                    details: String::from_str(&env, "Required core contract is not registered"),
-                });
-            }
+        let mut versions = Self::recorded_versions(&env, &name);
+        if versions.len() < 2 {
+            return Err(Error::InvalidVersion);
         }
+        let from_version = versions.pop_back().ok_or(Error::InvalidVersion)?;
+        let to_version = versions.last().ok_or(Error::InvalidVersion)?;
 
-        // 2. Iterate through all registered contracts for Duplicate and Placeholder checks
-        for name in names_in_storage.iter() {
-            total_checked += 1;
-            if let Some(record) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, ContractRecord>(&DataKey::Contract(name.clone()))
-            {
-                // Placeholder Check (all-zeros address)
-                if Self::is_placeholder(&env, &record.address) {
-                    issues.push_back(RegistryIssue {
-                        contract_name: name.clone(),
-                        issue_type: IssueType::Placeholder,
-                        details: String::from_str(&env, "Contract address is a zero-address placeholder"),
-                    });
-                }
+        let record: ContractRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ContractHistory(name.clone(), to_version))
+            .ok_or(Error::ContractNotFound)?;
 
-                // Duplicate Check
-                for (idx, other_addr) in addresses.iter().enumerate() {
-                    if other_addr == record.address {
-                        let _ = names_in_storage.get(idx as u32); // acknowledge the duplicate name exists
-                        issues.push_back(RegistryIssue {
-                            contract_name: name.clone(),
-                            issue_type: IssueType::Duplicate,
-                            details: String::from_str(&env, "Shares address with another registered contract"),
-                        });
-                        break;
-                    }
-                }
-                addresses.push_back(record.address);
-            }
+        // Restore the prior record as current
+        env.storage()
+            .persistent()
+            .set(&DataKey::Contract(name.clone()), &record);
+        env.storage().persistent().extend_ttl(
+            &DataKey::Contract(name.clone()),
+            PERSISTENT_BUMP_LEDGERS,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+
+        // Step the latest version back
+        env.storage()
+            .persistent()
+            .set(&DataKey::LatestVersion(name.clone()), &to_version);
+        env.storage().persistent().extend_ttl(
+            &DataKey::LatestVersion(name.clone()),
+            PERSISTENT_BUMP_LEDGERS,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+
+        let key = DataKey::VersionList(name.clone());
+        env.storage().persistent().set(&key, &versions);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+
+        ContractRolledBack {
+            name: name.clone(),
+            from_version,
+            to_version,
+            address: record.address,
+            admin,
         }
+        .publish(&env);
 
-        Ok(ValidationReport {
-            timestamp: env.ledger().sequence(),
-            issues,
-            total_checked,
-        })
-    }
+        Self::notify_subscribers(&env, &name);
 
-    /// Helper to detect placeholder addresses (explicit zero-address check).
-    /// Placeholder is defined as the Stellar zero-address strkey:
-    /// CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAD2KM
-    fn is_placeholder(env: &Env, address: &Address) -> bool {
-        let zero_strkey = String::from_str(env, "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAD2KM");
-        let placeholder_addr = Address::from_string(&zero_strkey);
-        address == &placeholder_addr
+        Ok(())
     }
 
-    /// Resolve the current address for a contract name.
+    /// Remove a contract name from the registry.
     ///
-    /// # Arguments
-    /// * `name` - Contract name to resolve
+    /// Historical records are retained; the name stops resolving and may be
+    /// registered again later. Any guardian freeze on the name is cleared.
     ///
-    /// # Returns
-    /// The current contract address
+    /// # Arguments
+    /// * `name` - Contract name to remove
     ///
     /// # Errors
     /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `RegistryPaused` - If the registry is paused
+    /// * `NotAuthorized` - If caller is not the admin
     /// * `ContractNotFound` - If contract name doesn't exist
+    /// * `NameFrozen` - If the name has been frozen
     ///
-    /// # Note
-    /// This is a public read operation - no authorization required
-    pub fn resolve(env: Env, name: String) -> Result<Address, Error> {
-        // Check initialization
+    /// # Events
+    /// Emits `ContractDeregistered` event on success
+    pub fn deregister(env: Env, name: String) -> Result<(), Error> {
         Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+        let admin = Self::require_admin(&env)?;
 
-        // Get and return current address
         let record: ContractRecord = env
             .storage()
             .persistent()
-            .get(&DataKey::Contract(name))
+            .get(&DataKey::Contract(name.clone()))
             .ok_or(Error::ContractNotFound)?;
 
-        Ok(record.address)
+        Self::require_not_frozen(&env, &name)?;
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Contract(name.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::LatestVersion(name.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::VersionList(name.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::PrunedThrough(name.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ContractMeta(name.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::GuardianFrozen(name.clone()));
+
+        // Drop the name from the AllNames list
+        let names: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AllNames)
+            .unwrap_or_else(|| Vec::new(&env));
+        if let Some(index) = names.first_index_of(&name) {
+            let mut names = names;
+            names.remove(index);
+            env.storage().instance().set(&DataKey::AllNames, &names);
+        }
+
+        ContractDeregistered {
+            name: name.clone(),
+            address: record.address,
+            version: record.version,
+            admin,
+        }
+        .publish(&env);
+
+        Self::notify_subscribers(&env, &name);
+
+        Ok(())
     }
 
-    /// Get the full version history for a contract.
+    /// Permanently freeze a contract name.
     ///
-    /// # Arguments
-    /// * `name` - Contract name to query
+    /// Once frozen, `update` and `deregister` fail with `NameFrozen`, so a
+    /// resolved address is guaranteed never to change. There is no unfreeze.
     ///
-    /// # Returns
-    /// Vector of all historical contract records, ordered by version
+    /// # Arguments
+    /// * `name` - Contract name to freeze
     ///
     /// # Errors
     /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `RegistryPaused` - If the registry is paused
+    /// * `NotAuthorized` - If caller is not the admin
     /// * `ContractNotFound` - If contract name doesn't exist
     ///
-    /// # Note
-    /// This is a public read operation - no authorization required
-    pub fn history(env: Env, name: String) -> Result<Vec<ContractRecord>, Error> {
-        // Check initialization
+    /// # Events
+    /// Emits `ContractNameFrozen` event on success
+    pub fn freeze(env: Env, name: String) -> Result<(), Error> {
         Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+        let admin = Self::require_admin(&env)?;
 
-        // Check if contract exists
         if !env
             .storage()
             .persistent()
@@ -499,45 +875,135 @@ impl ContractAddressRegistry {
             return Err(Error::ContractNotFound);
         }
 
-        // Get latest version
-        let latest_version: u32 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::LatestVersion(name.clone()))
-            .unwrap_or(0);
+        let key = DataKey::Frozen(name.clone());
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_BUMP_LEDGERS,
+            PERSISTENT_BUMP_LEDGERS,
+        );
 
-        // Collect all historical records
-        let mut history = Vec::new(&env);
+        ContractNameFrozen { name, admin }.publish(&env);
 
-        for version in 1..=latest_version {
-            if let Some(record) = env
-                .storage()
-                .persistent()
-                .get::<DataKey, ContractRecord>(&DataKey::ContractHistory(name.clone(), version))
-            {
-                history.push_back(record);
+        Ok(())
+    }
+
+    /// Re-extend the TTL of a name's persistent entries without changing them.
+    ///
+    /// Covers the current record, latest version, every history entry and
+    /// the name's auxiliary entries (version list, description, frozen flag).
+    /// Anyone may call this, so keepers can keep stable names alive.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `ContractNotFound` - If the name is not registered
+    pub fn touch(env: Env, name: String) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+
+        let storage = env.storage().persistent();
+        if !storage.has(&DataKey::Contract(name.clone())) {
+            return Err(Error::ContractNotFound);
+        }
+
+        let mut keys = Vec::from_array(
+            &env,
+            [
+                DataKey::Contract(name.clone()),
+                DataKey::LatestVersion(name.clone()),
+                DataKey::VersionList(name.clone()),
+                DataKey::PrunedThrough(name.clone()),
+                DataKey::ContractMeta(name.clone()),
+                DataKey::Frozen(name.clone()),
+                DataKey::GuardianFrozen(name.clone()),
+            ],
+        );
+        for version in Self::recorded_versions(&env, &name).iter() {
+            keys.push_back(DataKey::ContractHistory(name.clone(), version));
+        }
+
+        for key in keys.iter() {
+            if storage.has(&key) {
+                storage.extend_ttl(&key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
             }
         }
 
-        Ok(history)
+        Ok(())
     }
 
-    /// Get the current version number for a contract.
+    /// Check whether a contract name has been frozen.
+    pub fn is_frozen(env: Env, name: String) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Frozen(name))
+            .unwrap_or(false)
+    }
+
+    /// Grant or revoke guardian rights for an address.
     ///
-    /// # Arguments
-    /// * `name` - Contract name to query
+    /// Guardians can call `guardian_freeze` to block resolution of a single
+    /// name during an incident without holding admin rights.
     ///
-    /// # Returns
-    /// The current version number
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `NotAuthorized` - If caller is not the admin
+    /// * `RegistryPaused` - If the registry is paused
+    ///
+    /// # Events
+    /// Emits `GuardianUpdated`
+    pub fn set_guardian(env: Env, guardian: Address, enabled: bool) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+        let admin = Self::require_admin(&env)?;
+
+        let key = DataKey::Guardian(guardian.clone());
+        if enabled {
+            env.storage().persistent().set(&key, &true);
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_BUMP_LEDGERS,
+                PERSISTENT_BUMP_LEDGERS,
+            );
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        GuardianUpdated {
+            guardian,
+            enabled,
+            admin,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Check whether an address is a guardian.
+    pub fn is_guardian(env: Env, address: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Guardian(address))
+            .unwrap_or(false)
+    }
+
+    /// Freeze a single compromised name so `resolve` and `resolve_record`
+    /// fail with `ContractFrozen`. Callable by any guardian, and allowed
+    /// while the registry is paused. Only the admin can lift the freeze.
     ///
     /// # Errors
     /// * `NotInitialized` - If registry hasn't been initialized
-    /// * `ContractNotFound` - If contract name doesn't exist
-    pub fn get_version(env: Env, name: String) -> Result<u32, Error> {
-        // Check initialization
+    /// * `NotAuthorized` - If `guardian` is not a guardian
+    /// * `ContractNotFound` - If the name is not registered
+    /// * `ContractFrozen` - If the name is already guardian-frozen
+    ///
+    /// # Events
+    /// Emits `GuardianFrozen` naming the guardian
+    pub fn guardian_freeze(env: Env, guardian: Address, name: String) -> Result<(), Error> {
         Self::require_initialized(&env)?;
+        guardian.require_auth();
 
-        // Check if contract exists
+        if !Self::is_guardian(env.clone(), guardian.clone()) {
+            return Err(Error::NotAuthorized);
+        }
         if !env
             .storage()
             .persistent()
@@ -545,78 +1011,938 @@ impl ContractAddressRegistry {
         {
             return Err(Error::ContractNotFound);
         }
+        if Self::is_guardian_frozen(env.clone(), name.clone()) {
+            return Err(Error::ContractFrozen);
+        }
 
-        // Get latest version
-        let version: u32 = env
-            .storage()
-            .persistent()
-            .get(&DataKey::LatestVersion(name))
-            .unwrap_or(0);
+        let key = DataKey::GuardianFrozen(name.clone());
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_BUMP_LEDGERS,
+            PERSISTENT_BUMP_LEDGERS,
+        );
 
-        Ok(version)
+        GuardianFrozen { name, guardian }.publish(&env);
+
+        Ok(())
     }
 
-    /// Get the current admin address.
-    ///
-    /// # Returns
-    /// The admin address
+    /// Lift a guardian freeze so the name resolves again.
     ///
     /// # Errors
     /// * `NotInitialized` - If registry hasn't been initialized
-    pub fn get_admin(env: Env) -> Result<Address, Error> {
+    /// * `NotAuthorized` - If caller is not the admin
+    /// * `RegistryPaused` - If the registry is paused
+    /// * `NotGuardianFrozen` - If the name is not guardian-frozen
+    ///
+    /// # Events
+    /// Emits `GuardianUnfrozen`
+    pub fn guardian_unfreeze(env: Env, name: String) -> Result<(), Error> {
         Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+        let admin = Self::require_admin(&env)?;
 
+        if !Self::is_guardian_frozen(env.clone(), name.clone()) {
+            return Err(Error::NotGuardianFrozen);
+        }
         env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)
-    }
+            .persistent()
+            .remove(&DataKey::GuardianFrozen(name.clone()));
 
-    // ── Private Helper Methods ─────────────────────────────────────────────
+        GuardianUnfrozen { name, admin }.publish(&env);
 
-    /// Verify that the registry has been initialized
-    fn require_initialized(env: &Env) -> Result<(), Error> {
-        if !env.storage().instance().has(&DataKey::Initialized) {
-            return Err(Error::NotInitialized);
-        }
         Ok(())
     }
 
-    /// Verify that the caller is the admin and return admin address
-    fn require_admin(env: &Env) -> Result<Address, Error> {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)?;
-
-        admin.require_auth();
-        Ok(admin)
+    /// Check whether a name is currently guardian-frozen.
+    pub fn is_guardian_frozen(env: Env, name: String) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::GuardianFrozen(name))
+            .unwrap_or(false)
     }
 
-    /// Validate contract name format and length
-    fn validate_name(_env: &Env, name: &String) -> Result<(), Error> {
-        let len = name.len();
+    /// Subscribe a contract to registry changes.
+    ///
+    /// After every `update`, `rollback` and `deregister` the registry calls
+    /// `on_registry_update(name)` on each subscriber so cached addresses can
+    /// be refreshed. A failing callback is skipped and reported through a
+    /// `SubscriberNotifyFailed` event; it never blocks the registry change.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `NotAuthorized` - If caller is not the admin
+    /// * `RegistryPaused` - If the registry is paused
+    /// * `InvalidAddress` - If `subscriber` is not a contract address
+    /// * `AlreadySubscribed` - If `subscriber` is already subscribed
+    pub fn add_subscriber(env: Env, subscriber: Address) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+        Self::require_admin(&env)?;
+        Self::validate_address(&subscriber)?;
 
-        if len < MIN_NAME_LENGTH {
-            return Err(Error::InvalidName);
+        let mut subscribers = Self::subscribers(env.clone());
+        if subscribers.contains(&subscriber) {
+            return Err(Error::AlreadySubscribed);
         }
+        subscribers.push_back(subscriber);
+        env.storage()
+            .instance()
+            .set(&DataKey::Subscribers, &subscribers);
 
-        if len > MAX_NAME_LENGTH {
-            return Err(Error::InvalidName);
-        }
+        Ok(())
+    }
 
-        // Check for valid characters (alphanumeric, hyphens, underscores)
-        // Note: Soroban String doesn't have convenient char iteration,
-        // so we do basic validation
-        if len == 0 {
-            return Err(Error::InvalidName);
-        }
+    /// Remove a contract from the subscriber list.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `NotAuthorized` - If caller is not the admin
+    /// * `RegistryPaused` - If the registry is paused
+    /// * `NotSubscribed` - If `subscriber` is not subscribed
+    pub fn remove_subscriber(env: Env, subscriber: Address) -> Result<(), Error> {
+        Self::require_initialized(&env)?;
+        Self::require_not_paused(&env)?;
+        Self::require_admin(&env)?;
+
+        let mut subscribers = Self::subscribers(env.clone());
+        let index = subscribers
+            .first_index_of(&subscriber)
+            .ok_or(Error::NotSubscribed)?;
+        subscribers.remove(index);
+        env.storage()
+            .instance()
+            .set(&DataKey::Subscribers, &subscribers);
 
         Ok(())
     }
 
-    /// Validate version number (must be > 0)
+    /// List subscribed contracts in subscription order.
+    pub fn subscribers(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Subscribers)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Performs a validation report of the registry.
+    ///
+    /// Flags missing required contracts, duplicate addresses across different aliases,
+    /// and placeholder records.
+    ///
+    /// # Returns
+    /// A structured report (`ValidationReport`) flagging:
+    /// - **Missing**: Required core contracts (e.g., `prize-pool`) not registered.
+    /// - **Duplicate**: Multiple aliases pointing to the identical address.
+    /// - **Placeholder**: Address matches the zero-address placeholder (`CAAA...`).
+    ///
+    /// # Operator Guidance
+    /// - **Missing** records: Deploy the missing contract and register it.
+    /// - **Duplicate** records: Investigate alias misconfigurations.
+    /// - **Placeholder** records: Replace with real addresses before production use.
+    pub fn validation_report(env: Env) -> Result<ValidationReport, Error> {
+        Self::require_initialized(&env)?;
+
+        let mut issues = Vec::new(&env);
+        let names_in_storage: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AllNames)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut addresses = Vec::new(&env);
+        let mut total_checked = 0;
+
+        // 1. Check for missing core contracts
+        let core_contracts = Vec::from_array(
+            &env,
+            [
+                String::from_str(&env, "prize-pool"),
+                String::from_str(&env, "random-generator"),
+                String::from_str(&env, "coin-flip"),
+            ],
+        );
+
+        for core_name in core_contracts.iter() {
+            if !env
+                .storage()
+                .persistent()
+                .has(&DataKey::Contract(core_name.clone()))
+            {
+                issues.push_back(RegistryIssue {
+                    contract_name: core_name,
+                    issue_type: IssueType::Missing,
+                    details: String::from_str(&env, "Required core contract is not registered"),
+                });
+            }
+        }
+
+        // 2. Iterate through all registered contracts for Duplicate and Placeholder checks
+        for name in names_in_storage.iter() {
+            total_checked += 1;
+            if let Some(record) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, ContractRecord>(&DataKey::Contract(name.clone()))
+            {
+                // Placeholder Check (all-zeros address)
+                if Self::is_placeholder(&env, &record.address) {
+                    issues.push_back(RegistryIssue {
+                        contract_name: name.clone(),
+                        issue_type: IssueType::Placeholder,
+                        details: String::from_str(&env, "Contract address is a zero-address placeholder"),
+                    });
+                }
+
+                // Duplicate Check
+                for (idx, other_addr) in addresses.iter().enumerate() {
+                    if other_addr == record.address {
+                        let _ = names_in_storage.get(idx as u32); // acknowledge the duplicate name exists
+                        issues.push_back(RegistryIssue {
+                            contract_name: name.clone(),
+                            issue_type: IssueType::Duplicate,
+                            details: String::from_str(&env, "Shares address with another registered contract"),
+                        });
+                        break;
+                    }
+                }
+                addresses.push_back(record.address);
+            }
+        }
+
+        Ok(ValidationReport {
+            timestamp: env.ledger().sequence(),
+            issues,
+            total_checked,
+        })
+    }
+
+    /// Helper to detect placeholder addresses (explicit zero-address check).
+    /// Placeholder is defined as the Stellar zero-address strkey:
+    /// CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAD2KM
+    fn is_placeholder(env: &Env, address: &Address) -> bool {
+        let zero_strkey = String::from_str(env, "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAD2KM");
+        let placeholder_addr = Address::from_string(&zero_strkey);
+        address == &placeholder_addr
+    }
+
+    /// Resolve the current address for a contract name.
+    ///
+    /// # Arguments
+    /// * `name` - Contract name to resolve
+    ///
+    /// # Returns
+    /// The current contract address
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `ContractNotFound` - If contract name doesn't exist
+    ///
+    /// # Note
+    /// This is a public read operation - no authorization required
+    pub fn resolve(env: Env, name: String) -> Result<Address, Error> {
+        // Check initialization
+        Self::require_initialized(&env)?;
+
+        // Get and return current address
+        let record: ContractRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contract(name.clone()))
+            .ok_or(Error::ContractNotFound)?;
+        Self::require_not_guardian_frozen(&env, &name)?;
+
+        Ok(record.address)
+    }
+
+    /// Resolve a set of names, pairing each with its address or `None`.
+    ///
+    /// Unlike `resolve`, a missing name does not fail the call, so callers
+    /// can degrade gracefully and report the gaps. Guardian-frozen names are
+    /// reported as `None`. Order follows `names`.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    pub fn resolve_report(
+        env: Env,
+        names: Vec<String>,
+    ) -> Result<Vec<(String, Option<Address>)>, Error> {
+        Self::require_initialized(&env)?;
+
+        let mut report = Vec::new(&env);
+        for name in names.iter() {
+            let address = if Self::is_guardian_frozen(env.clone(), name.clone()) {
+                None
+            } else {
+                env.storage()
+                    .persistent()
+                    .get::<DataKey, ContractRecord>(&DataKey::Contract(name.clone()))
+                    .map(|record| record.address)
+            };
+            report.push_back((name, address));
+        }
+
+        Ok(report)
+    }
+
+    /// Resolve the full current record for a contract name.
+    ///
+    /// Returns the address together with its version and registration
+    /// metadata, avoiding a separate `get_version` call.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `ContractNotFound` - If contract name doesn't exist
+    ///
+    /// # Note
+    /// This is a public read operation - no authorization required
+    pub fn resolve_record(env: Env, name: String) -> Result<ContractRecord, Error> {
+        Self::require_initialized(&env)?;
+
+        let record = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Contract(name.clone()))
+            .ok_or(Error::ContractNotFound)?;
+        Self::require_not_guardian_frozen(&env, &name)?;
+
+        Ok(record)
+    }
+
+    /// Get the full version history for a contract.
+    ///
+    /// # Arguments
+    /// * `name` - Contract name to query
+    ///
+    /// # Returns
+    /// Vector of all retained historical contract records, ordered by
+    /// version. Versions need not be sequential; only recorded versions are
+    /// visited, and versions pruned under `max_history` are not returned.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `ContractNotFound` - If contract name doesn't exist
+    ///
+    /// # Note
+    /// This is a public read operation - no authorization required
+    pub fn history(env: Env, name: String) -> Result<Vec<ContractRecord>, Error> {
+        Self::history_page(env, name, 0, u32::MAX)
+    }
+
+    /// Get one page of the version history for a contract.
+    ///
+    /// # Arguments
+    /// * `name` - Contract name to query
+    /// * `start_version` - Lowest version to include
+    /// * `limit` - Maximum number of records to return
+    ///
+    /// # Returns
+    /// Up to `limit` records with version `>= start_version`, ordered by
+    /// version. Empty when `start_version` is past the latest version.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `ContractNotFound` - If contract name doesn't exist
+    pub fn history_page(
+        env: Env,
+        name: String,
+        start_version: u32,
+        limit: u32,
+    ) -> Result<Vec<ContractRecord>, Error> {
+        // Check initialization
+        Self::require_initialized(&env)?;
+
+        // Check if contract exists
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Contract(name.clone()))
+        {
+            return Err(Error::ContractNotFound);
+        }
+
+        // Collect historical records in the requested window
+        let mut history = Vec::new(&env);
+
+        for version in Self::recorded_versions(&env, &name).iter() {
+            if history.len() >= limit {
+                break;
+            }
+            if version < start_version {
+                continue;
+            }
+            if let Some(record) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, ContractRecord>(&DataKey::ContractHistory(name.clone(), version))
+            {
+                history.push_back(record);
+            }
+        }
+
+        Ok(history)
+    }
+
+    /// Get the record for a specific historical version of a contract.
+    ///
+    /// Reads the single history entry directly instead of loading the full
+    /// history vector.
+    ///
+    /// # Arguments
+    /// * `name` - Contract name to query
+    /// * `version` - Version number to fetch
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `ContractNotFound` - If that version was never recorded for the name
+    pub fn get_record_at(env: Env, name: String, version: u32) -> Result<ContractRecord, Error> {
+        Self::require_initialized(&env)?;
+
+        env.storage()
+            .persistent()
+            .get(&DataKey::ContractHistory(name, version))
+            .ok_or(Error::ContractNotFound)
+    }
+
+    /// Get the current version number for a contract.
+    ///
+    /// # Arguments
+    /// * `name` - Contract name to query
+    ///
+    /// # Returns
+    /// The current version number
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `ContractNotFound` - If contract name doesn't exist
+    pub fn get_version(env: Env, name: String) -> Result<u32, Error> {
+        // Check initialization
+        Self::require_initialized(&env)?;
+
+        // Check if contract exists
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Contract(name.clone()))
+        {
+            return Err(Error::ContractNotFound);
+        }
+
+        // Get latest version
+        let version: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LatestVersion(name))
+            .unwrap_or(0);
+
+        Ok(version)
+    }
+
+    /// Count the versions recorded for a contract without loading any records.
+    ///
+    /// Unlike `get_version`, which returns the latest version *number*, this
+    /// counts actual history entries, so a name registered at v1 and updated
+    /// to v5 reports 2.
+    ///
+    /// # Arguments
+    /// * `name` - Contract name to query
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `ContractNotFound` - If contract name doesn't exist
+    pub fn version_count(env: Env, name: String) -> Result<u32, Error> {
+        Self::require_initialized(&env)?;
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Contract(name.clone()))
+        {
+            return Err(Error::ContractNotFound);
+        }
+
+        Ok(Self::recorded_versions(&env, &name).len())
+    }
+
+    /// Report the latest version number alongside the number of recorded
+    /// versions, so callers can spot gaps in a name's history at a glance.
+    ///
+    /// # Returns
+    /// `(latest_version, recorded_count)`; the two differ whenever some
+    /// version between 1 and latest was never recorded or has been pruned
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `ContractNotFound` - If contract name doesn't exist
+    pub fn version_report(env: Env, name: String) -> Result<(u32, u32), Error> {
+        let latest_version = Self::get_version(env.clone(), name.clone())?;
+        let recorded_count = Self::recorded_versions(&env, &name).len();
+        Ok((latest_version, recorded_count))
+    }
+
+    /// List the ranges of never-recorded versions between the first retained
+    /// version and the latest.
+    ///
+    /// Versions at or below `pruned_through` are outside the retained
+    /// history and are never reported, so pruning does not show up as gaps.
+    ///
+    /// # Arguments
+    /// * `name` - Contract name to query
+    /// * `start_version` - Lowest version to consider
+    /// * `limit` - Maximum number of ranges to return
+    ///
+    /// # Returns
+    /// Up to `limit` inclusive `(from, to)` ranges in ascending order; empty
+    /// when the retained history is contiguous
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `ContractNotFound` - If contract name doesn't exist
+    pub fn missing_versions(
+        env: Env,
+        name: String,
+        start_version: u32,
+        limit: u32,
+    ) -> Result<Vec<(u32, u32)>, Error> {
+        let latest_version = Self::get_version(env.clone(), name.clone())?;
+        let pruned_through = Self::read_pruned_through(&env, &name);
+
+        let mut gaps = Vec::new(&env);
+        let mut next = start_version.max(pruned_through.saturating_add(1)).max(1);
+        for version in Self::recorded_versions(&env, &name).iter() {
+            if gaps.len() >= limit || version > latest_version {
+                return Ok(gaps);
+            }
+            if version < next {
+                continue;
+            }
+            if version > next {
+                gaps.push_back((next, version - 1));
+            }
+            match version.checked_add(1) {
+                Some(following) => next = following,
+                None => return Ok(gaps),
+            }
+        }
+        if gaps.len() < limit && next <= latest_version {
+            gaps.push_back((next, latest_version));
+        }
+
+        Ok(gaps)
+    }
+
+    /// Get the highest version of a contract pruned under `max_history`.
+    ///
+    /// # Returns
+    /// The highest pruned version, or 0 if nothing has been pruned
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `ContractNotFound` - If contract name doesn't exist
+    pub fn pruned_through(env: Env, name: String) -> Result<u32, Error> {
+        Self::require_initialized(&env)?;
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Contract(name.clone()))
+        {
+            return Err(Error::ContractNotFound);
+        }
+
+        Ok(Self::read_pruned_through(&env, &name))
+    }
+
+    /// Get the human-readable description stored for a contract.
+    ///
+    /// # Returns
+    /// The description, or `None` if none was provided
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    /// * `ContractNotFound` - If contract name doesn't exist
+    pub fn get_metadata(env: Env, name: String) -> Result<Option<String>, Error> {
+        Self::require_initialized(&env)?;
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Contract(name.clone()))
+        {
+            return Err(Error::ContractNotFound);
+        }
+
+        Ok(env.storage().persistent().get(&DataKey::ContractMeta(name)))
+    }
+
+    /// Return every current record as `(name, record)` pairs.
+    ///
+    /// Iterates the full name index in registration order and reads one
+    /// record per name, so cost grows linearly with the number of registered
+    /// names. Fine for monitoring small registries; large registries should
+    /// move to a paginated read before this approaches resource limits.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    pub fn snapshot(env: Env) -> Result<Vec<(String, ContractRecord)>, Error> {
+        Self::require_initialized(&env)?;
+
+        let names: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AllNames)
+            .unwrap_or_else(|| Vec::new(&env));
+
+        let mut records = Vec::new(&env);
+        for name in names.iter() {
+            if let Some(record) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, ContractRecord>(&DataKey::Contract(name.clone()))
+            {
+                records.push_back((name, record));
+            }
+        }
+
+        Ok(records)
+    }
+
+    /// Get the current admin address.
+    ///
+    /// # Returns
+    /// The admin address
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If registry hasn't been initialized
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
+        Self::require_initialized(&env)?;
+
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)
+    }
+
+    // ── Private Helper Methods ─────────────────────────────────────────────
+
+    /// Verify that the registry has been initialized
+    fn require_initialized(env: &Env) -> Result<(), Error> {
+        if !env.storage().instance().has(&DataKey::Initialized) {
+            return Err(Error::NotInitialized);
+        }
+        Ok(())
+    }
+
+    /// Verify that the caller is the admin and return admin address
+    fn require_admin(env: &Env) -> Result<Address, Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+
+        admin.require_auth();
+        Ok(admin)
+    }
+
+    /// When unique addresses are enforced, reject `address` if any name other
+    /// than `name` currently resolves to it
+    fn ensure_address_unmapped(env: &Env, address: &Address, name: &String) -> Result<(), Error> {
+        let enforced: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::EnforceUniqueAddresses)
+            .unwrap_or(false);
+        if !enforced {
+            return Ok(());
+        }
+
+        let names: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AllNames)
+            .unwrap_or_else(|| Vec::new(env));
+        for other in names.iter() {
+            if &other == name {
+                continue;
+            }
+            if let Some(record) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, ContractRecord>(&DataKey::Contract(other))
+            {
+                if &record.address == address {
+                    return Err(Error::AddressAlreadyMapped);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Call `on_registry_update(name)` on every subscriber, reporting
+    /// failures instead of propagating them
+    fn notify_subscribers(env: &Env, name: &String) {
+        let callback = Symbol::new(env, "on_registry_update");
+        for subscriber in Self::subscribers(env.clone()).iter() {
+            let result = env.try_invoke_contract::<(), InvokeError>(
+                &subscriber,
+                &callback,
+                vec![env, name.into_val(env)],
+            );
+            if !matches!(result, Ok(Ok(()))) {
+                SubscriberNotifyFailed {
+                    name: name.clone(),
+                    subscriber,
+                }
+                .publish(env);
+            }
+        }
+    }
+
+    /// Reject mutations while the registry is paused
+    fn require_not_paused(env: &Env) -> Result<(), Error> {
+        if Self::is_paused(env.clone()) {
+            return Err(Error::RegistryPaused);
+        }
+        Ok(())
+    }
+
+    /// Reject changes to a frozen name
+    fn require_not_frozen(env: &Env, name: &String) -> Result<(), Error> {
+        if Self::is_frozen(env.clone(), name.clone()) {
+            return Err(Error::NameFrozen);
+        }
+        Ok(())
+    }
+
+    fn require_not_guardian_frozen(env: &Env, name: &String) -> Result<(), Error> {
+        if Self::is_guardian_frozen(env.clone(), name.clone()) {
+            return Err(Error::ContractFrozen);
+        }
+        Ok(())
+    }
+
+    /// Validate contract name format and length
+    fn validate_name(_env: &Env, name: &String) -> Result<(), Error> {
+        let len = name.len();
+
+        if len < MIN_NAME_LENGTH {
+            return Err(Error::InvalidName);
+        }
+
+        if len > MAX_NAME_LENGTH {
+            return Err(Error::InvalidName);
+        }
+
+        // Check for valid characters (alphanumeric, hyphens, underscores)
+        // Note: Soroban String doesn't have convenient char iteration,
+        // so we do basic validation
+        if len == 0 {
+            return Err(Error::InvalidName);
+        }
+
+        Ok(())
+    }
+
+    /// Persist a new name's record, history entry, version bookkeeping and
+    /// `AllNames` membership, then emit `ContractRegistered`. Callers must
+    /// have validated the inputs already.
+    fn write_registration(
+        env: &Env,
+        admin: Address,
+        name: String,
+        address: Address,
+        version: u32,
+        description: Option<String>,
+    ) {
+        // Create contract record
+        let record = ContractRecord {
+            address: address.clone(),
+            version,
+            registered_at: env.ledger().sequence(),
+            registered_by: admin.clone(),
+        };
+
+        // Store current record
+        env.storage()
+            .persistent()
+            .set(&DataKey::Contract(name.clone()), &record);
+        env.storage().persistent().extend_ttl(
+            &DataKey::Contract(name.clone()),
+            PERSISTENT_BUMP_LEDGERS,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+
+        // Store in history
+        env.storage()
+            .persistent()
+            .set(&DataKey::ContractHistory(name.clone(), version), &record);
+        env.storage().persistent().extend_ttl(
+            &DataKey::ContractHistory(name.clone(), version),
+            PERSISTENT_BUMP_LEDGERS,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+
+        // Store latest version
+        env.storage()
+            .persistent()
+            .set(&DataKey::LatestVersion(name.clone()), &version);
+        env.storage().persistent().extend_ttl(
+            &DataKey::LatestVersion(name.clone()),
+            PERSISTENT_BUMP_LEDGERS,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+
+        // Record version in the version list
+        Self::append_version(env, &name, version);
+
+        // Store optional metadata
+        Self::store_description(env, &name, description);
+
+        // Update AllNames list
+        let mut names: Vec<String> = env
+            .storage()
+            .instance()
+            .get(&DataKey::AllNames)
+            .unwrap_or_else(|| Vec::new(env));
+        names.push_back(name.clone());
+        env.storage().instance().set(&DataKey::AllNames, &names);
+
+        ContractRegistered {
+            name,
+            address,
+            version,
+            admin,
+        }
+        .publish(env);
+    }
+
+    /// Reject account (G...) addresses; `resolve` targets must be contracts.
+    /// Contract strkeys are 56 characters and start with `C`.
+    fn validate_address(address: &Address) -> Result<(), Error> {
+        let strkey = address.to_string();
+        if strkey.len() != STRKEY_LENGTH {
+            return Err(Error::InvalidAddress);
+        }
+        let mut buf = [0u8; STRKEY_LENGTH as usize];
+        strkey.copy_into_slice(&mut buf);
+        if buf[0] != b'C' {
+            return Err(Error::InvalidAddress);
+        }
+        Ok(())
+    }
+
+    /// Append a version to the name's recorded version list
+    fn append_version(env: &Env, name: &String, version: u32) {
+        let key = DataKey::VersionList(name.clone());
+        let mut versions: Vec<u32> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(env));
+        versions.push_back(version);
+        env.storage().persistent().set(&key, &versions);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_BUMP_LEDGERS,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+    }
+
+    /// Drop the oldest history records of `name` beyond `max_history`
+    fn prune_history(env: &Env, name: &String) {
+        let max_history = Self::max_history(env.clone());
+        if max_history == 0 {
+            return;
+        }
+
+        let mut versions = Self::recorded_versions(env, name);
+        if versions.len() <= max_history {
+            return;
+        }
+        let mut pruned_through = Self::read_pruned_through(env, name);
+        while versions.len() > max_history {
+            if let Some(version) = versions.pop_front() {
+                env.storage()
+                    .persistent()
+                    .remove(&DataKey::ContractHistory(name.clone(), version));
+                pruned_through = pruned_through.max(version);
+                HistoryPruned {
+                    name: name.clone(),
+                    version,
+                }
+                .publish(env);
+            }
+        }
+
+        let key = DataKey::VersionList(name.clone());
+        env.storage().persistent().set(&key, &versions);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+
+        let key = DataKey::PrunedThrough(name.clone());
+        env.storage().persistent().set(&key, &pruned_through);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+    }
+
+    fn read_pruned_through(env: &Env, name: &String) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PrunedThrough(name.clone()))
+            .unwrap_or(0)
+    }
+
+    /// Versions recorded for a name. Entries written before the version list
+    /// existed fall back to the dense range `1..=LatestVersion`.
+    fn recorded_versions(env: &Env, name: &String) -> Vec<u32> {
+        if let Some(versions) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Vec<u32>>(&DataKey::VersionList(name.clone()))
+        {
+            return versions;
+        }
+
+        let latest_version: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LatestVersion(name.clone()))
+            .unwrap_or(0);
+        let mut versions = Vec::new(env);
+        for version in 1..=latest_version {
+            versions.push_back(version);
+        }
+        versions
+    }
+
+    /// Validate optional description length (bytes)
+    fn validate_description(description: &Option<String>) -> Result<(), Error> {
+        if let Some(description) = description {
+            if description.len() > MAX_DESCRIPTION_LENGTH {
+                return Err(Error::DescriptionTooLong);
+            }
+        }
+        Ok(())
+    }
+
+    /// Store a description for a name, leaving existing metadata untouched on `None`
+    fn store_description(env: &Env, name: &String, description: Option<String>) {
+        if let Some(description) = description {
+            let key = DataKey::ContractMeta(name.clone());
+            env.storage().persistent().set(&key, &description);
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_BUMP_LEDGERS,
+                PERSISTENT_BUMP_LEDGERS,
+            );
+        }
+    }
+
+    /// Validate version number (must be > 0)
     fn validate_version(version: u32) -> Result<(), Error> {
         if version == 0 {
             return Err(Error::InvalidVersion);
@@ -632,7 +1958,51 @@ impl ContractAddressRegistry {
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Address, Env, String};
+    use soroban_sdk::{
+        contract, contractimpl,
+        testutils::{storage::Persistent as _, Address as _, Events as _, Ledger as _},
+        vec, Address, Env, Event, String, Symbol,
+    };
+
+    // ── Mock subscribers ───────────────────────────────────────────────────
+
+    #[contract]
+    pub struct MockSubscriber;
+
+    #[contractimpl]
+    impl MockSubscriber {
+        pub fn on_registry_update(env: Env, name: String) {
+            let count: u32 = env
+                .storage()
+                .instance()
+                .get(&Symbol::new(&env, "count"))
+                .unwrap_or(0);
+            env.storage()
+                .instance()
+                .set(&Symbol::new(&env, "count"), &(count + 1));
+            env.storage().instance().set(&Symbol::new(&env, "last"), &name);
+        }
+
+        pub fn last(env: Env) -> (u32, Option<String>) {
+            (
+                env.storage()
+                    .instance()
+                    .get(&Symbol::new(&env, "count"))
+                    .unwrap_or(0),
+                env.storage().instance().get(&Symbol::new(&env, "last")),
+            )
+        }
+    }
+
+    #[contract]
+    pub struct FailingSubscriber;
+
+    #[contractimpl]
+    impl FailingSubscriber {
+        pub fn on_registry_update(_env: Env, _name: String) {
+            panic!("subscriber failure");
+        }
+    }
 
     // ── Test Helpers ───────────────────────────────────────────────────────
 
@@ -645,7 +2015,7 @@ mod test {
         let env = Env::default();
         env.mock_all_auths();
 
-        let contract_id = env.register_contract(None, ContractAddressRegistry);
+        let contract_id = env.register(ContractAddressRegistry, ());
         let client = ContractAddressRegistryClient::new(&env, &contract_id);
 
         let admin = Address::generate(&env);
@@ -672,140 +2042,802 @@ mod test {
     }
 
     #[test]
-    fn test_init_already_initialized() {
-        let (_env, client, admin, _) = setup_test();
-
+    fn test_init_already_initialized() {
+        let (_env, client, admin, _) = setup_test();
+
+        init_registry(&client, &admin);
+
+        // Try to initialize again
+        let result = client.try_init(&admin);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_init_emits_event() {
+        let (env, client, admin, _) = setup_test();
+        init_registry(&client, &admin);
+
+        assert_eq!(
+            env.events().all().filter_by_contract(&client.address),
+            [Initialized {
+                admin: admin.clone()
+            }
+            .to_xdr(&env, &client.address)]
+        );
+    }
+
+    #[test]
+    fn test_init_with_contracts_registers_genesis_entries() {
+        let (env, client, admin, contract_addr) = setup_test();
+        let other_addr = Address::generate(&env);
+
+        let mut entries = Vec::new(&env);
+        entries.push_back((String::from_str(&env, "game"), contract_addr.clone(), 1u32));
+        entries.push_back((String::from_str(&env, "vault"), other_addr.clone(), 3u32));
+        client.init_with_contracts(&admin, &entries);
+
+        assert_eq!(client.get_admin(), admin);
+        assert_eq!(
+            client.resolve(&String::from_str(&env, "game")),
+            contract_addr
+        );
+        assert_eq!(client.get_version(&String::from_str(&env, "vault")), 3);
+        assert_eq!(client.snapshot().len(), 2);
+    }
+
+    #[test]
+    fn test_init_with_contracts_rejects_bad_entries_atomically() {
+        let (env, client, admin, contract_addr) = setup_test();
+
+        let mut duplicated = Vec::new(&env);
+        duplicated.push_back((String::from_str(&env, "game"), contract_addr.clone(), 1u32));
+        duplicated.push_back((String::from_str(&env, "game"), contract_addr.clone(), 2u32));
+        assert_eq!(
+            client.try_init_with_contracts(&admin, &duplicated),
+            Err(Ok(Error::DuplicateRegistration))
+        );
+
+        let mut invalid = Vec::new(&env);
+        invalid.push_back((String::from_str(&env, "game"), contract_addr.clone(), 1u32));
+        invalid.push_back((String::from_str(&env, ""), contract_addr.clone(), 1u32));
+        assert_eq!(
+            client.try_init_with_contracts(&admin, &invalid),
+            Err(Ok(Error::InvalidName))
+        );
+
+        // Nothing was committed, so the registry can still be initialized
+        assert_eq!(client.try_get_admin(), Err(Ok(Error::NotInitialized)));
+        init_registry(&client, &admin);
+        assert_eq!(
+            client.try_resolve(&String::from_str(&env, "game")),
+            Err(Ok(Error::ContractNotFound))
+        );
+    }
+
+    // ── Registration Tests ─────────────────────────────────────────────────
+
+    #[test]
+    fn test_register_success() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+        let version = 1u32;
+
+        client.register(&name, &contract_addr, &version, &None);
+
+        // Verify contract is registered
+        let resolved = client.resolve(&name);
+        assert_eq!(resolved, contract_addr);
+
+        // Verify version
+        let stored_version = client.get_version(&name);
+        assert_eq!(stored_version, version);
+    }
+
+    #[test]
+    fn test_register_emits_event() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+        client.register(&name, &contract_addr, &1, &None);
+
+        assert_eq!(
+            env.events().all().filter_by_contract(&client.address),
+            [ContractRegistered {
+                name,
+                address: contract_addr,
+                version: 1,
+                admin,
+            }
+            .to_xdr(&env, &client.address)]
+        );
+    }
+
+    #[test]
+    fn test_register_not_initialized() {
+        let (env, client, _, contract_addr) = setup_test();
+
+        let name = String::from_str(&env, "prize-pool");
+        let result = client.try_register(&name, &contract_addr, &1, &None);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_register_duplicate() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+
+        // First registration
+        client.register(&name, &contract_addr, &1, &None);
+
+        // Try to register again
+        let result = client.try_register(&name, &contract_addr, &1, &None);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_register_invalid_version_zero() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+        let result = client.try_register(&name, &contract_addr, &0, &None);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_register_rejects_account_address() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let account_addr = Address::from_str(
+            &env,
+            "GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF",
+        );
+        let name = String::from_str(&env, "prize-pool");
+        assert_eq!(
+            client.try_register(&name, &account_addr, &1, &None),
+            Err(Ok(Error::InvalidAddress))
+        );
+
+        client.register(&name, &contract_addr, &1, &None);
+        assert_eq!(client.resolve(&name), contract_addr);
+
+        assert_eq!(
+            client.try_update(&name, &account_addr, &2, &None, &false),
+            Err(Ok(Error::InvalidAddress))
+        );
+    }
+
+    #[test]
+    fn test_register_invalid_name_empty() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "");
+        let result = client.try_register(&name, &contract_addr, &1, &None);
+
+        assert!(result.is_err());
+    }
+
+    // ── Update Tests ───────────────────────────────────────────────────────
+
+    #[test]
+    fn test_update_success() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+        let new_addr = Address::generate(&env);
+
+        // Register initial version
+        client.register(&name, &contract_addr, &1, &None);
+
+        // Update to new version
+        client.update(&name, &new_addr, &2, &None, &false);
+
+        // Verify new address is resolved
+        let resolved = client.resolve(&name);
+        assert_eq!(resolved, new_addr);
+
+        // Verify new version
+        let version = client.get_version(&name);
+        assert_eq!(version, 2);
+    }
+
+    #[test]
+    fn test_update_emits_event() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+        let new_addr = Address::generate(&env);
+        client.register(&name, &contract_addr, &1, &None);
+        client.update(&name, &new_addr, &2, &None, &false);
+
+        assert_eq!(
+            env.events().all().filter_by_contract(&client.address),
+            [ContractUpdated {
+                name,
+                old_address: contract_addr,
+                new_address: new_addr,
+                old_version: 1,
+                new_version: 2,
+                admin,
+            }
+            .to_xdr(&env, &client.address)]
+        );
+    }
+
+    #[test]
+    fn test_update_same_address_rejected() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+        client.register(&name, &contract_addr, &1, &None);
+
+        let result = client.try_update(&name, &contract_addr, &2, &None, &false);
+        assert_eq!(result, Err(Ok(Error::NoChange)));
+        assert_eq!(client.get_version(&name), 1);
+
+        // An explicit force still records the new version
+        client.update(&name, &contract_addr, &2, &None, &true);
+        assert_eq!(client.get_version(&name), 2);
+        assert_eq!(client.resolve(&name), contract_addr);
+    }
+
+    #[test]
+    fn test_update_not_found() {
+        let (env, client, admin, _) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "nonexistent");
+        let new_addr = Address::generate(&env);
+
+        let result = client.try_update(&name, &new_addr, &2, &None, &false);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_update_invalid_version_not_incremental() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+
+        // Register initial version
+        client.register(&name, &contract_addr, &1, &None);
+
+        // Try to update with same version
+        let new_addr = Address::generate(&env);
+        let result = client.try_update(&name, &new_addr, &1, &None, &false);
+        assert!(result.is_err());
+
+        // Try to update with lower version
+        let result = client.try_update(&name, &new_addr, &0, &None, &false);
+        assert!(result.is_err());
+    }
+
+    // ── Snapshot Tests ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_snapshot_returns_current_records() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        assert_eq!(client.snapshot().len(), 0);
+
+        let pool = String::from_str(&env, "prize-pool");
+        let flip = String::from_str(&env, "coin-flip");
+        let gone = String::from_str(&env, "retired");
+        let flip_v2 = Address::generate(&env);
+        client.register(&pool, &contract_addr, &1, &None);
+        client.register(&flip, &Address::generate(&env), &1, &None);
+        client.register(&gone, &Address::generate(&env), &1, &None);
+        client.update(&flip, &flip_v2, &2, &None, &false);
+        client.deregister(&gone);
+
+        let snapshot = client.snapshot();
+        assert_eq!(snapshot.len(), 2);
+
+        let (name, record) = snapshot.get(0).unwrap();
+        assert_eq!(name, pool);
+        assert_eq!(record.address, contract_addr);
+
+        let (name, record) = snapshot.get(1).unwrap();
+        assert_eq!(name, flip);
+        assert_eq!(record.address, flip_v2);
+        assert_eq!(record.version, 2);
+    }
+
+    #[test]
+    fn test_snapshot_not_initialized() {
+        let (_env, client, _admin, _) = setup_test();
+        assert_eq!(client.try_snapshot(), Err(Ok(Error::NotInitialized)));
+    }
+
+    // ── Unique Address Tests ───────────────────────────────────────────────
+
+    #[test]
+    fn test_same_address_under_two_names_allowed_by_default() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let first = String::from_str(&env, "prize-pool");
+        let second = String::from_str(&env, "prize-pool-copy");
+        client.register(&first, &contract_addr, &1, &None);
+        client.register(&second, &contract_addr, &1, &None);
+
+        assert_eq!(client.resolve(&second), contract_addr);
+    }
+
+    #[test]
+    fn test_unique_address_enforcement_rejects_duplicates() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+        client.set_unique_address_enforcement(&true);
+
+        let first = String::from_str(&env, "prize-pool");
+        let second = String::from_str(&env, "coin-flip");
+        client.register(&first, &contract_addr, &1, &None);
+
+        assert_eq!(
+            client.try_register(&second, &contract_addr, &1, &None),
+            Err(Ok(Error::AddressAlreadyMapped))
+        );
+
+        let other_addr = Address::generate(&env);
+        client.register(&second, &other_addr, &1, &None);
+        assert_eq!(
+            client.try_update(&second, &contract_addr, &2, &None, &false),
+            Err(Ok(Error::AddressAlreadyMapped))
+        );
+
+        // A name may still be force-updated to its own current address
+        client.update(&first, &contract_addr, &2, &None, &true);
+
+        client.set_unique_address_enforcement(&false);
+        client.update(&second, &contract_addr, &2, &None, &false);
+        assert_eq!(client.resolve(&second), contract_addr);
+    }
+
+    // ── Pause Tests ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_pause_blocks_mutations_but_not_reads() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+        let new_addr = Address::generate(&env);
+        client.register(&name, &contract_addr, &1, &None);
+
+        client.pause();
+        assert!(client.is_paused());
+
+        let other = String::from_str(&env, "coin-flip");
+        assert_eq!(
+            client.try_register(&other, &contract_addr, &1, &None),
+            Err(Ok(Error::RegistryPaused))
+        );
+        assert_eq!(
+            client.try_update(&name, &new_addr, &2, &None, &false),
+            Err(Ok(Error::RegistryPaused))
+        );
+        assert_eq!(client.try_rollback(&name), Err(Ok(Error::RegistryPaused)));
+        assert_eq!(client.try_deregister(&name), Err(Ok(Error::RegistryPaused)));
+        assert_eq!(client.try_freeze(&name), Err(Ok(Error::RegistryPaused)));
+
+        // Reads stay live
+        assert_eq!(client.resolve(&name), contract_addr);
+        assert_eq!(client.history(&name).len(), 1);
+        assert_eq!(client.get_version(&name), 1);
+
+        client.unpause();
+        assert!(!client.is_paused());
+        client.update(&name, &new_addr, &2, &None, &false);
+        assert_eq!(client.resolve(&name), new_addr);
+    }
+
+    #[test]
+    fn test_pause_twice_and_unpause_when_live_fail() {
+        let (_env, client, admin, _) = setup_test();
+        init_registry(&client, &admin);
+
+        assert_eq!(client.try_unpause(), Err(Ok(Error::NotPaused)));
+        client.pause();
+        assert_eq!(client.try_pause(), Err(Ok(Error::AlreadyPaused)));
+    }
+
+    // ── Rollback Tests ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_rollback_restores_previous_version() {
+        let (env, client, admin, addr_v1) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+        let addr_v2 = Address::generate(&env);
+        client.register(&name, &addr_v1, &1, &None);
+        client.update(&name, &addr_v2, &2, &None, &false);
+
+        client.rollback(&name);
+        assert_eq!(
+            env.events().all().filter_by_contract(&client.address),
+            [ContractRolledBack {
+                name: name.clone(),
+                from_version: 2,
+                to_version: 1,
+                address: addr_v1.clone(),
+                admin,
+            }
+            .to_xdr(&env, &client.address)]
+        );
+
+        assert_eq!(client.resolve(&name), addr_v1);
+        assert_eq!(client.get_version(&name), 1);
+        assert_eq!(client.history(&name).len(), 1);
+
+        // The rolled-back version number can be used again
+        client.update(&name, &addr_v2, &2, &None, &false);
+        assert_eq!(client.resolve(&name), addr_v2);
+    }
+
+    #[test]
+    fn test_rollback_follows_non_sequential_versions() {
+        let (env, client, admin, addr_v1) = setup_test();
         init_registry(&client, &admin);
 
-        // Try to initialize again
-        let result = client.try_init(&admin);
-        assert!(result.is_err());
-    }
+        let name = String::from_str(&env, "coin-flip");
+        client.register(&name, &addr_v1, &1, &None);
+        client.update(&name, &Address::generate(&env), &5, &None, &false);
 
-    // ── Registration Tests ─────────────────────────────────────────────────
+        client.rollback(&name);
+        assert_eq!(client.resolve(&name), addr_v1);
+        assert_eq!(client.get_version(&name), 1);
+    }
 
     #[test]
-    fn test_register_success() {
+    fn test_rollback_single_version_fails() {
         let (env, client, admin, contract_addr) = setup_test();
         init_registry(&client, &admin);
 
         let name = String::from_str(&env, "prize-pool");
-        let version = 1u32;
+        client.register(&name, &contract_addr, &1, &None);
 
-        client.register(&name, &contract_addr, &version);
+        assert_eq!(client.try_rollback(&name), Err(Ok(Error::InvalidVersion)));
+    }
 
-        // Verify contract is registered
-        let resolved = client.resolve(&name);
-        assert_eq!(resolved, contract_addr);
+    #[test]
+    fn test_rollback_blocked_when_frozen() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
 
-        // Verify version
-        let stored_version = client.get_version(&name);
-        assert_eq!(stored_version, version);
+        let name = String::from_str(&env, "prize-pool");
+        client.register(&name, &contract_addr, &1, &None);
+        client.update(&name, &Address::generate(&env), &2, &None, &false);
+        client.freeze(&name);
+
+        assert_eq!(client.try_rollback(&name), Err(Ok(Error::NameFrozen)));
     }
 
+    // ── History Limit Tests ────────────────────────────────────────────────
+
     #[test]
-    fn test_register_not_initialized() {
-        let (env, client, _, contract_addr) = setup_test();
+    fn test_update_prunes_history_beyond_limit() {
+        let (env, client, admin, addr_v1) = setup_test();
+        init_registry(&client, &admin);
+        assert_eq!(client.max_history(), 0);
+        assert_eq!(
+            client.try_set_max_history(&1),
+            Err(Ok(Error::InvalidHistoryLimit))
+        );
 
         let name = String::from_str(&env, "prize-pool");
-        let result = client.try_register(&name, &contract_addr, &1);
+        client.register(&name, &addr_v1, &1, &None);
+        client.update(&name, &Address::generate(&env), &2, &None, &false);
+        let addr_v3 = Address::generate(&env);
+        client.update(&name, &addr_v3, &3, &None, &false);
 
-        assert!(result.is_err());
+        client.set_max_history(&3);
+        let addr_v4 = Address::generate(&env);
+        client.update(&name, &addr_v4, &4, &None, &false);
+        assert_eq!(
+            env.events().all().filter_by_contract(&client.address),
+            [
+                HistoryPruned {
+                    name: name.clone(),
+                    version: 1,
+                }
+                .to_xdr(&env, &client.address),
+                ContractUpdated {
+                    name: name.clone(),
+                    old_address: addr_v3,
+                    new_address: addr_v4.clone(),
+                    old_version: 3,
+                    new_version: 4,
+                    admin,
+                }
+                .to_xdr(&env, &client.address),
+            ]
+        );
+
+        let history = client.history(&name);
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.get(0).unwrap().version, 2);
+        assert_eq!(client.version_count(&name), 3);
+        assert_eq!(client.try_get_record_at(&name, &1), Err(Ok(Error::ContractNotFound)));
+        assert_eq!(client.resolve(&name), addr_v4);
+
+        // The retained window still supports rollback
+        client.rollback(&name);
+        assert_eq!(client.get_version(&name), 3);
     }
 
+    // ── Subscriber Tests ───────────────────────────────────────────────────
+
     #[test]
-    fn test_register_duplicate() {
-        let (env, client, admin, contract_addr) = setup_test();
+    fn test_subscribers_notified_on_every_change() {
+        let (env, client, admin, addr_v1) = setup_test();
         init_registry(&client, &admin);
 
+        let subscriber = env.register(MockSubscriber, ());
+        let mock = MockSubscriberClient::new(&env, &subscriber);
+        client.add_subscriber(&subscriber);
+        assert_eq!(client.subscribers(), vec![&env, subscriber.clone()]);
+        assert_eq!(
+            client.try_add_subscriber(&subscriber),
+            Err(Ok(Error::AlreadySubscribed))
+        );
+
         let name = String::from_str(&env, "prize-pool");
+        client.register(&name, &addr_v1, &1, &None);
+        assert_eq!(mock.last(), (0, None));
+
+        client.update(&name, &Address::generate(&env), &2, &None, &false);
+        assert_eq!(mock.last(), (1, Some(name.clone())));
+        client.rollback(&name);
+        client.deregister(&name);
+        assert_eq!(mock.last(), (3, Some(name.clone())));
+
+        client.remove_subscriber(&subscriber);
+        assert_eq!(client.subscribers().len(), 0);
+        assert_eq!(
+            client.try_remove_subscriber(&subscriber),
+            Err(Ok(Error::NotSubscribed))
+        );
+    }
 
-        // First registration
-        client.register(&name, &contract_addr, &1);
+    #[test]
+    fn test_failing_subscriber_does_not_block_update() {
+        let (env, client, admin, addr_v1) = setup_test();
+        init_registry(&client, &admin);
 
-        // Try to register again
-        let result = client.try_register(&name, &contract_addr, &1);
+        let failing = env.register(FailingSubscriber, ());
+        let healthy = env.register(MockSubscriber, ());
+        client.add_subscriber(&failing);
+        client.add_subscriber(&healthy);
 
-        assert!(result.is_err());
+        let name = String::from_str(&env, "prize-pool");
+        let addr_v2 = Address::generate(&env);
+        client.register(&name, &addr_v1, &1, &None);
+        client.update(&name, &addr_v2, &2, &None, &false);
+        assert_eq!(
+            env.events().all().filter_by_contract(&client.address),
+            [
+                ContractUpdated {
+                    name: name.clone(),
+                    old_address: addr_v1,
+                    new_address: addr_v2.clone(),
+                    old_version: 1,
+                    new_version: 2,
+                    admin,
+                }
+                .to_xdr(&env, &client.address),
+                SubscriberNotifyFailed {
+                    name: name.clone(),
+                    subscriber: failing,
+                }
+                .to_xdr(&env, &client.address),
+            ]
+        );
+
+        assert_eq!(client.resolve(&name), addr_v2);
+        assert_eq!(MockSubscriberClient::new(&env, &healthy).last().0, 1);
     }
 
+    // ── Deregister / Freeze Tests ──────────────────────────────────────────
+
     #[test]
-    fn test_register_invalid_version_zero() {
+    fn test_deregister_removes_name() {
         let (env, client, admin, contract_addr) = setup_test();
         init_registry(&client, &admin);
 
         let name = String::from_str(&env, "prize-pool");
-        let result = client.try_register(&name, &contract_addr, &0);
+        client.register(&name, &contract_addr, &1, &None);
+        client.deregister(&name);
 
-        assert!(result.is_err());
+        assert_eq!(client.try_resolve(&name), Err(Ok(Error::ContractNotFound)));
+        assert_eq!(
+            client.try_deregister(&name),
+            Err(Ok(Error::ContractNotFound))
+        );
+
+        // The name can be registered again afterwards
+        client.register(&name, &contract_addr, &1, &None);
+        assert_eq!(client.resolve(&name), contract_addr);
     }
 
     #[test]
-    fn test_register_invalid_name_empty() {
+    fn test_deregister_clears_guardian_freeze() {
         let (env, client, admin, contract_addr) = setup_test();
         init_registry(&client, &admin);
 
-        let name = String::from_str(&env, "");
-        let result = client.try_register(&name, &contract_addr, &1);
+        let guardian = Address::generate(&env);
+        client.set_guardian(&guardian, &true);
 
-        assert!(result.is_err());
+        let name = String::from_str(&env, "prize-pool");
+        client.register(&name, &contract_addr, &1, &None);
+        client.guardian_freeze(&guardian, &name);
+        client.deregister(&name);
+        assert!(!client.is_guardian_frozen(&name));
+
+        // A later registration of the name starts out resolvable
+        client.register(&name, &contract_addr, &1, &None);
+        assert_eq!(client.resolve(&name), contract_addr);
     }
 
-    // ── Update Tests ───────────────────────────────────────────────────────
-
     #[test]
-    fn test_update_success() {
+    fn test_freeze_blocks_update_and_deregister() {
         let (env, client, admin, contract_addr) = setup_test();
         init_registry(&client, &admin);
 
-        let name = String::from_str(&env, "prize-pool");
-        let new_addr = Address::generate(&env);
-
-        // Register initial version
-        client.register(&name, &contract_addr, &1);
+        let name = String::from_str(&env, "governance-token");
+        client.register(&name, &contract_addr, &1, &None);
+        assert!(!client.is_frozen(&name));
 
-        // Update to new version
-        client.update(&name, &new_addr, &2);
+        client.freeze(&name);
+        assert!(client.is_frozen(&name));
 
-        // Verify new address is resolved
-        let resolved = client.resolve(&name);
-        assert_eq!(resolved, new_addr);
+        let new_addr = Address::generate(&env);
+        assert_eq!(
+            client.try_update(&name, &new_addr, &2, &None, &false),
+            Err(Ok(Error::NameFrozen))
+        );
+        assert_eq!(client.try_deregister(&name), Err(Ok(Error::NameFrozen)));
 
-        // Verify new version
-        let version = client.get_version(&name);
-        assert_eq!(version, 2);
+        // Reads are unaffected
+        assert_eq!(client.resolve(&name), contract_addr);
+        assert_eq!(client.get_version(&name), 1);
     }
 
     #[test]
-    fn test_update_not_found() {
+    fn test_freeze_not_found() {
         let (env, client, admin, _) = setup_test();
         init_registry(&client, &admin);
 
         let name = String::from_str(&env, "nonexistent");
-        let new_addr = Address::generate(&env);
+        assert_eq!(client.try_freeze(&name), Err(Ok(Error::ContractNotFound)));
+        assert!(!client.is_frozen(&name));
+    }
 
-        let result = client.try_update(&name, &new_addr, &2);
-        assert!(result.is_err());
+    // ── Guardian Freeze Tests ──────────────────────────────────────────────
+
+    #[test]
+    fn test_guardian_freeze_blocks_resolve_until_admin_unfreezes() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let guardian = Address::generate(&env);
+        client.set_guardian(&guardian, &true);
+
+        let name = String::from_str(&env, "prize-pool");
+        client.register(&name, &contract_addr, &1, &None);
+
+        client.guardian_freeze(&guardian, &name);
+        assert_eq!(
+            env.events().all().filter_by_contract(&client.address),
+            [GuardianFrozen {
+                name: name.clone(),
+                guardian: guardian.clone(),
+            }
+            .to_xdr(&env, &client.address)]
+        );
+        assert!(client.is_guardian_frozen(&name));
+        assert_eq!(client.try_resolve(&name), Err(Ok(Error::ContractFrozen)));
+        assert_eq!(
+            client.try_resolve_record(&name),
+            Err(Ok(Error::ContractFrozen))
+        );
+        assert_eq!(
+            client.resolve_report(&Vec::from_array(&env, [name.clone()])),
+            Vec::from_array(&env, [(name.clone(), None)])
+        );
+
+        client.guardian_unfreeze(&name);
+        assert_eq!(client.resolve(&name), contract_addr);
+        assert_eq!(
+            client.try_guardian_unfreeze(&name),
+            Err(Ok(Error::NotGuardianFrozen))
+        );
     }
 
     #[test]
-    fn test_update_invalid_version_not_incremental() {
+    fn test_guardian_freeze_requires_guardian() {
         let (env, client, admin, contract_addr) = setup_test();
         init_registry(&client, &admin);
 
         let name = String::from_str(&env, "prize-pool");
+        client.register(&name, &contract_addr, &1, &None);
 
-        // Register initial version
-        client.register(&name, &contract_addr, &1);
+        let outsider = Address::generate(&env);
+        assert_eq!(
+            client.try_guardian_freeze(&outsider, &name),
+            Err(Ok(Error::NotAuthorized))
+        );
 
-        // Try to update with same version
-        let new_addr = Address::generate(&env);
-        let result = client.try_update(&name, &new_addr, &1);
-        assert!(result.is_err());
+        client.set_guardian(&outsider, &true);
+        client.set_guardian(&outsider, &false);
+        assert_eq!(
+            env.events().all().filter_by_contract(&client.address),
+            [GuardianUpdated {
+                guardian: outsider.clone(),
+                enabled: false,
+                admin: admin.clone(),
+            }
+            .to_xdr(&env, &client.address)]
+        );
+        assert!(!client.is_guardian(&outsider));
+        assert_eq!(
+            client.try_guardian_freeze(&outsider, &name),
+            Err(Ok(Error::NotAuthorized))
+        );
+    }
 
-        // Try to update with lower version
-        let result = client.try_update(&name, &new_addr, &0);
-        assert!(result.is_err());
+    // ── TTL Maintenance Tests ──────────────────────────────────────────────
+
+    #[test]
+    fn test_touch_extends_ttl_of_all_entries() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+        client.register(&name, &contract_addr, &1, &None);
+        client.update(&name, &Address::generate(&env), &2, &None, &false);
+
+        env.ledger().with_mut(|li| li.sequence_number += 10_000);
+        client.touch(&name);
+
+        env.as_contract(&client.address, || {
+            let storage = env.storage().persistent();
+            for key in [
+                DataKey::Contract(name.clone()),
+                DataKey::LatestVersion(name.clone()),
+                DataKey::ContractHistory(name.clone(), 1),
+                DataKey::ContractHistory(name.clone(), 2),
+            ] {
+                assert_eq!(storage.get_ttl(&key), PERSISTENT_BUMP_LEDGERS);
+            }
+        });
+    }
+
+    #[test]
+    fn test_touch_not_found() {
+        let (env, client, admin, _) = setup_test();
+        init_registry(&client, &admin);
+
+        assert_eq!(
+            client.try_touch(&String::from_str(&env, "nonexistent")),
+            Err(Ok(Error::ContractNotFound))
+        );
     }
 
     // ── Resolve Tests ──────────────────────────────────────────────────────
@@ -817,12 +2849,31 @@ mod test {
 
         let name = String::from_str(&env, "prize-pool");
 
-        client.register(&name, &contract_addr, &1);
+        client.register(&name, &contract_addr, &1, &None);
 
         let resolved = client.resolve(&name);
         assert_eq!(resolved, contract_addr);
     }
 
+    #[test]
+    fn test_resolve_report_flags_missing_names() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let present = String::from_str(&env, "prize-pool");
+        let missing = String::from_str(&env, "coin-flip");
+        client.register(&present, &contract_addr, &1, &None);
+
+        let mut names = Vec::new(&env);
+        names.push_back(present.clone());
+        names.push_back(missing.clone());
+
+        let report = client.resolve_report(&names);
+        assert_eq!(report.len(), 2);
+        assert_eq!(report.get(0).unwrap(), (present, Some(contract_addr)));
+        assert_eq!(report.get(1).unwrap(), (missing, None));
+    }
+
     #[test]
     fn test_resolve_not_found() {
         let (env, client, admin, _) = setup_test();
@@ -844,6 +2895,27 @@ mod test {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_resolve_record_returns_current_record() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+        let new_addr = Address::generate(&env);
+
+        client.register(&name, &contract_addr, &1, &None);
+        client.update(&name, &new_addr, &2, &None, &false);
+
+        let record = client.resolve_record(&name);
+        assert_eq!(record.address, new_addr);
+        assert_eq!(record.version, 2);
+        assert_eq!(record.registered_by, admin);
+        assert_eq!(record.registered_at, env.ledger().sequence());
+
+        let missing = client.try_resolve_record(&String::from_str(&env, "nonexistent"));
+        assert_eq!(missing, Err(Ok(Error::ContractNotFound)));
+    }
+
     // ── History Tests ──────────────────────────────────────────────────────
 
     #[test]
@@ -853,7 +2925,7 @@ mod test {
 
         let name = String::from_str(&env, "prize-pool");
 
-        client.register(&name, &contract_addr, &1);
+        client.register(&name, &contract_addr, &1, &None);
 
         let history = client.history(&name);
         assert_eq!(history.len(), 1);
@@ -871,13 +2943,13 @@ mod test {
         let addr_v3 = Address::generate(&env);
 
         // Register v1
-        client.register(&name, &contract_addr, &1);
+        client.register(&name, &contract_addr, &1, &None);
 
         // Update to v2
-        client.update(&name, &addr_v2, &2);
+        client.update(&name, &addr_v2, &2, &None, &false);
 
         // Update to v3
-        client.update(&name, &addr_v3, &3);
+        client.update(&name, &addr_v3, &3, &None, &false);
 
         let history = client.history(&name);
         assert_eq!(history.len(), 3);
@@ -893,6 +2965,58 @@ mod test {
         assert_eq!(history.get(2).unwrap().address, addr_v3);
     }
 
+    #[test]
+    fn test_history_page() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+        client.register(&name, &contract_addr, &1, &None);
+        for version in 2..=5u32 {
+            client.update(&name, &Address::generate(&env), &version, &None, &false);
+        }
+
+        let page = client.history_page(&name, &1, &2);
+        assert_eq!(page.len(), 2);
+        assert_eq!(page.get(0).unwrap().version, 1);
+        assert_eq!(page.get(1).unwrap().version, 2);
+
+        let page = client.history_page(&name, &4, &10);
+        assert_eq!(page.len(), 2);
+        assert_eq!(page.get(0).unwrap().version, 4);
+        assert_eq!(page.get(1).unwrap().version, 5);
+
+        // Past the latest version: empty, not an error
+        assert_eq!(client.history_page(&name, &6, &10).len(), 0);
+        assert_eq!(client.history_page(&name, &1, &0).len(), 0);
+
+        let missing = String::from_str(&env, "nonexistent");
+        assert_eq!(
+            client.try_history_page(&missing, &1, &10),
+            Err(Ok(Error::ContractNotFound))
+        );
+    }
+
+    #[test]
+    fn test_history_non_sequential_versions() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+        let addr_v500 = Address::generate(&env);
+
+        client.register(&name, &contract_addr, &10, &None);
+        client.update(&name, &addr_v500, &500, &None, &false);
+
+        let history = client.history(&name);
+        assert_eq!(history.len(), 2);
+        assert_eq!(history.get(0).unwrap().version, 10);
+        assert_eq!(history.get(0).unwrap().address, contract_addr);
+        assert_eq!(history.get(1).unwrap().version, 500);
+        assert_eq!(history.get(1).unwrap().address, addr_v500);
+        assert_eq!(client.get_version(&name), 500);
+    }
+
     #[test]
     fn test_history_not_found() {
         let (env, client, admin, _) = setup_test();
@@ -904,6 +3028,82 @@ mod test {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_get_record_at_specific_version() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+        let addr_v2 = Address::generate(&env);
+        let addr_v3 = Address::generate(&env);
+
+        client.register(&name, &contract_addr, &1, &None);
+        client.update(&name, &addr_v2, &2, &None, &false);
+        client.update(&name, &addr_v3, &3, &None, &false);
+
+        let record = client.get_record_at(&name, &2);
+        assert_eq!(record.version, 2);
+        assert_eq!(record.address, addr_v2);
+
+        assert_eq!(client.get_record_at(&name, &1).address, contract_addr);
+
+        let result = client.try_get_record_at(&name, &4);
+        assert_eq!(result, Err(Ok(Error::ContractNotFound)));
+
+        let result = client.try_get_record_at(&String::from_str(&env, "nonexistent"), &1);
+        assert_eq!(result, Err(Ok(Error::ContractNotFound)));
+    }
+
+    // ── Metadata Tests ─────────────────────────────────────────────────────
+
+    #[test]
+    fn test_metadata_register_and_update() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "pp-v2");
+        let description = String::from_str(&env, "Prize pool v2 with fee splitting");
+        client.register(&name, &contract_addr, &1, &Some(description.clone()));
+        assert_eq!(client.get_metadata(&name), Some(description.clone()));
+
+        // None on update keeps the existing description
+        let addr_v2 = Address::generate(&env);
+        client.update(&name, &addr_v2, &2, &None, &false);
+        assert_eq!(client.get_metadata(&name), Some(description));
+
+        let new_description = String::from_str(&env, "Prize pool v2.1 hotfix");
+        let addr_v3 = Address::generate(&env);
+        client.update(&name, &addr_v3, &3, &Some(new_description.clone()), &false);
+        assert_eq!(client.get_metadata(&name), Some(new_description));
+    }
+
+    #[test]
+    fn test_metadata_absent_and_not_found() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+        client.register(&name, &contract_addr, &1, &None);
+        assert_eq!(client.get_metadata(&name), None);
+
+        let result = client.try_get_metadata(&String::from_str(&env, "nonexistent"));
+        assert_eq!(result, Err(Ok(Error::ContractNotFound)));
+    }
+
+    #[test]
+    fn test_metadata_too_long_rejected() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+        let long = String::from_bytes(&env, &[b'a'; 257]);
+        let result = client.try_register(&name, &contract_addr, &1, &Some(long));
+        assert_eq!(result, Err(Ok(Error::DescriptionTooLong)));
+
+        let max = String::from_bytes(&env, &[b'a'; 256]);
+        client.register(&name, &contract_addr, &1, &Some(max));
+    }
+
     // ── Version Query Tests ────────────────────────────────────────────────
 
     #[test]
@@ -913,12 +3113,139 @@ mod test {
 
         let name = String::from_str(&env, "prize-pool");
 
-        client.register(&name, &contract_addr, &1);
+        client.register(&name, &contract_addr, &1, &None);
 
         let version = client.get_version(&name);
         assert_eq!(version, 1);
     }
 
+    #[test]
+    fn test_version_count() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+        client.register(&name, &contract_addr, &1, &None);
+        assert_eq!(client.version_count(&name), 1);
+
+        client.update(&name, &Address::generate(&env), &2, &None, &false);
+        client.update(&name, &Address::generate(&env), &7, &None, &false);
+
+        // Three records exist even though the latest version number is 7
+        assert_eq!(client.version_count(&name), 3);
+        assert_eq!(client.get_version(&name), 7);
+
+        let missing = String::from_str(&env, "nonexistent");
+        assert_eq!(
+            client.try_version_count(&missing),
+            Err(Ok(Error::ContractNotFound))
+        );
+    }
+
+    #[test]
+    fn test_version_report_and_missing_versions() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+
+        let name = String::from_str(&env, "prize-pool");
+        client.register(&name, &contract_addr, &1, &None);
+        assert_eq!(client.version_report(&name), (1, 1));
+        assert_eq!(
+            client.missing_versions(&name, &0, &10),
+            Vec::<(u32, u32)>::new(&env)
+        );
+
+        client.update(&name, &Address::generate(&env), &2, &None, &false);
+        client.update(&name, &Address::generate(&env), &5, &None, &false);
+        assert_eq!(client.version_report(&name), (5, 3));
+        assert_eq!(
+            client.missing_versions(&name, &0, &10),
+            vec![&env, (3u32, 4u32)]
+        );
+
+        client.update(&name, &Address::generate(&env), &8, &None, &false);
+        assert_eq!(
+            client.missing_versions(&name, &0, &10),
+            vec![&env, (3u32, 4u32), (6u32, 7u32)]
+        );
+        // Paging: a limit caps the ranges, a start version clips them
+        assert_eq!(
+            client.missing_versions(&name, &0, &1),
+            vec![&env, (3u32, 4u32)]
+        );
+        assert_eq!(
+            client.missing_versions(&name, &4, &10),
+            vec![&env, (4u32, 4u32), (6u32, 7u32)]
+        );
+        assert_eq!(
+            client.missing_versions(&name, &9, &10),
+            Vec::<(u32, u32)>::new(&env)
+        );
+
+        // A name first registered above v1 reports the leading gap too
+        let late = String::from_str(&env, "late-start");
+        client.register(&late, &Address::generate(&env), &3, &None);
+        assert_eq!(
+            client.missing_versions(&late, &0, &10),
+            vec![&env, (1u32, 2u32)]
+        );
+
+        // Versions up to u32::MAX do not overflow the walk
+        let top = String::from_str(&env, "top");
+        client.register(&top, &Address::generate(&env), &1, &None);
+        client.update(&top, &Address::generate(&env), &u32::MAX, &None, &false);
+        assert_eq!(
+            client.missing_versions(&top, &0, &10),
+            vec![&env, (2u32, u32::MAX - 1)]
+        );
+
+        let unknown = String::from_str(&env, "nonexistent");
+        assert_eq!(
+            client.try_version_report(&unknown),
+            Err(Ok(Error::ContractNotFound))
+        );
+        assert_eq!(
+            client.try_missing_versions(&unknown, &0, &10),
+            Err(Ok(Error::ContractNotFound))
+        );
+        assert_eq!(
+            client.try_pruned_through(&unknown),
+            Err(Ok(Error::ContractNotFound))
+        );
+    }
+
+    #[test]
+    fn test_missing_versions_skips_pruned_history() {
+        let (env, client, admin, contract_addr) = setup_test();
+        init_registry(&client, &admin);
+        client.set_max_history(&2);
+
+        let name = String::from_str(&env, "prize-pool");
+        client.register(&name, &contract_addr, &1, &None);
+        client.update(&name, &Address::generate(&env), &3, &None, &false);
+        assert_eq!(client.pruned_through(&name), 0);
+        assert_eq!(
+            client.missing_versions(&name, &0, &10),
+            vec![&env, (2u32, 2u32)]
+        );
+
+        // v1 is pruned and not reported; v2 was never recorded and still is
+        client.update(&name, &Address::generate(&env), &4, &None, &false);
+        assert_eq!(client.pruned_through(&name), 1);
+        assert_eq!(
+            client.missing_versions(&name, &0, &10),
+            vec![&env, (2u32, 2u32)]
+        );
+
+        // Once v3 is pruned, everything up to it is outside retained history
+        client.update(&name, &Address::generate(&env), &7, &None, &false);
+        assert_eq!(client.pruned_through(&name), 3);
+        assert_eq!(
+            client.missing_versions(&name, &0, &10),
+            vec![&env, (5u32, 6u32)]
+        );
+    }
+
     #[test]
     fn test_get_version_after_update() {
         let (env, client, admin, contract_addr) = setup_test();
@@ -926,10 +3253,10 @@ mod test {
 
         let name = String::from_str(&env, "prize-pool");
 
-        client.register(&name, &contract_addr, &1);
+        client.register(&name, &contract_addr, &1, &None);
 
         let new_addr = Address::generate(&env);
-        client.update(&name, &new_addr, &2);
+        client.update(&name, &new_addr, &2, &None, &false);
 
         let version = client.get_version(&name);
         assert_eq!(version, 2);
@@ -964,7 +3291,7 @@ mod test {
         let name = String::from_str(&env, "coin-flip");
 
         // Register initial version
-        client.register(&name, &contract_addr, &1);
+        client.register(&name, &contract_addr, &1, &None);
 
         // Verify resolution
         let addr = client.resolve(&name);
@@ -972,7 +3299,7 @@ mod test {
 
         // Update to v2
         let addr_v2 = Address::generate(&env);
-        client.update(&name, &addr_v2, &2);
+        client.update(&name, &addr_v2, &2, &None, &false);
 
         // Verify new resolution
         let addr = client.resolve(&name);
@@ -980,7 +3307,7 @@ mod test {
 
         // Update to v3
         let addr_v3 = Address::generate(&env);
-        client.update(&name, &addr_v3, &3);
+        client.update(&name, &addr_v3, &3, &None, &false);
 
         // Verify history
         let history = client.history(&name);
@@ -1006,7 +3333,7 @@ mod test {
         // Register multiple contracts
         for name in names.iter() {
             let addr = Address::generate(&env);
-            client.register(&name, &addr, &1);
+            client.register(&name, &addr, &1, &None);
 
             // Verify each one resolves correctly
             let resolved = client.resolve(&name);
@@ -1024,9 +3351,9 @@ mod test {
         let addr2 = Address::generate(&env);
         let addr3 = Address::generate(&env);
 
-        client.register(&String::from_str(&env, "prize-pool"), &addr1, &1);
-        client.register(&String::from_str(&env, "random-generator"), &addr2, &1);
-        client.register(&String::from_str(&env, "coin-flip"), &addr3, &1);
+        client.register(&String::from_str(&env, "prize-pool"), &addr1, &1, &None);
+        client.register(&String::from_str(&env, "random-generator"), &addr2, &1, &None);
+        client.register(&String::from_str(&env, "coin-flip"), &addr3, &1, &None);
 
         let report = client.validation_report();
         assert_eq!(report.issues.len(), 0);
@@ -1039,7 +3366,7 @@ mod test {
         init_registry(&client, &admin);
 
         // Only register one
-        client.register(&String::from_str(&env, "prize-pool"), &addr1, &1);
+        client.register(&String::from_str(&env, "prize-pool"), &addr1, &1, &None);
 
         let report = client.validation_report();
         // Should flag 2 missing (rng, coin-flip)
@@ -1053,9 +3380,9 @@ mod test {
         let (env, client, admin, addr1) = setup_test();
         init_registry(&client, &admin);
 
-        client.register(&String::from_str(&env, "prize-pool"), &addr1, &1);
+        client.register(&String::from_str(&env, "prize-pool"), &addr1, &1, &None);
         // Register another one with the SAME address
-        client.register(&String::from_str(&env, "other-alias"), &addr1, &1);
+        client.register(&String::from_str(&env, "other-alias"), &addr1, &1, &None);
 
         let report = client.validation_report();
         // Should flag 2 missing (rng, coin-flip) + 1 duplicate
@@ -1079,7 +3406,7 @@ mod test {
         let zero_strkey = String::from_str(&env, "CAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAD2KM");
         let placeholder_addr = Address::from_string(&zero_strkey);
 
-        client.register(&String::from_str(&env, "prize-pool"), &placeholder_addr, &1);
+        client.register(&String::from_str(&env, "prize-pool"), &placeholder_addr, &1, &None);
 
         let report = client.validation_report();
         // Should flag 2 missing + 1 placeholder
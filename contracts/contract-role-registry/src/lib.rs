@@ -1,12 +1,15 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractevent, contractimpl, contracttype, Address, Env, Symbol, Vec};
+use soroban_sdk::{
+    contract, contractevent, contractimpl, contracttype, Address, BytesN, Env, Symbol, Vec,
+};
 
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
     Admin,
     Role(Address, Symbol),
+    MigrationSeeded,
 }
 
 #[contractevent(topics = ["role_assigned"])]
@@ -27,6 +30,14 @@ pub struct RoleRevoked {
     pub role: Symbol,
 }
 
+#[contractevent(topics = ["migration_seeded"])]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MigrationSeeded {
+    #[topic]
+    pub admin: Address,
+    pub grants: u32,
+}
+
 #[contract]
 pub struct ContractRoleRegistry;
 
@@ -38,6 +49,10 @@ impl ContractRoleRegistry {
             panic!("Already initialized");
         }
         env.storage().instance().set(&DataKey::Admin, &admin);
+        // Nothing to migrate on a fresh deploy; only `upgrade` arms the seed.
+        env.storage()
+            .instance()
+            .set(&DataKey::MigrationSeeded, &true);
     }
 
     /// Assigns a role to a given address. Requires admin authorization.
@@ -121,6 +136,59 @@ impl ContractRoleRegistry {
             }
         }
     }
+
+    /// Seeds roles in bulk after the registry has been migrated behind an
+    /// upgrade. `admin` must be the stored admin and authorize the call.
+    /// Guarded by a one-shot flag that `init` sets and `upgrade` clears, so it
+    /// succeeds only once after each upgrade.
+    pub fn migrate_seed(env: Env, admin: Address, grants: Vec<(Address, Symbol)>) {
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Not initialized");
+        if admin != stored_admin {
+            panic!("Not authorized");
+        }
+        admin.require_auth();
+
+        if env.storage().instance().has(&DataKey::MigrationSeeded) {
+            panic!("Migration seed already applied");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::MigrationSeeded, &true);
+
+        let count = grants.len();
+        for (target, role) in grants.into_iter() {
+            let key = DataKey::Role(target.clone(), role.clone());
+            if !env.storage().persistent().has(&key) {
+                env.storage().persistent().set(&key, &());
+                RoleAssigned { target, role }.publish(&env);
+            }
+        }
+
+        MigrationSeeded {
+            admin,
+            grants: count,
+        }
+        .publish(&env);
+    }
+
+    /// Replaces the contract code with the uploaded `new_wasm_hash`. Requires
+    /// admin authorization. Clears the `migrate_seed` flag so the upgraded
+    /// code gets its own one-shot seed.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("Not initialized");
+        admin.require_auth();
+
+        env.storage().instance().remove(&DataKey::MigrationSeeded);
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+    }
 }
 #[cfg(test)]
 mod test;
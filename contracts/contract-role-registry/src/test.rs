@@ -2,7 +2,7 @@
 
 use super::*;
 use soroban_sdk::testutils::Address as _;
-use soroban_sdk::{symbol_short, Env};
+use soroban_sdk::{symbol_short, Bytes, Env};
 
 #[test]
 fn test_init_and_admin() {
@@ -41,13 +41,13 @@ fn test_role_assignment() {
 
     client.init(&admin);
 
-    assert_eq!(client.has_role(&target, &role), false);
+    assert!(!client.has_role(&target, &role));
 
     client.assign_role(&target, &role);
-    assert_eq!(client.has_role(&target, &role), true);
+    assert!(client.has_role(&target, &role));
 
     client.revoke_role(&target, &role);
-    assert_eq!(client.has_role(&target, &role), false);
+    assert!(!client.has_role(&target, &role));
 }
 
 #[test]
@@ -88,18 +88,18 @@ fn test_bulk_role_assignment() {
     assignments.push_back((target1.clone(), role1.clone()));
     assignments.push_back((target2.clone(), role2.clone()));
 
-    assert_eq!(client.has_role(&target1, &role1), false);
-    assert_eq!(client.has_role(&target2, &role2), false);
+    assert!(!client.has_role(&target1, &role1));
+    assert!(!client.has_role(&target2, &role2));
 
     client.bulk_assign_role(&assignments);
 
-    assert_eq!(client.has_role(&target1, &role1), true);
-    assert_eq!(client.has_role(&target2, &role2), true);
+    assert!(client.has_role(&target1, &role1));
+    assert!(client.has_role(&target2, &role2));
 
     client.bulk_revoke_role(&assignments);
 
-    assert_eq!(client.has_role(&target1, &role1), false);
-    assert_eq!(client.has_role(&target2, &role2), false);
+    assert!(!client.has_role(&target1, &role1));
+    assert!(!client.has_role(&target2, &role2));
 }
 
 #[test]
@@ -123,3 +123,121 @@ fn test_unauthorized_bulk_assignment() {
     // This should panic because admin auth is missing.
     client.bulk_assign_role(&assignments);
 }
+
+#[test]
+fn test_migrate_seed_assigns_roles_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let target1 = Address::generate(&env);
+    let target2 = Address::generate(&env);
+    let role1 = symbol_short!("GAME");
+    let role2 = symbol_short!("ORACLE");
+
+    let contract_id = env.register(ContractRoleRegistry, ());
+    let client = ContractRoleRegistryClient::new(&env, &contract_id);
+
+    client.init(&admin);
+    client.upgrade(&env.crypto().sha256(&Bytes::new(&env)).into());
+
+    let mut grants = soroban_sdk::Vec::new(&env);
+    grants.push_back((target1.clone(), role1.clone()));
+    grants.push_back((target2.clone(), role2.clone()));
+
+    client.migrate_seed(&admin, &grants);
+
+    assert!(client.has_role(&target1, &role1));
+    assert!(client.has_role(&target2, &role2));
+
+    // The one-shot flag rejects a second seed
+    assert!(client.try_migrate_seed(&admin, &grants).is_err());
+}
+
+#[test]
+fn test_migrate_seed_rejected_before_upgrade() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let target = Address::generate(&env);
+
+    let contract_id = env.register(ContractRoleRegistry, ());
+    let client = ContractRoleRegistryClient::new(&env, &contract_id);
+
+    client.init(&admin);
+
+    let mut grants = soroban_sdk::Vec::new(&env);
+    grants.push_back((target.clone(), symbol_short!("GAME")));
+
+    // No upgrade has happened, so there is nothing to seed
+    assert!(client.try_migrate_seed(&admin, &grants).is_err());
+    assert!(!client.has_role(&target, &symbol_short!("GAME")));
+}
+
+#[test]
+#[should_panic(expected = "Not authorized")]
+fn test_migrate_seed_rejects_non_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let intruder = Address::generate(&env);
+
+    let contract_id = env.register(ContractRoleRegistry, ());
+    let client = ContractRoleRegistryClient::new(&env, &contract_id);
+
+    client.init(&admin);
+
+    let mut grants = soroban_sdk::Vec::new(&env);
+    grants.push_back((intruder.clone(), symbol_short!("ADMIN")));
+
+    client.migrate_seed(&intruder, &grants);
+}
+
+#[test]
+fn test_upgrade_rearms_migrate_seed() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let target1 = Address::generate(&env);
+    let target2 = Address::generate(&env);
+
+    let contract_id = env.register(ContractRoleRegistry, ());
+    let client = ContractRoleRegistryClient::new(&env, &contract_id);
+
+    client.init(&admin);
+
+    // Natively registered test contracts run under the hash of empty code
+    let wasm_hash: BytesN<32> = env.crypto().sha256(&Bytes::new(&env)).into();
+    client.upgrade(&wasm_hash);
+
+    let mut grants = soroban_sdk::Vec::new(&env);
+    grants.push_back((target1.clone(), symbol_short!("GAME")));
+    client.migrate_seed(&admin, &grants);
+    assert!(client.try_migrate_seed(&admin, &grants).is_err());
+
+    client.upgrade(&wasm_hash);
+
+    let mut grants = soroban_sdk::Vec::new(&env);
+    grants.push_back((target2.clone(), symbol_short!("ORACLE")));
+    client.migrate_seed(&admin, &grants);
+    assert!(client.has_role(&target2, &symbol_short!("ORACLE")));
+    assert!(client.try_migrate_seed(&admin, &grants).is_err());
+}
+
+#[test]
+#[should_panic]
+fn test_upgrade_requires_admin_auth() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+
+    let contract_id = env.register(ContractRoleRegistry, ());
+    let client = ContractRoleRegistryClient::new(&env, &contract_id);
+
+    client.init(&admin);
+
+    // No auth is mocked, so the admin check fails.
+    client.upgrade(&env.crypto().sha256(&Bytes::new(&env)).into());
+}
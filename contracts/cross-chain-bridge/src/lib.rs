@@ -1,7 +1,7 @@
 #![no_std]
 use soroban_sdk::{
     contract, contracterror, contractevent, contractimpl, contracttype,
-    token, Address, BytesN, Env, Map, String, Symbol, Vec,
+    token, vec, xdr::ToXdr, Address, Bytes, BytesN, Env, IntoVal, Map, String, Symbol, Vec,
 };
 
 #[contracterror]
@@ -19,6 +19,8 @@ pub enum Error {
     ContractPaused = 9,
     InvalidQuorum = 10,
     InvalidSignature = 11,
+    TooManySignatures = 12,
+    UnsupportedMessageVersion = 13,
 }
 
 #[contracttype]
@@ -31,8 +33,21 @@ pub enum DataKey {
     WrappedTokenMapping(Address),
     ProcessedProofs(BytesN<32>),
     Paused,
+    MaxSignatures,
+    LockedBalance(Address),
+    MessageVersions,
 }
 
+/// Default cap on a `signatures` map, as a multiple of the validator count.
+const DEFAULT_SIGNATURES_PER_VALIDATOR: u32 = 2;
+
+/// Signed-message layout accepted until the admin configures others.
+pub const DEFAULT_MESSAGE_VERSION: u32 = 1;
+
+/// Domain tag in the `release_with_call` digest, so a signature over a
+/// plain release cannot authorize a callback.
+const RELEASE_WITH_CALL_TAG: &[u8] = b"release_with_call";
+
 // ── Events ────────────────────────────────────────────────────────
 #[contractevent]
 pub struct BridgeInitialized {
@@ -104,6 +119,10 @@ impl CrossChainBridge {
         env.storage().instance().set(&DataKey::Validators, &validators);
         env.storage().instance().set(&DataKey::Quorum, &quorum);
         env.storage().instance().set(&DataKey::Paused, &false);
+        env.storage().instance().set(
+            &DataKey::MessageVersions,
+            &vec![&env, DEFAULT_MESSAGE_VERSION],
+        );
 
         BridgeInitialized { admin, quorum }.publish(&env);
         Ok(())
@@ -116,6 +135,52 @@ impl CrossChainBridge {
         Ok(())
     }
 
+    /// Cap the number of entries accepted in a `signatures` map. Must be at
+    /// least the quorum. Until set, the cap is twice the validator count.
+    pub fn set_max_signatures(env: Env, max: u32) -> Result<(), Error> {
+        require_admin(&env)?;
+        let quorum: u32 =
+            env.storage().instance().get(&DataKey::Quorum).ok_or(Error::NotAuthorized)?;
+        if max < quorum {
+            return Err(Error::InvalidQuorum);
+        }
+        env.storage().instance().set(&DataKey::MaxSignatures, &max);
+        Ok(())
+    }
+
+    pub fn max_signatures(env: Env) -> u32 {
+        max_signatures(&env)
+    }
+
+    /// Replace the set of signed-message versions accepted on inbound
+    /// transfers. During a rollout both the old and new version can be
+    /// accepted, then the old one dropped once validators have switched.
+    pub fn set_message_versions(env: Env, versions: Vec<u32>) -> Result<(), Error> {
+        require_admin(&env)?;
+        if versions.is_empty() {
+            return Err(Error::UnsupportedMessageVersion);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::MessageVersions, &versions);
+        Ok(())
+    }
+
+    pub fn message_versions(env: Env) -> Vec<u32> {
+        message_versions(&env)
+    }
+
+    /// Net amount of `asset` locked through `lock` minus everything released.
+    ///
+    /// Unlike the raw token balance this ignores tokens sent to the bridge
+    /// directly, so monitoring can compare the two to detect drift.
+    pub fn locked_of(env: Env, asset: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LockedBalance(asset))
+            .unwrap_or(0)
+    }
+
     pub fn set_paused(env: Env, paused: bool) -> Result<(), Error> {
         require_admin(&env)?;
         env.storage().instance().set(&DataKey::Paused, &paused);
@@ -137,7 +202,8 @@ impl CrossChainBridge {
         from.require_auth();
 
         let client = token::Client::new(&env, &asset);
-        client.transfer(&from, &env.current_contract_address(), &amount);
+        client.transfer(&from, env.current_contract_address(), &amount);
+        adjust_locked(&env, &asset, amount)?;
 
         TokenLocked {
             asset,
@@ -155,11 +221,15 @@ impl CrossChainBridge {
         asset_symbol: Symbol,
         amount: i128,
         recipient: Address,
+        message_version: u32,
         proof: BytesN<32>,
         signatures: Map<BytesN<32>, BytesN<64>>,
     ) -> Result<(), Error> {
         ensure_not_paused(&env)?;
-        verify_quorum(&env, &proof, &signatures)?;
+        ensure_signature_count(&env, &signatures)?;
+        require_message_version(&env, message_version)?;
+        let digest = message_digest(&env, message_version, &proof);
+        verify_quorum(&env, &digest, &signatures)?;
         mark_processed(&env, &proof)?;
 
         let asset_address: Address = env
@@ -218,15 +288,20 @@ impl CrossChainBridge {
         asset: Address,
         amount: i128,
         recipient: Address,
+        message_version: u32,
         proof: BytesN<32>,
         signatures: Map<BytesN<32>, BytesN<64>>,
     ) -> Result<(), Error> {
         ensure_not_paused(&env)?;
-        verify_quorum(&env, &proof, &signatures)?;
+        ensure_signature_count(&env, &signatures)?;
+        require_message_version(&env, message_version)?;
+        let digest = message_digest(&env, message_version, &proof);
+        verify_quorum(&env, &digest, &signatures)?;
         mark_processed(&env, &proof)?;
 
         let client = token::Client::new(&env, &asset);
         client.transfer(&env.current_contract_address(), &recipient, &amount);
+        adjust_locked(&env, &asset, -amount)?;
 
         TokenReleased {
             asset,
@@ -237,10 +312,167 @@ impl CrossChainBridge {
         .publish(&env);
         Ok(())
     }
+
+    /// Release locked assets to a contract and invoke `callback_fn` on it.
+    ///
+    /// The transfer happens first, then `callback_fn(asset, amount)` is called
+    /// on `recipient`. If the callback fails the whole release reverts,
+    /// including the transfer and the processed-proof mark. Validators sign
+    /// the digest returned by `call_digest`, which binds the transfer and the
+    /// callback.
+    #[allow(clippy::too_many_arguments)]
+    pub fn release_with_call(
+        env: Env,
+        asset: Address,
+        amount: i128,
+        recipient: Address,
+        callback_fn: Symbol,
+        message_version: u32,
+        proof: BytesN<32>,
+        signatures: Map<BytesN<32>, BytesN<64>>,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        ensure_signature_count(&env, &signatures)?;
+        require_message_version(&env, message_version)?;
+        let digest = call_digest(
+            &env,
+            message_version,
+            &asset,
+            amount,
+            &recipient,
+            &callback_fn,
+            &proof,
+        );
+        verify_quorum(&env, &digest, &signatures)?;
+        mark_processed(&env, &proof)?;
+
+        let client = token::Client::new(&env, &asset);
+        client.transfer(&env.current_contract_address(), &recipient, &amount);
+        adjust_locked(&env, &asset, -amount)?;
+
+        env.invoke_contract::<()>(
+            &recipient,
+            &callback_fn,
+            vec![&env, asset.into_val(&env), amount.into_val(&env)],
+        );
+
+        TokenReleased {
+            asset,
+            recipient,
+            amount,
+            proof,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Release several locked transfers under a single validator proof.
+    ///
+    /// Each entry is `(asset, amount, recipient)`. Validators sign the digest
+    /// returned by `batch_digest`, which commits to the message version, every
+    /// entry and `proof` as the batch nonce. Any failing transfer reverts the whole batch.
+    pub fn release_batch(
+        env: Env,
+        entries: Vec<(Address, i128, Address)>,
+        message_version: u32,
+        proof: BytesN<32>,
+        signatures: Map<BytesN<32>, BytesN<64>>,
+    ) -> Result<(), Error> {
+        ensure_not_paused(&env)?;
+        ensure_signature_count(&env, &signatures)?;
+        if entries.is_empty() {
+            return Err(Error::InvalidAmount);
+        }
+        for (_, amount, _) in entries.iter() {
+            if amount <= 0 {
+                return Err(Error::InvalidAmount);
+            }
+        }
+
+        require_message_version(&env, message_version)?;
+        let digest = batch_digest(&env, message_version, &entries, &proof);
+        verify_quorum(&env, &digest, &signatures)?;
+        mark_processed(&env, &proof)?;
+
+        for (asset, amount, recipient) in entries.iter() {
+            let client = token::Client::new(&env, &asset);
+            client.transfer(&env.current_contract_address(), &recipient, &amount);
+            adjust_locked(&env, &asset, -amount)?;
+
+            TokenReleased {
+                asset,
+                recipient,
+                amount,
+                proof: proof.clone(),
+            }
+            .publish(&env);
+        }
+        Ok(())
+    }
 }
 
 // --- Internal Helpers ---
 
+/// Digest validators sign for single transfers:
+/// sha256(be_bytes(message_version) || proof).
+fn message_digest(env: &Env, message_version: u32, proof: &BytesN<32>) -> BytesN<32> {
+    let mut payload = Bytes::from_array(env, &message_version.to_be_bytes());
+    payload.append(&Bytes::from(proof.clone()));
+    env.crypto().sha256(&payload).into()
+}
+
+/// Digest validators sign for `release_batch`:
+/// sha256(be_bytes(message_version) || xdr(entries) || proof).
+fn batch_digest(
+    env: &Env,
+    message_version: u32,
+    entries: &Vec<(Address, i128, Address)>,
+    proof: &BytesN<32>,
+) -> BytesN<32> {
+    let mut payload = Bytes::from_array(env, &message_version.to_be_bytes());
+    payload.append(&entries.clone().to_xdr(env));
+    payload.append(&Bytes::from(proof.clone()));
+    env.crypto().sha256(&payload).into()
+}
+
+/// Digest validators sign for `release_with_call`:
+/// sha256(be_bytes(message_version) || "release_with_call" ||
+/// xdr((asset, amount, recipient, callback_fn)) || proof).
+///
+/// The callback arguments are always `(asset, amount)`, so the tuple covers
+/// everything the call does.
+fn call_digest(
+    env: &Env,
+    message_version: u32,
+    asset: &Address,
+    amount: i128,
+    recipient: &Address,
+    callback_fn: &Symbol,
+    proof: &BytesN<32>,
+) -> BytesN<32> {
+    let mut payload = Bytes::from_array(env, &message_version.to_be_bytes());
+    payload.append(&Bytes::from_slice(env, RELEASE_WITH_CALL_TAG));
+    let call = (asset.clone(), amount, recipient.clone(), callback_fn.clone());
+    payload.append(&call.to_xdr(env));
+    payload.append(&Bytes::from(proof.clone()));
+    env.crypto().sha256(&payload).into()
+}
+
+/// Reject proofs built against a message layout the bridge does not accept.
+fn require_message_version(env: &Env, message_version: u32) -> Result<(), Error> {
+    if !message_versions(env).contains(message_version) {
+        return Err(Error::UnsupportedMessageVersion);
+    }
+    Ok(())
+}
+
+fn message_versions(env: &Env) -> Vec<u32> {
+    env.storage()
+        .instance()
+        .get(&DataKey::MessageVersions)
+        .unwrap_or(vec![env, DEFAULT_MESSAGE_VERSION])
+}
+
 fn ensure_not_paused(env: &Env) -> Result<(), Error> {
     let paused: bool = env.storage().instance().get(&DataKey::Paused).unwrap_or(false);
     if paused {
@@ -256,6 +488,26 @@ fn require_admin(env: &Env) -> Result<(), Error> {
     Ok(())
 }
 
+fn max_signatures(env: &Env) -> u32 {
+    if let Some(max) = env.storage().instance().get(&DataKey::MaxSignatures) {
+        return max;
+    }
+    let validators: Vec<BytesN<32>> =
+        env.storage().instance().get(&DataKey::Validators).unwrap_or(Vec::new(env));
+    validators.len().saturating_mul(DEFAULT_SIGNATURES_PER_VALIDATOR)
+}
+
+/// Reject oversized maps before any Ed25519 verification burns budget.
+fn ensure_signature_count(
+    env: &Env,
+    signatures: &Map<BytesN<32>, BytesN<64>>,
+) -> Result<(), Error> {
+    if signatures.len() > max_signatures(env) {
+        return Err(Error::TooManySignatures);
+    }
+    Ok(())
+}
+
 fn verify_quorum(
     env: &Env,
     proof: &BytesN<32>,
@@ -290,6 +542,14 @@ fn verify_quorum(
     Ok(())
 }
 
+fn adjust_locked(env: &Env, asset: &Address, delta: i128) -> Result<(), Error> {
+    let key = DataKey::LockedBalance(asset.clone());
+    let locked: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    let locked = locked.checked_add(delta).ok_or(Error::Overflow)?;
+    env.storage().persistent().set(&key, &locked);
+    Ok(())
+}
+
 fn mark_processed(env: &Env, proof: &BytesN<32>) -> Result<(), Error> {
     if env.storage().persistent().has(&DataKey::ProcessedProofs(proof.clone())) {
         return Err(Error::ProofAlreadyProcessed);
@@ -346,16 +606,129 @@ mod test {
 
         let proof_bytes = [7u8; 32];
         let proof = BytesN::from_array(&env, &proof_bytes);
-        let signature_bytes = signing_key.sign(&proof_bytes).to_bytes();
+        let signature_bytes = signing_key
+            .sign(&message_digest(&env, DEFAULT_MESSAGE_VERSION, &proof).to_array())
+            .to_bytes();
         let sig = BytesN::from_array(&env, &signature_bytes);
 
         let mut sigs = Map::new(&env);
         sigs.set(validator_pk, sig);
 
-        client.release(&token_addr, &300, &user, &proof, &sigs);
+        client.release(
+            &token_addr,
+            &300,
+            &user,
+            &DEFAULT_MESSAGE_VERSION,
+            &proof,
+            &sigs,
+        );
         assert_eq!(token_client.balance(&user), 700);
     }
 
+    #[test]
+    fn test_locked_of_tracks_lock_and_release() {
+        let env = Env::default();
+        let (client, _, bridge_addr, validator_pk, signing_key) = setup(&env);
+        env.mock_all_auths();
+
+        let user = Address::generate(&env);
+        let token_addr = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+        let token_sac = StellarAssetClient::new(&env, &token_addr);
+        token_sac.mint(&user, &1000);
+        assert_eq!(client.locked_of(&token_addr), 0);
+
+        client.lock(&user, &token_addr, &600, &symbol_short!("SOL"), &String::from_str(&env, "0xabc"));
+        client.lock(&user, &token_addr, &150, &symbol_short!("SOL"), &String::from_str(&env, "0xabc"));
+        assert_eq!(client.locked_of(&token_addr), 750);
+
+        // Tokens sent to the bridge outside `lock` are not counted
+        token_sac.mint(&bridge_addr, &40);
+        assert_eq!(client.locked_of(&token_addr), 750);
+
+        let proof_bytes = [8u8; 32];
+        let proof = BytesN::from_array(&env, &proof_bytes);
+        let sig = BytesN::from_array(
+            &env,
+            &signing_key
+                .sign(&message_digest(&env, DEFAULT_MESSAGE_VERSION, &proof).to_array())
+                .to_bytes(),
+        );
+        let mut sigs = Map::new(&env);
+        sigs.set(validator_pk.clone(), sig);
+        client.release(
+            &token_addr,
+            &300,
+            &user,
+            &DEFAULT_MESSAGE_VERSION,
+            &proof,
+            &sigs,
+        );
+        assert_eq!(client.locked_of(&token_addr), 450);
+
+        let entries = Vec::from_array(&env, [(token_addr.clone(), 200i128, user.clone())]);
+        let proof = BytesN::from_array(&env, &[9u8; 32]);
+        let digest = batch_digest(&env, DEFAULT_MESSAGE_VERSION, &entries, &proof);
+        let sig = BytesN::from_array(&env, &signing_key.sign(&digest.to_array()).to_bytes());
+        let mut sigs = Map::new(&env);
+        sigs.set(validator_pk, sig);
+        client.release_batch(&entries, &DEFAULT_MESSAGE_VERSION, &proof, &sigs);
+        assert_eq!(client.locked_of(&token_addr), 250);
+    }
+
+    #[test]
+    fn test_message_version_rollout() {
+        let env = Env::default();
+        let (client, _, bridge_addr, validator_pk, signing_key) = setup(&env);
+        env.mock_all_auths();
+
+        let user = Address::generate(&env);
+        let token_addr = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let token_client = TokenClient::new(&env, &token_addr);
+        StellarAssetClient::new(&env, &token_addr).mint(&bridge_addr, &500);
+        assert_eq!(
+            client.message_versions(),
+            vec![&env, DEFAULT_MESSAGE_VERSION]
+        );
+
+        // Version 2 is not accepted yet, whatever the validators signed
+        let proof = BytesN::from_array(&env, &[51u8; 32]);
+        let sig = signing_key
+            .sign(&message_digest(&env, 2, &proof).to_array())
+            .to_bytes();
+        let mut sigs = Map::new(&env);
+        sigs.set(validator_pk.clone(), BytesN::from_array(&env, &sig));
+        let result = client.try_release(&token_addr, &100, &user, &2u32, &proof, &sigs);
+        assert_eq!(result, Err(Ok(Error::UnsupportedMessageVersion)));
+
+        // Coordinated rollout: accept both, then only the new version
+        client.set_message_versions(&vec![&env, 1u32, 2u32]);
+        client.release(&token_addr, &100, &user, &2u32, &proof, &sigs);
+        assert_eq!(token_client.balance(&user), 100);
+
+        client.set_message_versions(&vec![&env, 2u32]);
+        let old_proof = BytesN::from_array(&env, &[52u8; 32]);
+        let old_sig = signing_key
+            .sign(&message_digest(&env, DEFAULT_MESSAGE_VERSION, &old_proof).to_array())
+            .to_bytes();
+        let mut old_sigs = Map::new(&env);
+        old_sigs.set(validator_pk, BytesN::from_array(&env, &old_sig));
+        let result = client.try_release(
+            &token_addr,
+            &100,
+            &user,
+            &DEFAULT_MESSAGE_VERSION,
+            &old_proof,
+            &old_sigs,
+        );
+        assert_eq!(result, Err(Ok(Error::UnsupportedMessageVersion)));
+        assert_eq!(
+            client.try_set_message_versions(&Vec::new(&env)),
+            Err(Ok(Error::UnsupportedMessageVersion))
+        );
+    }
+
     #[test]
     #[should_panic(expected = "HostError: Error(Crypto, InvalidInput)")]
     fn test_release_with_invalid_sig() {
@@ -371,7 +744,14 @@ mod test {
         let mut sigs = Map::new(&env);
         sigs.set(validator_pk, bad_sig);
 
-        client.release(&token_addr, &100, &user, &proof, &sigs);
+        client.release(
+            &token_addr,
+            &100,
+            &user,
+            &DEFAULT_MESSAGE_VERSION,
+            &proof,
+            &sigs,
+        );
     }
 
     #[test]
@@ -389,13 +769,303 @@ mod test {
 
         let proof_bytes = [11u8; 32];
         let proof = BytesN::from_array(&env, &proof_bytes);
-        let signature_bytes = signing_key.sign(&proof_bytes).to_bytes();
+        let signature_bytes = signing_key
+            .sign(&message_digest(&env, DEFAULT_MESSAGE_VERSION, &proof).to_array())
+            .to_bytes();
         let sig = BytesN::from_array(&env, &signature_bytes);
 
         let mut sigs = Map::new(&env);
         sigs.set(validator_pk, sig);
 
-        client.mint_wrapped(&eth_symbol, &1000, &user, &proof, &sigs);
+        client.mint_wrapped(
+            &eth_symbol,
+            &1000,
+            &user,
+            &DEFAULT_MESSAGE_VERSION,
+            &proof,
+            &sigs,
+        );
         assert_eq!(token_client.balance(&user), 1000);
     }
+
+    #[test]
+    fn test_release_rejects_oversized_signature_map() {
+        let env = Env::default();
+        let (client, _, _, validator_pk, _) = setup(&env);
+        env.mock_all_auths();
+
+        let user = Address::generate(&env);
+        let token_addr = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+        let proof = BytesN::from_array(&env, &[31u8; 32]);
+
+        // One validator gives a default cap of two entries
+        assert_eq!(client.max_signatures(), 2);
+        let junk = BytesN::from_array(&env, &[0u8; 64]);
+        let mut sigs = Map::new(&env);
+        sigs.set(validator_pk, junk.clone());
+        for i in 1..=2u8 {
+            sigs.set(BytesN::from_array(&env, &[i; 32]), junk.clone());
+        }
+
+        let result = client.try_release(
+            &token_addr,
+            &100,
+            &user,
+            &DEFAULT_MESSAGE_VERSION,
+            &proof,
+            &sigs,
+        );
+        assert_eq!(result, Err(Ok(Error::TooManySignatures)));
+        let result = client.try_mint_wrapped(
+            &symbol_short!("ETH"),
+            &100,
+            &user,
+            &DEFAULT_MESSAGE_VERSION,
+            &proof,
+            &sigs,
+        );
+        assert_eq!(result, Err(Ok(Error::TooManySignatures)));
+
+        assert_eq!(client.try_set_max_signatures(&0), Err(Ok(Error::InvalidQuorum)));
+        client.set_max_signatures(&1);
+        sigs.remove(BytesN::from_array(&env, &[2u8; 32]));
+        let result = client.try_release(
+            &token_addr,
+            &100,
+            &user,
+            &DEFAULT_MESSAGE_VERSION,
+            &proof,
+            &sigs,
+        );
+        assert_eq!(result, Err(Ok(Error::TooManySignatures)));
+    }
+
+    #[contract]
+    struct MockRecipient;
+
+    #[contracttype]
+    #[derive(Clone)]
+    enum RecipientKey {
+        Received,
+    }
+
+    #[contractimpl]
+    impl MockRecipient {
+        pub fn on_release(env: Env, asset: Address, amount: i128) {
+            env.storage().instance().set(&RecipientKey::Received, &(asset, amount));
+        }
+
+        pub fn on_release_fail(_env: Env, _asset: Address, _amount: i128) {
+            panic!("recipient rejected release");
+        }
+
+        pub fn received(env: Env) -> Option<(Address, i128)> {
+            env.storage().instance().get(&RecipientKey::Received)
+        }
+    }
+
+    #[test]
+    fn test_release_with_call_invokes_recipient() {
+        let env = Env::default();
+        let (client, _, bridge_addr, validator_pk, signing_key) = setup(&env);
+        env.mock_all_auths();
+
+        let token_addr = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+        let token_client = TokenClient::new(&env, &token_addr);
+        StellarAssetClient::new(&env, &token_addr).mint(&bridge_addr, &500);
+
+        let recipient_addr = env.register(MockRecipient, ());
+        let recipient = MockRecipientClient::new(&env, &recipient_addr);
+
+        let proof_bytes = [41u8; 32];
+        let proof = BytesN::from_array(&env, &proof_bytes);
+        let callback_fn = Symbol::new(&env, "on_release");
+        let digest = call_digest(
+            &env,
+            DEFAULT_MESSAGE_VERSION,
+            &token_addr,
+            200,
+            &recipient_addr,
+            &callback_fn,
+            &proof,
+        );
+        let sig = BytesN::from_array(&env, &signing_key.sign(&digest.to_array()).to_bytes());
+        let mut sigs = Map::new(&env);
+        sigs.set(validator_pk, sig);
+
+        client.release_with_call(
+            &token_addr,
+            &200,
+            &recipient_addr,
+            &callback_fn,
+            &DEFAULT_MESSAGE_VERSION,
+            &proof,
+            &sigs,
+        );
+        assert_eq!(token_client.balance(&recipient_addr), 200);
+        assert_eq!(recipient.received(), Some((token_addr, 200)));
+    }
+
+    #[test]
+    fn test_release_with_call_reverts_when_callback_fails() {
+        let env = Env::default();
+        let (client, _, bridge_addr, validator_pk, signing_key) = setup(&env);
+        env.mock_all_auths();
+
+        let token_addr = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+        let token_client = TokenClient::new(&env, &token_addr);
+        StellarAssetClient::new(&env, &token_addr).mint(&bridge_addr, &500);
+        let recipient_addr = env.register(MockRecipient, ());
+
+        let proof_bytes = [42u8; 32];
+        let proof = BytesN::from_array(&env, &proof_bytes);
+        let callback_fn = Symbol::new(&env, "on_release_fail");
+        let digest = call_digest(
+            &env,
+            DEFAULT_MESSAGE_VERSION,
+            &token_addr,
+            200,
+            &recipient_addr,
+            &callback_fn,
+            &proof,
+        );
+        let sig = BytesN::from_array(&env, &signing_key.sign(&digest.to_array()).to_bytes());
+        let mut sigs = Map::new(&env);
+        sigs.set(validator_pk.clone(), sig);
+
+        let result = client.try_release_with_call(
+            &token_addr,
+            &200,
+            &recipient_addr,
+            &callback_fn,
+            &DEFAULT_MESSAGE_VERSION,
+            &proof,
+            &sigs,
+        );
+        assert!(result.is_err());
+        assert_eq!(token_client.balance(&recipient_addr), 0);
+        assert_eq!(token_client.balance(&bridge_addr), 500);
+
+        // The proof was not consumed, so a corrected call can still go through
+        let release_sig = signing_key.sign(&message_digest(&env, DEFAULT_MESSAGE_VERSION, &proof).to_array());
+        sigs.set(validator_pk, BytesN::from_array(&env, &release_sig.to_bytes()));
+        client.release(&token_addr, &200, &recipient_addr, &DEFAULT_MESSAGE_VERSION, &proof, &sigs);
+        assert_eq!(token_client.balance(&recipient_addr), 200);
+    }
+
+    #[test]
+    fn test_release_with_call_rejects_release_signature() {
+        let env = Env::default();
+        let (client, _, bridge_addr, validator_pk, signing_key) = setup(&env);
+        env.mock_all_auths();
+
+        let token_addr = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+        let token_client = TokenClient::new(&env, &token_addr);
+        StellarAssetClient::new(&env, &token_addr).mint(&bridge_addr, &500);
+        let recipient_addr = env.register(MockRecipient, ());
+        let recipient = MockRecipientClient::new(&env, &recipient_addr);
+
+        // Quorum-signed for a plain `release`, replayed with a callback
+        let proof = BytesN::from_array(&env, &[43u8; 32]);
+        let release_sig = signing_key.sign(&message_digest(&env, DEFAULT_MESSAGE_VERSION, &proof).to_array());
+        let mut sigs = Map::new(&env);
+        sigs.set(validator_pk, BytesN::from_array(&env, &release_sig.to_bytes()));
+
+        let result = client.try_release_with_call(
+            &token_addr,
+            &200,
+            &recipient_addr,
+            &Symbol::new(&env, "on_release"),
+            &DEFAULT_MESSAGE_VERSION,
+            &proof,
+            &sigs,
+        );
+        assert!(result.is_err());
+        assert_eq!(token_client.balance(&bridge_addr), 500);
+        assert_eq!(recipient.received(), None);
+
+        // The signature remains valid for the release it was made for
+        client.release(
+            &token_addr,
+            &200,
+            &recipient_addr,
+            &DEFAULT_MESSAGE_VERSION,
+            &proof,
+            &sigs,
+        );
+        assert_eq!(token_client.balance(&recipient_addr), 200);
+    }
+
+    #[test]
+    fn test_release_batch_three_recipients() {
+        let env = Env::default();
+        let (client, _, bridge_addr, validator_pk, signing_key) = setup(&env);
+        env.mock_all_auths();
+
+        let token_addr = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+        let token_client = TokenClient::new(&env, &token_addr);
+        StellarAssetClient::new(&env, &token_addr).mint(&bridge_addr, &1000);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+        let entries = Vec::from_array(
+            &env,
+            [
+                (token_addr.clone(), 100i128, alice.clone()),
+                (token_addr.clone(), 200i128, bob.clone()),
+                (token_addr.clone(), 300i128, carol.clone()),
+            ],
+        );
+
+        let proof = BytesN::from_array(&env, &[21u8; 32]);
+        let digest = batch_digest(&env, DEFAULT_MESSAGE_VERSION, &entries, &proof);
+        let sig = BytesN::from_array(&env, &signing_key.sign(&digest.to_array()).to_bytes());
+        let mut sigs = Map::new(&env);
+        sigs.set(validator_pk, sig);
+
+        client.release_batch(&entries, &DEFAULT_MESSAGE_VERSION, &proof, &sigs);
+        assert_eq!(token_client.balance(&alice), 100);
+        assert_eq!(token_client.balance(&bob), 200);
+        assert_eq!(token_client.balance(&carol), 300);
+        assert_eq!(token_client.balance(&bridge_addr), 400);
+
+        // The batch proof can only be used once
+        let replay = client.try_release_batch(&entries, &DEFAULT_MESSAGE_VERSION, &proof, &sigs);
+        assert_eq!(replay, Err(Ok(Error::ProofAlreadyProcessed)));
+    }
+
+    #[test]
+    fn test_release_batch_reverts_when_one_transfer_fails() {
+        let env = Env::default();
+        let (client, _, bridge_addr, validator_pk, signing_key) = setup(&env);
+        env.mock_all_auths();
+
+        let token_addr = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+        let token_client = TokenClient::new(&env, &token_addr);
+        StellarAssetClient::new(&env, &token_addr).mint(&bridge_addr, &250);
+
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let entries = Vec::from_array(
+            &env,
+            [
+                (token_addr.clone(), 200i128, alice.clone()),
+                (token_addr.clone(), 100i128, bob.clone()),
+            ],
+        );
+
+        let proof = BytesN::from_array(&env, &[22u8; 32]);
+        let digest = batch_digest(&env, DEFAULT_MESSAGE_VERSION, &entries, &proof);
+        let sig = BytesN::from_array(&env, &signing_key.sign(&digest.to_array()).to_bytes());
+        let mut sigs = Map::new(&env);
+        sigs.set(validator_pk, sig);
+
+        assert!(client
+            .try_release_batch(&entries, &DEFAULT_MESSAGE_VERSION, &proof, &sigs)
+            .is_err());
+        assert_eq!(token_client.balance(&alice), 0);
+        assert_eq!(token_client.balance(&bob), 0);
+        assert_eq!(token_client.balance(&bridge_addr), 250);
+    }
 }
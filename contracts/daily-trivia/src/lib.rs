@@ -7,9 +7,31 @@
 
 use soroban_sdk::{
     contract, contractclient, contracterror, contractevent, contractimpl, contracttype,
-    symbol_short, Address, Bytes, BytesN, Env, Symbol,
+    symbol_short, Address, Bytes, BytesN, Env, Symbol, Vec,
 };
 
+/// Length of a trivia day. Days are UTC-aligned buckets of the ledger
+/// timestamp: `day = timestamp / SECONDS_PER_DAY`.
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Lifetime of a daily attempt counter, about two days at 5s/ledger. The
+/// counter is only read for its own day, so it can expire soon after.
+const ATTEMPTS_TTL_LEDGERS: u32 = 34_560;
+
+/// Most rounds `close_all_open` closes in one call. A zero-winner round writes
+/// two ledger entries (round and pool release), so this stays under the
+/// per-transaction write limit.
+const MAX_ROUNDS_PER_CLOSE_ALL: u32 = 20;
+
+/// Most winners kept in a round's `Winners` list, which keeps the entry well
+/// under the ledger entry size limit. Later winners still get a rank and can
+/// claim; they are just not listed.
+const MAX_LISTED_WINNERS: u32 = 500;
+
+/// Most rounds that may be open at once. Bounds the `OpenRounds` index, which
+/// every open and close rewrites, so it stays a small persistent entry.
+const MAX_OPEN_ROUNDS: u32 = 100;
+
 // ---------------------------------------------------------------------------
 // External contract clients
 // ---------------------------------------------------------------------------
@@ -48,6 +70,15 @@ pub enum Error {
     NoRewardAvailable = 10,
     InvalidAmount = 11,
     Overflow = 12,
+    DailyLimitReached = 13,
+    SubmissionWindowClosed = 14,
+    WrongRoundType = 15,
+    InvalidChoice = 16,
+    RoundStillOpen = 17,
+    CommitmentMismatch = 18,
+    AlreadyRevealed = 19,
+    TooManyOpenRounds = 20,
+    DeadlinePassed = 21,
 }
 
 // ---------------------------------------------------------------------------
@@ -61,6 +92,33 @@ pub enum RoundStatus {
     Closed = 1,
 }
 
+/// Where a round's winnings are paid from. Exactly one source settles a
+/// round; the choice is captured when the round opens.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PayoutSource {
+    /// The prize pool reserves the reward on open and pays winners directly.
+    PrizePool = 0,
+    /// The contract's own funds in the balance contract are debited and the
+    /// winner is credited. Nothing is reserved in the prize pool.
+    Balance = 1,
+}
+
+/// How a submitted payload is normalized before it is hashed. The mode is
+/// chosen per round in `open_round`, and the round's `answer_commitment` must
+/// be computed over the normalized form of the answer.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnswerNormalization {
+    /// The payload is hashed exactly as submitted.
+    None = 0,
+    /// Leading and trailing ASCII whitespace (space, `\t`, `\n`, `\r`,
+    /// `\x0b`, `\x0c`) is stripped and ASCII `A`-`Z` are mapped to `a`-`z`.
+    /// Interior whitespace and all other bytes, including non-ASCII UTF-8,
+    /// are left untouched.
+    LowercaseTrim = 1,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct RoundData {
@@ -72,14 +130,29 @@ pub struct RoundData {
     pub status: RoundStatus,
     pub opened_at: u64,
     pub closed_at: u64,
+    /// Submissions are rejected from this timestamp on; 0 = until closed
+    pub closes_at: u64,
+    pub payout_source: PayoutSource,
+    pub normalization: AnswerNormalization,
+    /// Number of choices of a multiple-choice round, whose
+    /// `answer_commitment` commits to the correct choice and a salt; 0 for a
+    /// commitment round
+    pub num_choices: u32,
 }
 
 #[contracttype]
 #[derive(Clone)]
 pub struct Submission {
+    /// `sha256` of the normalized payload, or of the big-endian choice index
+    /// for multiple-choice rounds
     pub answer_hash: BytesN<32>,
+    /// Multiple-choice submissions are graded once the round's answer is
+    /// revealed and read as incorrect until then.
     pub correct: bool,
     pub claimed: bool,
+    /// 1-based order among correct answers in the round; 0 if incorrect.
+    /// Same-ledger submissions are ranked in transaction order.
+    pub rank: u32,
 }
 
 #[contracttype]
@@ -87,9 +160,29 @@ pub enum DataKey {
     Admin,
     PrizePoolContract,
     BalanceContract,
+    PayoutSource,
     LatestRoundId,
+    /// Ids of rounds that are still open, in opening order — persistent
+    /// storage, at most `MAX_OPEN_ROUNDS` entries.
+    OpenRounds,
     Round(u64),
     Submission(u64, Address),
+    /// Correct submitters of a round in rank order, capped at
+    /// `MAX_LISTED_WINNERS`.
+    Winners(u64),
+    /// Correct 0-based index of a multiple-choice round, set on reveal.
+    CorrectChoice(u64),
+    /// Submissions per answer hash of a multiple-choice round.
+    AnswerTally(u64, BytesN<32>),
+    /// Submitters per answer hash of a multiple-choice round in submission
+    /// order, capped at `MAX_LISTED_WINNERS`.
+    AnswerSubmitters(u64, BytesN<32>),
+    /// Answer payload published by `reveal_answer` after close.
+    Revealed(u64),
+    /// Answers allowed per player per day; absent or 0 means unlimited.
+    MaxDailyAttempts,
+    /// Answers submitted by a player on a given day — temporary storage.
+    DailyAttempts(Address, u64),
 }
 
 #[contracttype]
@@ -141,6 +234,13 @@ pub struct RoundClosed {
     pub payout_per_winner: i128,
 }
 
+#[contractevent]
+pub struct AnswerRevealed {
+    #[topic]
+    pub round_id: u64,
+    pub answer_payload: Bytes,
+}
+
 #[contractevent]
 pub struct RewardClaimed {
     #[topic]
@@ -178,46 +278,94 @@ impl DailyTrivia {
         Ok(())
     }
 
+    /// Selects the payout source for rounds opened from now on. Rounds that
+    /// are already open keep the source they were opened with.
+    pub fn set_payout_source(env: Env, source: PayoutSource) -> Result<(), Error> {
+        require_admin(&env)?;
+        env.storage().instance().set(&DataKey::PayoutSource, &source);
+        Ok(())
+    }
+
+    pub fn get_payout_source(env: Env) -> PayoutSource {
+        get_payout_source(&env)
+    }
+
+    /// Caps how many answers a player may submit per UTC day, across all
+    /// rounds. 0 removes the cap. Takes effect immediately, including for
+    /// attempts already made today.
+    pub fn set_max_daily_attempts(env: Env, max_daily_attempts: u32) -> Result<(), Error> {
+        require_admin(&env)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxDailyAttempts, &max_daily_attempts);
+        Ok(())
+    }
+
+    pub fn get_max_daily_attempts(env: Env) -> u32 {
+        get_max_daily_attempts(&env)
+    }
+
+    /// Answers `player` may still submit today, or `None` when there is no
+    /// daily cap.
+    pub fn remaining_daily_attempts(env: Env, player: Address) -> Option<u32> {
+        let max = get_max_daily_attempts(&env);
+        if max == 0 {
+            return None;
+        }
+        Some(max.saturating_sub(get_daily_attempts(&env, &player)))
+    }
+
+    /// Open a round. Submitted payloads are transformed by `normalization`
+    /// before hashing, so `answer_commitment` is the hash of the normalized
+    /// answer. A non-zero `closes_at` stops accepting answers at that
+    /// timestamp and must be in the future; the admin still closes the round
+    /// to settle it.
     pub fn open_round(
         env: Env,
         round_id: u64,
         answer_commitment: BytesN<32>,
+        normalization: AnswerNormalization,
         reward_amount: i128,
+        closes_at: u64,
     ) -> Result<(), Error> {
-        let admin = require_admin(&env)?;
-        require_positive(reward_amount)?;
-
-        let key = DataKey::Round(round_id);
-        if env.storage().persistent().has(&key) {
-            return Err(Error::RoundAlreadyExists);
-        }
-
-        let prize_pool = get_prize_pool(&env)?;
-        let pool_client = PrizePoolClient::new(&env, &prize_pool);
-        pool_client.reserve(&admin, &round_id, &reward_amount);
-
-        let now = env.ledger().timestamp();
-        let round = RoundData {
+        store_new_round(
+            &env,
+            round_id,
             answer_commitment,
+            normalization,
             reward_amount,
-            payout_per_winner: 0,
-            winner_count: 0,
-            participant_count: 0,
-            status: RoundStatus::Open,
-            opened_at: now,
-            closed_at: 0,
-        };
-        env.storage().persistent().set(&key, &round);
-        env.storage()
-            .instance()
-            .set(&DataKey::LatestRoundId, &round_id);
+            closes_at,
+            0,
+        )
+    }
 
-        RoundOpened {
+    /// Open a multiple-choice round answered with `submit_mc_answer`.
+    ///
+    /// `choice_commitment` is `sha256(choice || salt)`, where `choice` is the
+    /// correct 0-based index as 4 big-endian bytes and `salt` is a secret the
+    /// admin keeps until `reveal_answer`. Submissions are graded when that
+    /// payload is revealed after close, so the correct index is never in
+    /// storage while the round accepts answers.
+    pub fn open_mc_round(
+        env: Env,
+        round_id: u64,
+        choice_commitment: BytesN<32>,
+        num_choices: u32,
+        reward_amount: i128,
+        closes_at: u64,
+    ) -> Result<(), Error> {
+        if num_choices == 0 {
+            return Err(Error::InvalidChoice);
+        }
+        store_new_round(
+            &env,
             round_id,
+            choice_commitment,
+            AnswerNormalization::None,
             reward_amount,
-        }
-        .publish(&env);
-        Ok(())
+            closes_at,
+            num_choices,
+        )
     }
 
     pub fn submit_answer(
@@ -229,97 +377,62 @@ impl DailyTrivia {
         require_initialized(&env)?;
         player.require_auth();
 
-        let key = DataKey::Round(round_id);
-        let mut round: RoundData = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .ok_or(Error::RoundNotFound)?;
-
-        if round.status != RoundStatus::Open {
-            return Err(Error::RoundClosed);
-        }
-        if env.ledger().timestamp() < round.opened_at {
-            return Err(Error::RoundNotOpen);
-        }
-
-        let submission_key = DataKey::Submission(round_id, player.clone());
-        if env.storage().persistent().has(&submission_key) {
-            return Err(Error::AlreadySubmitted);
+        let round = load_accepting_round(&env, round_id)?;
+        if round.num_choices != 0 {
+            return Err(Error::WrongRoundType);
         }
 
+        let answer_payload = normalize_answer(&env, &answer_payload, round.normalization);
         let answer_hash: BytesN<32> = env.crypto().sha256(&answer_payload).into();
         let correct = answer_hash == round.answer_commitment;
-        round.participant_count = round
-            .participant_count
-            .checked_add(1)
-            .ok_or(Error::Overflow)?;
-
-        if correct {
-            round.winner_count = round.winner_count.checked_add(1).ok_or(Error::Overflow)?;
-        }
-        env.storage().persistent().set(&key, &round);
+        record_submission(&env, player, round_id, round, answer_hash, Some(correct))
+    }
 
-        let submission = Submission {
-            answer_hash,
-            correct,
-            claimed: false,
-        };
-        env.storage().persistent().set(&submission_key, &submission);
+    /// Answer a multiple-choice round with a 0-based `choice` index.
+    pub fn submit_mc_answer(
+        env: Env,
+        player: Address,
+        round_id: u64,
+        choice: u32,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        player.require_auth();
 
-        AnswerSubmitted {
-            round_id,
-            player,
-            correct,
+        let round = load_accepting_round(&env, round_id)?;
+        if round.num_choices == 0 {
+            return Err(Error::WrongRoundType);
         }
-        .publish(&env);
-        Ok(())
+        if choice >= round.num_choices {
+            return Err(Error::InvalidChoice);
+        }
+
+        let answer_hash = choice_hash(&env, choice);
+        record_submission(&env, player, round_id, round, answer_hash, None)
     }
 
     pub fn close_round(env: Env, round_id: u64) -> Result<(), Error> {
         let admin = require_admin(&env)?;
-        let key = DataKey::Round(round_id);
-        let mut round: RoundData = env
-            .storage()
-            .persistent()
-            .get(&key)
-            .ok_or(Error::RoundNotFound)?;
-
-        if round.status != RoundStatus::Open {
-            return Err(Error::RoundNotOpen);
-        }
-        let now = env.ledger().timestamp();
-        if now < round.opened_at {
-            return Err(Error::RoundNotOpen);
-        }
+        close_open_round(&env, &admin, round_id)
+    }
 
-        let payout_per_winner = if round.winner_count == 0 {
-            0
-        } else {
-            round
-                .reward_amount
-                .checked_div(round.winner_count as i128)
-                .ok_or(Error::Overflow)?
-        };
+    /// Close every open round, e.g. before decommissioning the contract.
+    ///
+    /// Each round is settled exactly as by `close_round`. At most
+    /// `MAX_ROUNDS_PER_CLOSE_ALL` rounds are closed per call, oldest first;
+    /// returns how many are still open so callers can repeat until it is 0.
+    pub fn close_all_open(env: Env) -> Result<u32, Error> {
+        let admin = require_admin(&env)?;
 
-        if round.winner_count == 0 {
-            let prize_pool = get_prize_pool(&env)?;
-            let pool_client = PrizePoolClient::new(&env, &prize_pool);
-            pool_client.release(&admin, &round_id, &round.reward_amount);
+        let open_rounds = get_open_rounds(&env);
+        for round_id in open_rounds.iter().take(MAX_ROUNDS_PER_CLOSE_ALL as usize) {
+            close_open_round(&env, &admin, round_id)?;
         }
+        Ok(get_open_rounds(&env).len())
+    }
 
-        round.status = RoundStatus::Closed;
-        round.closed_at = now;
-        round.payout_per_winner = payout_per_winner;
-        env.storage().persistent().set(&key, &round);
-
-        RoundClosed {
-            round_id,
-            winners: round.winner_count,
-            payout_per_winner,
-        }
-        .publish(&env);
-        Ok(())
+    /// Ids of the rounds that are still open, oldest first.
+    pub fn get_open_rounds(env: Env) -> Vec<u64> {
+        get_open_rounds(&env)
     }
 
     pub fn claim_reward(env: Env, player: Address, round_id: u64) -> Result<i128, Error> {
@@ -337,11 +450,12 @@ impl DailyTrivia {
         }
 
         let submission_key = DataKey::Submission(round_id, player.clone());
-        let mut submission: Submission = env
+        let submission: Submission = env
             .storage()
             .persistent()
             .get(&submission_key)
             .ok_or(Error::NoRewardAvailable)?;
+        let mut submission = graded_submission(&env, &round, round_id, submission);
 
         if submission.claimed {
             return Err(Error::AlreadyClaimed);
@@ -351,36 +465,42 @@ impl DailyTrivia {
             return Err(Error::NoRewardAvailable);
         }
 
-        let prize_pool = get_prize_pool(&env)?;
-        let pool_client = PrizePoolClient::new(&env, &prize_pool);
-        let admin = get_admin(&env)?;
-        pool_client.payout(&admin, &player, &round_id, &round.payout_per_winner);
-
-        let balance_contract = get_balance_contract(&env)?;
-        let balance_client = BalanceClient::new(&env, &balance_contract);
-        let contract_addr = env.current_contract_address();
-
-        let contract_balance = balance_client.balance_of(&contract_addr);
-        if contract_balance < round.payout_per_winner {
-            return Err(Error::InvalidAmount);
-        }
-
-        balance_client.debit(
-            &contract_addr,
-            &contract_addr,
-            &round.payout_per_winner,
-            &symbol_short!("payout"),
-        );
-        balance_client.credit(
-            &contract_addr,
-            &player,
-            &round.payout_per_winner,
-            &symbol_short!("win"),
-        );
-
+        // Mark claimed before the external transfer.
         submission.claimed = true;
         env.storage().persistent().set(&submission_key, &submission);
 
+        match round.payout_source {
+            PayoutSource::PrizePool => {
+                let prize_pool = get_prize_pool(&env)?;
+                let pool_client = PrizePoolClient::new(&env, &prize_pool);
+                let admin = get_admin(&env)?;
+                pool_client.payout(&admin, &player, &round_id, &round.payout_per_winner);
+            }
+            PayoutSource::Balance => {
+                let balance_contract = get_balance_contract(&env)?;
+                let balance_client = BalanceClient::new(&env, &balance_contract);
+                let contract_addr = env.current_contract_address();
+
+                let contract_balance = balance_client.balance_of(&contract_addr);
+                if contract_balance < round.payout_per_winner {
+                    return Err(Error::InvalidAmount);
+                }
+
+                balance_client.debit(
+                    &contract_addr,
+                    &contract_addr,
+                    &round.payout_per_winner,
+                    &symbol_short!("payout"),
+                );
+                balance_client.credit(
+                    &contract_addr,
+                    &player,
+                    &round.payout_per_winner,
+                    &symbol_short!("win"),
+                );
+            }
+        }
+
         RewardClaimed {
             round_id,
             player,
@@ -390,6 +510,67 @@ impl DailyTrivia {
         Ok(round.payout_per_winner)
     }
 
+    /// Publish a closed round's answer so anyone can check it against
+    /// `answer_commitment`. `answer_payload` must hash to the commitment
+    /// exactly: the normalized answer the admin committed to, or for a
+    /// multiple-choice round the big-endian choice followed by the salt.
+    /// Revealing a multiple-choice round grades it: the players who picked
+    /// the revealed choice become its winners and share the reward, and a
+    /// round nobody won releases its prize pool reservation.
+    pub fn reveal_answer(env: Env, round_id: u64, answer_payload: Bytes) -> Result<(), Error> {
+        let admin = require_admin(&env)?;
+
+        let round: RoundData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Round(round_id))
+            .ok_or(Error::RoundNotFound)?;
+        if round.status != RoundStatus::Closed {
+            return Err(Error::RoundStillOpen);
+        }
+
+        let key = DataKey::Revealed(round_id);
+        if env.storage().persistent().has(&key) {
+            return Err(Error::AlreadyRevealed);
+        }
+        let answer_hash: BytesN<32> = env.crypto().sha256(&answer_payload).into();
+        if answer_hash != round.answer_commitment {
+            return Err(Error::CommitmentMismatch);
+        }
+        if round.num_choices != 0 {
+            grade_mc_round(&env, &admin, round_id, round, &answer_payload)?;
+        }
+        env.storage().persistent().set(&key, &answer_payload);
+
+        AnswerRevealed {
+            round_id,
+            answer_payload,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// The answer revealed for a round, or `None` if it has not been revealed.
+    pub fn get_revealed(env: Env, round_id: u64) -> Option<Bytes> {
+        env.storage().persistent().get(&DataKey::Revealed(round_id))
+    }
+
+    pub fn get_submission(env: Env, round_id: u64, player: Address) -> Option<Submission> {
+        let submission: Submission = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Submission(round_id, player))?;
+        let round: RoundData = env.storage().persistent().get(&DataKey::Round(round_id))?;
+        Some(graded_submission(&env, &round, round_id, submission))
+    }
+
+    /// Correct submitters of a round in rank order. Only the first
+    /// `MAX_LISTED_WINNERS` (500) are listed; `winner_count` on the round is
+    /// always the full count.
+    pub fn get_winners(env: Env, round_id: u64) -> Vec<Address> {
+        get_winners(&env, round_id)
+    }
+
     pub fn get_round(env: Env, round_id: u64) -> Option<RoundData> {
         env.storage().persistent().get(&DataKey::Round(round_id))
     }
@@ -477,6 +658,404 @@ fn get_prize_pool(env: &Env) -> Result<Address, Error> {
         .ok_or(Error::NotInitialized)
 }
 
+fn get_payout_source(env: &Env) -> PayoutSource {
+    env.storage()
+        .instance()
+        .get(&DataKey::PayoutSource)
+        .unwrap_or(PayoutSource::PrizePool)
+}
+
+fn normalize_answer(env: &Env, payload: &Bytes, mode: AnswerNormalization) -> Bytes {
+    match mode {
+        AnswerNormalization::None => payload.clone(),
+        AnswerNormalization::LowercaseTrim => {
+            let is_space = |b: u8| matches!(b, b' ' | b'\t' | b'\n' | b'\r' | 0x0b | 0x0c);
+            let mut start = 0u32;
+            let mut end = payload.len();
+            while start < end && is_space(payload.get_unchecked(start)) {
+                start += 1;
+            }
+            while end > start && is_space(payload.get_unchecked(end - 1)) {
+                end -= 1;
+            }
+
+            let mut normalized = Bytes::new(env);
+            for b in payload.slice(start..end).iter() {
+                normalized.push_back(b.to_ascii_lowercase());
+            }
+            normalized
+        }
+    }
+}
+
+fn get_max_daily_attempts(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::MaxDailyAttempts)
+        .unwrap_or(0)
+}
+
+fn daily_attempts_key(env: &Env, player: &Address) -> DataKey {
+    DataKey::DailyAttempts(player.clone(), env.ledger().timestamp() / SECONDS_PER_DAY)
+}
+
+fn get_daily_attempts(env: &Env, player: &Address) -> u32 {
+    env.storage()
+        .temporary()
+        .get(&daily_attempts_key(env, player))
+        .unwrap_or(0)
+}
+
+/// Counts one answer against today's cap. The counter is keyed by day, so a
+/// new day starts from zero without any explicit reset.
+fn record_daily_attempt(env: &Env, player: &Address) -> Result<(), Error> {
+    let max = get_max_daily_attempts(env);
+    let attempts = get_daily_attempts(env, player);
+    if max != 0 && attempts >= max {
+        return Err(Error::DailyLimitReached);
+    }
+
+    let key = daily_attempts_key(env, player);
+    env.storage().temporary().set(&key, &(attempts + 1));
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, ATTEMPTS_TTL_LEDGERS, ATTEMPTS_TTL_LEDGERS);
+    Ok(())
+}
+
+/// Create an open round, reserving its reward in the prize pool when that is
+/// the payout source. `num_choices` is 0 for commitment rounds.
+fn store_new_round(
+    env: &Env,
+    round_id: u64,
+    answer_commitment: BytesN<32>,
+    normalization: AnswerNormalization,
+    reward_amount: i128,
+    closes_at: u64,
+    num_choices: u32,
+) -> Result<(), Error> {
+    let admin = require_admin(env)?;
+    require_positive(reward_amount)?;
+    let now = env.ledger().timestamp();
+    if closes_at != 0 && closes_at <= now {
+        return Err(Error::DeadlinePassed);
+    }
+
+    let key = DataKey::Round(round_id);
+    if env.storage().persistent().has(&key) {
+        return Err(Error::RoundAlreadyExists);
+    }
+    let mut open_rounds = get_open_rounds(env);
+    if open_rounds.len() >= MAX_OPEN_ROUNDS {
+        return Err(Error::TooManyOpenRounds);
+    }
+
+    let payout_source = get_payout_source(env);
+    if payout_source == PayoutSource::PrizePool {
+        let prize_pool = get_prize_pool(env)?;
+        let pool_client = PrizePoolClient::new(env, &prize_pool);
+        pool_client.reserve(&admin, &round_id, &reward_amount);
+    }
+
+    let round = RoundData {
+        answer_commitment,
+        reward_amount,
+        payout_per_winner: 0,
+        winner_count: 0,
+        participant_count: 0,
+        status: RoundStatus::Open,
+        opened_at: now,
+        closed_at: 0,
+        closes_at,
+        payout_source,
+        normalization,
+        num_choices,
+    };
+    env.storage().persistent().set(&key, &round);
+    env.storage()
+        .instance()
+        .set(&DataKey::LatestRoundId, &round_id);
+    open_rounds.push_back(round_id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::OpenRounds, &open_rounds);
+
+    RoundOpened {
+        round_id,
+        reward_amount,
+    }
+    .publish(env);
+    Ok(())
+}
+
+/// Load a round that is currently accepting answers.
+fn load_accepting_round(env: &Env, round_id: u64) -> Result<RoundData, Error> {
+    let round: RoundData = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Round(round_id))
+        .ok_or(Error::RoundNotFound)?;
+
+    if round.status != RoundStatus::Open {
+        return Err(Error::RoundClosed);
+    }
+    let now = env.ledger().timestamp();
+    if now < round.opened_at {
+        return Err(Error::RoundNotOpen);
+    }
+    if round.closes_at != 0 && now >= round.closes_at {
+        return Err(Error::SubmissionWindowClosed);
+    }
+    Ok(round)
+}
+
+/// Store a player's submission and update the round's counters. `correct`
+/// is `None` for multiple-choice rounds, which are graded on reveal.
+fn record_submission(
+    env: &Env,
+    player: Address,
+    round_id: u64,
+    mut round: RoundData,
+    answer_hash: BytesN<32>,
+    correct: Option<bool>,
+) -> Result<(), Error> {
+    let submission_key = DataKey::Submission(round_id, player.clone());
+    if env.storage().persistent().has(&submission_key) {
+        return Err(Error::AlreadySubmitted);
+    }
+    record_daily_attempt(env, &player)?;
+
+    round.participant_count = round
+        .participant_count
+        .checked_add(1)
+        .ok_or(Error::Overflow)?;
+
+    // `winner_count` doubles as the per-round rank counter: each correct
+    // answer takes the next rank, so ranks are unique and follow the
+    // order in which submissions executed (transaction order within a
+    // ledger).
+    let mut rank = 0u32;
+    match correct {
+        Some(true) => {
+            round.winner_count = round.winner_count.checked_add(1).ok_or(Error::Overflow)?;
+            rank = round.winner_count;
+            if rank <= MAX_LISTED_WINNERS {
+                append_listed(env, &DataKey::Winners(round_id), &player);
+            }
+        }
+        Some(false) => {}
+        // Rank among players with the same answer, which is the rank among
+        // winners if that answer is revealed as correct.
+        None => {
+            let tally_key = DataKey::AnswerTally(round_id, answer_hash.clone());
+            let tally: u32 = env.storage().persistent().get(&tally_key).unwrap_or(0);
+            rank = tally.checked_add(1).ok_or(Error::Overflow)?;
+            env.storage().persistent().set(&tally_key, &rank);
+            if rank <= MAX_LISTED_WINNERS {
+                append_listed(
+                    env,
+                    &DataKey::AnswerSubmitters(round_id, answer_hash.clone()),
+                    &player,
+                );
+            }
+        }
+    }
+    let correct = correct.unwrap_or(false);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Round(round_id), &round);
+
+    let submission = Submission {
+        answer_hash,
+        correct,
+        claimed: false,
+        rank,
+    };
+    env.storage().persistent().set(&submission_key, &submission);
+
+    AnswerSubmitted {
+        round_id,
+        player,
+        correct,
+    }
+    .publish(env);
+    Ok(())
+}
+
+fn append_listed(env: &Env, key: &DataKey, player: &Address) {
+    let mut listed: Vec<Address> = env
+        .storage()
+        .persistent()
+        .get(key)
+        .unwrap_or_else(|| Vec::new(env));
+    listed.push_back(player.clone());
+    env.storage().persistent().set(key, &listed);
+}
+
+/// `sha256` of a big-endian choice index, the answer hash of a
+/// multiple-choice submission.
+fn choice_hash(env: &Env, choice: u32) -> BytesN<32> {
+    env.crypto()
+        .sha256(&Bytes::from_array(env, &choice.to_be_bytes()))
+        .into()
+}
+
+/// A submission as graded so far. Multiple-choice submissions are stored
+/// ungraded and count as correct once the revealed choice matches theirs.
+fn graded_submission(
+    env: &Env,
+    round: &RoundData,
+    round_id: u64,
+    mut submission: Submission,
+) -> Submission {
+    if round.num_choices != 0 {
+        let correct_choice: Option<u32> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CorrectChoice(round_id));
+        submission.correct =
+            correct_choice.is_some_and(|choice| submission.answer_hash == choice_hash(env, choice));
+        if !submission.correct {
+            submission.rank = 0;
+        }
+    }
+    submission
+}
+
+/// Grade a closed multiple-choice round against its revealed
+/// `choice || salt` payload: fix the winners and per-winner payout, and
+/// release the pool reservation if nobody picked the correct choice.
+fn grade_mc_round(
+    env: &Env,
+    admin: &Address,
+    round_id: u64,
+    mut round: RoundData,
+    answer_payload: &Bytes,
+) -> Result<(), Error> {
+    if answer_payload.len() < 4 {
+        return Err(Error::InvalidChoice);
+    }
+    let mut choice_bytes = [0u8; 4];
+    answer_payload
+        .slice(0..4)
+        .copy_into_slice(&mut choice_bytes);
+    let choice = u32::from_be_bytes(choice_bytes);
+    if choice >= round.num_choices {
+        return Err(Error::InvalidChoice);
+    }
+
+    let answer_hash = choice_hash(env, choice);
+    round.winner_count = env
+        .storage()
+        .persistent()
+        .get(&DataKey::AnswerTally(round_id, answer_hash.clone()))
+        .unwrap_or(0);
+    round.payout_per_winner = if round.winner_count == 0 {
+        0
+    } else {
+        round
+            .reward_amount
+            .checked_div(round.winner_count as i128)
+            .ok_or(Error::Overflow)?
+    };
+
+    if round.winner_count == 0 && round.payout_source == PayoutSource::PrizePool {
+        let prize_pool = get_prize_pool(env)?;
+        let pool_client = PrizePoolClient::new(env, &prize_pool);
+        pool_client.release(admin, &round_id, &round.reward_amount);
+    }
+
+    if let Some(winners) = env
+        .storage()
+        .persistent()
+        .get::<DataKey, Vec<Address>>(&DataKey::AnswerSubmitters(round_id, answer_hash))
+    {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Winners(round_id), &winners);
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::CorrectChoice(round_id), &choice);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Round(round_id), &round);
+    Ok(())
+}
+
+fn get_winners(env: &Env, round_id: u64) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Winners(round_id))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn get_open_rounds(env: &Env) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OpenRounds)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Settle an open round: fix the per-winner payout, release the pool
+/// reservation if nobody won, and drop it from the open-rounds index.
+/// Multiple-choice rounds have no winners yet; `reveal_answer` settles them.
+fn close_open_round(env: &Env, admin: &Address, round_id: u64) -> Result<(), Error> {
+    let key = DataKey::Round(round_id);
+    let mut round: RoundData = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::RoundNotFound)?;
+
+    if round.status != RoundStatus::Open {
+        return Err(Error::RoundNotOpen);
+    }
+    let now = env.ledger().timestamp();
+    if now < round.opened_at {
+        return Err(Error::RoundNotOpen);
+    }
+
+    let payout_per_winner = if round.winner_count == 0 {
+        0
+    } else {
+        round
+            .reward_amount
+            .checked_div(round.winner_count as i128)
+            .ok_or(Error::Overflow)?
+    };
+
+    if round.winner_count == 0
+        && round.num_choices == 0
+        && round.payout_source == PayoutSource::PrizePool
+    {
+        let prize_pool = get_prize_pool(env)?;
+        let pool_client = PrizePoolClient::new(env, &prize_pool);
+        pool_client.release(admin, &round_id, &round.reward_amount);
+    }
+
+    round.status = RoundStatus::Closed;
+    round.closed_at = now;
+    round.payout_per_winner = payout_per_winner;
+    env.storage().persistent().set(&key, &round);
+
+    let mut open_rounds = get_open_rounds(env);
+    if let Some(index) = open_rounds.first_index_of(round_id) {
+        open_rounds.remove(index);
+        env.storage()
+            .persistent()
+            .set(&DataKey::OpenRounds, &open_rounds);
+    }
+
+    RoundClosed {
+        round_id,
+        winners: round.winner_count,
+        payout_per_winner,
+    }
+    .publish(env);
+    Ok(())
+}
+
 fn get_balance_contract(env: &Env) -> Result<Address, Error> {
     env.storage()
         .instance()
@@ -492,7 +1071,9 @@ fn get_balance_contract(env: &Env) -> Result<Address, Error> {
 mod test {
     use super::*;
     use soroban_sdk::{
-        contract, contractimpl, contracttype, testutils::Address as _, Address, Env, IntoVal,
+        contract, contractimpl, contracttype,
+        testutils::{Address as _, Events as _, Ledger as _},
+        Address, Env, Event, IntoVal,
     };
 
     #[contract]
@@ -502,6 +1083,7 @@ mod test {
     pub enum PoolKey {
         Reserved(u64),
         Paid(u64),
+        PaidTo(Address),
         Released(u64),
     }
 
@@ -519,10 +1101,29 @@ mod test {
                 .set(&PoolKey::Released(game_id), &amount);
         }
 
-        pub fn payout(env: Env, _admin: Address, _to: Address, game_id: u64, amount: i128) {
+        pub fn payout(env: Env, _admin: Address, to: Address, game_id: u64, amount: i128) {
             env.storage()
                 .persistent()
                 .set(&PoolKey::Paid(game_id), &amount);
+            let paid = Self::paid_to(env.clone(), to.clone());
+            env.storage()
+                .persistent()
+                .set(&PoolKey::PaidTo(to), &(paid + amount));
+        }
+
+        pub fn is_reserved(env: Env, game_id: u64) -> bool {
+            env.storage().persistent().has(&PoolKey::Reserved(game_id))
+        }
+
+        pub fn is_released(env: Env, game_id: u64) -> bool {
+            env.storage().persistent().has(&PoolKey::Released(game_id))
+        }
+
+        pub fn paid_to(env: Env, to: Address) -> i128 {
+            env.storage()
+                .persistent()
+                .get(&PoolKey::PaidTo(to))
+                .unwrap_or(0)
         }
     }
 
@@ -575,6 +1176,20 @@ mod test {
         Address,
         Address,
         MockBalanceClient<'_>,
+    ) {
+        let (client, admin, player, trivia_id, balance, _pool) = setup_with_pool(env);
+        (client, admin, player, trivia_id, balance)
+    }
+
+    fn setup_with_pool(
+        env: &Env,
+    ) -> (
+        DailyTriviaClient<'_>,
+        Address,
+        Address,
+        Address,
+        MockBalanceClient<'_>,
+        MockPrizePoolClient<'_>,
     ) {
         env.mock_all_auths();
 
@@ -584,6 +1199,7 @@ mod test {
         let balance_client = MockBalanceClient::new(env, &balance_id);
 
         let pool_id = env.register(MockPrizePool, ());
+        let pool_client = MockPrizePoolClient::new(env, &pool_id);
 
         let trivia_id = env.register(DailyTrivia, ());
         let trivia_client = DailyTriviaClient::new(env, &trivia_id);
@@ -592,20 +1208,36 @@ mod test {
         let contract_addr = trivia_id.clone();
         balance_client.set_balance(&contract_addr, &1_000);
 
-        (trivia_client, admin, player, trivia_id, balance_client)
+        (
+            trivia_client,
+            admin,
+            player,
+            trivia_id,
+            balance_client,
+            pool_client,
+        )
     }
 
     fn hash_answer(env: &Env, payload: &Bytes) -> BytesN<32> {
         env.crypto().sha256(payload).into()
     }
 
+    /// Reveal payload `choice || salt` of a multiple-choice round and its
+    /// commitment.
+    fn mc_answer(env: &Env, choice: u32, salt: &[u8]) -> (Bytes, BytesN<32>) {
+        let mut payload = Bytes::from_array(env, &choice.to_be_bytes());
+        payload.append(&Bytes::from_slice(env, salt));
+        let commitment = hash_answer(env, &payload);
+        (payload, commitment)
+    }
+
     #[test]
     fn test_round_open_close_lifecycle() {
         let env = Env::default();
         let (client, _admin, _player, _trivia_id, _balance) = setup(&env);
 
         let commitment = hash_answer(&env, &Bytes::from_array(&env, &[1, 2, 3]));
-        client.open_round(&1, &commitment, &100);
+        client.open_round(&1, &commitment, &AnswerNormalization::None, &100, &0);
 
         let round = client.get_round(&1).unwrap();
         assert_eq!(round.status, RoundStatus::Open);
@@ -615,6 +1247,95 @@ mod test {
         assert_eq!(round.status, RoundStatus::Closed);
     }
 
+    #[test]
+    fn test_close_all_open_settles_every_open_round() {
+        let env = Env::default();
+        let (client, _admin, player, trivia_id, _balance, pool) = setup_with_pool(&env);
+
+        let payload = Bytes::from_array(&env, &[4, 2]);
+        let commitment = hash_answer(&env, &payload);
+        client.open_round(&1, &commitment, &AnswerNormalization::None, &100, &0);
+        client.open_round(&2, &commitment, &AnswerNormalization::None, &300, &0);
+        client.open_round(&3, &commitment, &AnswerNormalization::None, &200, &0);
+        client.submit_answer(&player, &2, &payload);
+        client.close_round(&1);
+        assert_eq!(client.get_open_rounds(), soroban_sdk::vec![&env, 2u64, 3u64]);
+
+        assert_eq!(client.close_all_open(), 0);
+        assert_eq!(
+            env.events().all().filter_by_contract(&trivia_id),
+            [
+                RoundClosed {
+                    round_id: 2,
+                    winners: 1,
+                    payout_per_winner: 300,
+                }
+                .to_xdr(&env, &trivia_id),
+                RoundClosed {
+                    round_id: 3,
+                    winners: 0,
+                    payout_per_winner: 0,
+                }
+                .to_xdr(&env, &trivia_id),
+            ]
+        );
+
+        assert_eq!(client.get_open_rounds().len(), 0);
+        assert_eq!(client.get_round(&2).unwrap().status, RoundStatus::Closed);
+        assert_eq!(client.get_round(&3).unwrap().status, RoundStatus::Closed);
+        assert!(!pool.is_released(&2));
+        assert!(pool.is_released(&3));
+
+        // The winner of a force-closed round can still claim
+        assert_eq!(client.claim_reward(&player, &2), 300);
+        assert_eq!(client.close_all_open(), 0);
+    }
+
+    #[test]
+    fn test_close_all_open_is_bounded_per_call() {
+        let env = Env::default();
+        let (client, _admin, _player, _trivia_id, _balance) = setup(&env);
+
+        let commitment = hash_answer(&env, &Bytes::from_array(&env, &[7]));
+        for round_id in 0..(MAX_ROUNDS_PER_CLOSE_ALL as u64 + 3) {
+            client.open_round(&round_id, &commitment, &AnswerNormalization::None, &10, &0);
+        }
+
+        assert_eq!(client.close_all_open(), 3);
+        assert_eq!(
+            client.get_open_rounds().first(),
+            Some(MAX_ROUNDS_PER_CLOSE_ALL as u64)
+        );
+        assert_eq!(client.close_all_open(), 0);
+    }
+
+    #[test]
+    fn test_open_rounds_index_is_capped_and_persistent() {
+        let env = Env::default();
+        let (client, _admin, _player, trivia_id, _balance) = setup(&env);
+
+        let commitment = hash_answer(&env, &Bytes::from_array(&env, &[7]));
+        for round_id in 0..MAX_OPEN_ROUNDS as u64 {
+            client.open_round(&round_id, &commitment, &AnswerNormalization::None, &10, &0);
+        }
+        let next = MAX_OPEN_ROUNDS as u64;
+        assert_eq!(
+            client.try_open_round(&next, &commitment, &AnswerNormalization::None, &10, &0),
+            Err(Ok(Error::TooManyOpenRounds))
+        );
+        assert!(client.get_round(&next).is_none());
+
+        env.as_contract(&trivia_id, || {
+            assert!(!env.storage().instance().has(&DataKey::OpenRounds));
+            assert!(env.storage().persistent().has(&DataKey::OpenRounds));
+        });
+
+        // Closing a round frees a slot
+        client.close_round(&0);
+        client.open_round(&next, &commitment, &AnswerNormalization::None, &10, &0);
+        assert_eq!(client.get_open_rounds().len(), MAX_OPEN_ROUNDS);
+    }
+
     #[test]
     fn test_one_entry_per_round() {
         let env = Env::default();
@@ -622,7 +1343,7 @@ mod test {
 
         let payload = Bytes::from_array(&env, &[9]);
         let commitment = hash_answer(&env, &payload);
-        client.open_round(&2, &commitment, &100);
+        client.open_round(&2, &commitment, &AnswerNormalization::None, &100, &0);
 
         client.submit_answer(&player, &2, &payload);
         let result = client.try_submit_answer(&player, &2, &payload);
@@ -636,14 +1357,283 @@ mod test {
 
         let payload = Bytes::from_array(&env, &[7, 7]);
         let commitment = hash_answer(&env, &payload);
-        client.open_round(&3, &commitment, &100);
+        client.open_round(&3, &commitment, &AnswerNormalization::None, &100, &0);
 
         client.submit_answer(&player, &3, &payload);
         client.close_round(&3);
 
         let reward = client.claim_reward(&player, &3);
         assert_eq!(reward, 100);
-        assert_eq!(balance.balance_of(&player), 100);
+        // Default source is the prize pool; the balance contract is untouched.
+        assert_eq!(balance.balance_of(&player), 0);
+    }
+
+    #[test]
+    fn test_correct_submissions_get_unique_monotonic_ranks() {
+        let env = Env::default();
+        let (client, _admin, _player, _trivia_id, _balance) = setup(&env);
+
+        let payload = Bytes::from_array(&env, &[4, 2]);
+        let commitment = hash_answer(&env, &payload);
+        client.open_round(&9, &commitment, &AnswerNormalization::None, &300, &0);
+
+        let first = Address::generate(&env);
+        let wrong = Address::generate(&env);
+        let second = Address::generate(&env);
+        let third = Address::generate(&env);
+
+        // All submissions land in the same ledger; ranks follow call order.
+        client.submit_answer(&first, &9, &payload);
+        client.submit_answer(&wrong, &9, &Bytes::from_array(&env, &[0]));
+        client.submit_answer(&second, &9, &payload);
+        client.submit_answer(&third, &9, &payload);
+
+        assert_eq!(client.get_submission(&9, &first).unwrap().rank, 1);
+        assert_eq!(client.get_submission(&9, &second).unwrap().rank, 2);
+        assert_eq!(client.get_submission(&9, &third).unwrap().rank, 3);
+        assert_eq!(client.get_submission(&9, &wrong).unwrap().rank, 0);
+        assert!(client.get_submission(&9, &Address::generate(&env)).is_none());
+    }
+
+    #[test]
+    fn test_get_winners_lists_correct_submitters_in_rank_order() {
+        let env = Env::default();
+        let (client, _admin, _player, _trivia_id, _balance) = setup(&env);
+
+        let payload = Bytes::from_array(&env, &[4, 2]);
+        let commitment = hash_answer(&env, &payload);
+        client.open_round(&9, &commitment, &AnswerNormalization::None, &300, &0);
+        assert_eq!(client.get_winners(&9).len(), 0);
+
+        let first = Address::generate(&env);
+        let wrong = Address::generate(&env);
+        let second = Address::generate(&env);
+        client.submit_answer(&first, &9, &payload);
+        client.submit_answer(&wrong, &9, &Bytes::from_array(&env, &[0]));
+        client.submit_answer(&second, &9, &payload);
+        client.close_round(&9);
+
+        assert_eq!(
+            client.get_winners(&9),
+            soroban_sdk::vec![&env, first, second]
+        );
+        assert_eq!(client.get_winners(&10).len(), 0);
+    }
+
+    #[test]
+    fn test_prize_pool_source_pays_player_directly() {
+        let env = Env::default();
+        let (client, _admin, player, trivia_id, balance, pool) = setup_with_pool(&env);
+
+        let payload = Bytes::from_array(&env, &[5, 5]);
+        let commitment = hash_answer(&env, &payload);
+        client.open_round(&10, &commitment, &AnswerNormalization::None, &100, &0);
+        assert!(pool.is_reserved(&10));
+
+        let other = Address::generate(&env);
+        client.submit_answer(&player, &10, &payload);
+        client.submit_answer(&other, &10, &payload);
+        client.close_round(&10);
+
+        let round = client.get_round(&10).unwrap();
+        let reward = client.claim_reward(&player, &10);
+        assert_eq!(reward, round.payout_per_winner);
+        assert_eq!(pool.paid_to(&player), round.payout_per_winner);
+        assert_eq!(balance.balance_of(&player), 0);
+        assert_eq!(balance.balance_of(&trivia_id), 1_000);
+    }
+
+    #[test]
+    fn test_balance_source_credits_player_from_contract_funds() {
+        let env = Env::default();
+        let (client, _admin, player, trivia_id, balance, pool) = setup_with_pool(&env);
+
+        client.set_payout_source(&PayoutSource::Balance);
+        assert_eq!(client.get_payout_source(), PayoutSource::Balance);
+
+        let payload = Bytes::from_array(&env, &[6, 6]);
+        let commitment = hash_answer(&env, &payload);
+        client.open_round(&11, &commitment, &AnswerNormalization::None, &100, &0);
+        assert!(!pool.is_reserved(&11));
+
+        let other = Address::generate(&env);
+        client.submit_answer(&player, &11, &payload);
+        client.submit_answer(&other, &11, &payload);
+        client.close_round(&11);
+
+        let round = client.get_round(&11).unwrap();
+        assert_eq!(round.payout_source, PayoutSource::Balance);
+
+        let reward = client.claim_reward(&player, &11);
+        assert_eq!(reward, round.payout_per_winner);
+        assert_eq!(balance.balance_of(&player), round.payout_per_winner);
+        assert_eq!(
+            balance.balance_of(&trivia_id),
+            1_000 - round.payout_per_winner
+        );
+        assert_eq!(pool.paid_to(&player), 0);
+    }
+
+    #[test]
+    fn test_normalized_round_accepts_case_and_whitespace_variants() {
+        let env = Env::default();
+        let (client, _admin, _player, _trivia_id, _balance) = setup(&env);
+
+        let commitment = hash_answer(&env, &Bytes::from_slice(&env, b"new york"));
+        client.open_round(
+            &20,
+            &commitment,
+            &AnswerNormalization::LowercaseTrim,
+            &300,
+            &0,
+        );
+
+        for (payload, expected) in [
+            (&b"New York"[..], true),
+            (&b"  NEW YORK \n"[..], true),
+            (&b"\tnew york\r\n"[..], true),
+            (&b"new  york"[..], false),
+            (&b"newyork"[..], false),
+        ] {
+            let player = Address::generate(&env);
+            client.submit_answer(&player, &20, &Bytes::from_slice(&env, payload));
+            let submission = client.get_submission(&20, &player).unwrap();
+            assert_eq!(submission.correct, expected);
+        }
+    }
+
+    #[test]
+    fn test_unnormalized_round_requires_exact_bytes() {
+        let env = Env::default();
+        let (client, _admin, player, _trivia_id, _balance) = setup(&env);
+
+        let commitment = hash_answer(&env, &Bytes::from_slice(&env, b"paris"));
+        client.open_round(&21, &commitment, &AnswerNormalization::None, &100, &0);
+        assert_eq!(
+            client.get_round(&21).unwrap().normalization,
+            AnswerNormalization::None
+        );
+
+        client.submit_answer(&player, &21, &Bytes::from_slice(&env, b"Paris "));
+        assert!(!client.get_submission(&21, &player).unwrap().correct);
+    }
+
+    #[test]
+    fn test_multiple_choice_round_grades_choice_index() {
+        let env = Env::default();
+        let (client, _admin, player, trivia_id, _balance) = setup(&env);
+
+        let (payload, commitment) = mc_answer(&env, 2, b"pepper-and-salt!");
+        assert_eq!(
+            client.try_open_mc_round(&40, &commitment, &0, &100, &0),
+            Err(Ok(Error::InvalidChoice))
+        );
+        client.open_mc_round(&40, &commitment, &4, &100, &0);
+        assert_eq!(client.get_round(&40).unwrap().num_choices, 4);
+
+        let wrong = Address::generate(&env);
+        let late = Address::generate(&env);
+        assert_eq!(
+            client.try_submit_mc_answer(&wrong, &40, &4),
+            Err(Ok(Error::InvalidChoice))
+        );
+        assert_eq!(
+            client.try_submit_answer(&wrong, &40, &Bytes::from_array(&env, &[2])),
+            Err(Ok(Error::WrongRoundType))
+        );
+        client.submit_mc_answer(&wrong, &40, &1);
+        client.submit_mc_answer(&player, &40, &2);
+        client.submit_mc_answer(&late, &40, &2);
+
+        // Nothing in storage gives the answer away while the round is open
+        env.as_contract(&trivia_id, || {
+            assert!(!env.storage().persistent().has(&DataKey::CorrectChoice(40)));
+        });
+        assert!(!client.get_submission(&40, &player).unwrap().correct);
+        assert_eq!(client.get_winners(&40), soroban_sdk::vec![&env]);
+
+        // Closing alone does not grade the round
+        client.close_round(&40);
+        assert_eq!(
+            client.try_claim_reward(&player, &40),
+            Err(Ok(Error::NoRewardAvailable))
+        );
+
+        client.reveal_answer(&40, &payload);
+        let round = client.get_round(&40).unwrap();
+        assert_eq!(round.winner_count, 2);
+        assert_eq!(round.payout_per_winner, 50);
+        assert!(!client.get_submission(&40, &wrong).unwrap().correct);
+        assert_eq!(client.get_submission(&40, &wrong).unwrap().rank, 0);
+        assert_eq!(client.get_submission(&40, &player).unwrap().rank, 1);
+        assert_eq!(client.get_submission(&40, &late).unwrap().rank, 2);
+        assert_eq!(
+            client.get_winners(&40),
+            soroban_sdk::vec![&env, player.clone(), late.clone()]
+        );
+
+        assert_eq!(client.claim_reward(&player, &40), 50);
+        assert_eq!(client.claim_reward(&late, &40), 50);
+        assert!(client.try_claim_reward(&wrong, &40).is_err());
+    }
+
+    #[test]
+    fn test_multiple_choice_reveal_checks_commitment_and_releases_unwon_reward() {
+        let env = Env::default();
+        let (client, _admin, player, _trivia_id, _balance, pool) = setup_with_pool(&env);
+
+        let (payload, commitment) = mc_answer(&env, 3, b"another-secret");
+        client.open_mc_round(&42, &commitment, &4, &100, &0);
+        client.submit_mc_answer(&player, &42, &0);
+        assert_eq!(
+            client.try_reveal_answer(&42, &payload),
+            Err(Ok(Error::RoundStillOpen))
+        );
+
+        // Nobody has won yet, but the reservation is kept until the reveal
+        client.close_round(&42);
+        assert!(!pool.is_released(&42));
+
+        let (guess, _) = mc_answer(&env, 0, b"another-secret");
+        assert_eq!(
+            client.try_reveal_answer(&42, &guess),
+            Err(Ok(Error::CommitmentMismatch))
+        );
+
+        client.reveal_answer(&42, &payload);
+        assert_eq!(client.get_round(&42).unwrap().winner_count, 0);
+        assert!(pool.is_released(&42));
+        assert_eq!(
+            client.try_claim_reward(&player, &42),
+            Err(Ok(Error::NoRewardAvailable))
+        );
+        assert_eq!(
+            client.try_reveal_answer(&42, &payload),
+            Err(Ok(Error::AlreadyRevealed))
+        );
+
+        // A commitment to an out-of-range choice can never be revealed
+        let (bad, bad_commitment) = mc_answer(&env, 4, b"salt");
+        client.open_mc_round(&43, &bad_commitment, &4, &100, &0);
+        client.close_round(&43);
+        assert_eq!(
+            client.try_reveal_answer(&43, &bad),
+            Err(Ok(Error::InvalidChoice))
+        );
+    }
+
+    #[test]
+    fn test_commitment_round_rejects_choice_answers() {
+        let env = Env::default();
+        let (client, _admin, player, _trivia_id, _balance) = setup(&env);
+
+        let commitment = hash_answer(&env, &Bytes::from_array(&env, &[1]));
+        client.open_round(&41, &commitment, &AnswerNormalization::None, &100, &0);
+        assert_eq!(
+            client.try_submit_mc_answer(&player, &41, &0),
+            Err(Ok(Error::WrongRoundType))
+        );
+        assert!(client.get_submission(&41, &player).is_none());
     }
 
     #[test]
@@ -652,7 +1642,7 @@ mod test {
         let (client, _admin, player, _trivia_id, _balance) = setup(&env);
 
         let commitment = hash_answer(&env, &Bytes::from_array(&env, &[1]));
-        client.open_round(&4, &commitment, &100);
+        client.open_round(&4, &commitment, &AnswerNormalization::None, &100, &0);
 
         let wrong = Bytes::from_array(&env, &[2]);
         client.submit_answer(&player, &4, &wrong);
@@ -669,7 +1659,7 @@ mod test {
 
         let payload = Bytes::from_array(&env, &[4, 4]);
         let commitment = hash_answer(&env, &payload);
-        client.open_round(&5, &commitment, &100);
+        client.open_round(&5, &commitment, &AnswerNormalization::None, &100, &0);
 
         client.submit_answer(&player, &5, &payload);
         client.close_round(&5);
@@ -679,6 +1669,42 @@ mod test {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_reveal_answer_requires_closed_round_and_matching_payload() {
+        let env = Env::default();
+        let (client, _admin, _player, trivia_id, _balance) = setup(&env);
+
+        let payload = Bytes::from_array(&env, &[9, 9]);
+        let commitment = hash_answer(&env, &payload);
+        client.open_round(&50, &commitment, &AnswerNormalization::None, &100, &0);
+        assert_eq!(
+            client.try_reveal_answer(&50, &payload),
+            Err(Ok(Error::RoundStillOpen))
+        );
+
+        client.close_round(&50);
+        assert_eq!(
+            client.try_reveal_answer(&50, &Bytes::from_array(&env, &[9])),
+            Err(Ok(Error::CommitmentMismatch))
+        );
+        assert_eq!(client.get_revealed(&50), None);
+
+        client.reveal_answer(&50, &payload);
+        assert_eq!(
+            env.events().all().filter_by_contract(&trivia_id),
+            [AnswerRevealed {
+                round_id: 50,
+                answer_payload: payload.clone(),
+            }
+            .to_xdr(&env, &trivia_id)]
+        );
+        assert_eq!(client.get_revealed(&50), Some(payload.clone()));
+        assert_eq!(
+            client.try_reveal_answer(&50, &payload),
+            Err(Ok(Error::AlreadyRevealed))
+        );
+    }
+
     #[test]
     fn test_unauthorized_admin_calls_rejected() {
         let env = Env::default();
@@ -696,16 +1722,106 @@ mod test {
                     &env,
                     6u64.into_val(&env),
                     commitment.into_val(&env),
-                    100i128.into_val(&env)
+                    100i128.into_val(&env),
+                    0u64.into_val(&env)
                 ],
                 sub_invokes: &[],
             },
         }]);
 
-        let result = client.try_open_round(&6, &commitment, &100);
+        let result = client.try_open_round(&6, &commitment, &AnswerNormalization::None, &100, &0);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_submission_deadline_rejects_late_answers() {
+        let env = Env::default();
+        let (client, _admin, player, _trivia_id, _balance) = setup(&env);
+        env.ledger().set_timestamp(1_000);
+
+        let payload = Bytes::from_array(&env, &[6]);
+        let commitment = hash_answer(&env, &payload);
+        client.open_round(&30, &commitment, &AnswerNormalization::None, &100, &2_000);
+
+        env.ledger().set_timestamp(1_999);
+        client.submit_answer(&player, &30, &payload);
+        assert!(client.get_submission(&30, &player).unwrap().correct);
+
+        env.ledger().set_timestamp(2_000);
+        let late = Address::generate(&env);
+        assert_eq!(
+            client.try_submit_answer(&late, &30, &payload),
+            Err(Ok(Error::SubmissionWindowClosed))
+        );
+
+        // The admin still finalizes the round after the deadline.
+        client.close_round(&30);
+        assert_eq!(client.get_round(&30).unwrap().winner_count, 1);
+        assert_eq!(client.claim_reward(&player, &30), 100);
+    }
+
+    #[test]
+    fn test_open_round_rejects_deadline_not_in_future() {
+        let env = Env::default();
+        let (client, _admin, _player, _trivia_id, _balance, pool) = setup_with_pool(&env);
+        env.ledger().set_timestamp(1_000);
+
+        let commitment = hash_answer(&env, &Bytes::from_array(&env, &[6]));
+        for closes_at in [999u64, 1_000] {
+            assert_eq!(
+                client.try_open_round(
+                    &31,
+                    &commitment,
+                    &AnswerNormalization::None,
+                    &100,
+                    &closes_at
+                ),
+                Err(Ok(Error::DeadlinePassed))
+            );
+        }
+        assert_eq!(
+            client.try_open_mc_round(&31, &commitment, &4, &100, &1_000),
+            Err(Ok(Error::DeadlinePassed))
+        );
+        assert!(client.get_round(&31).is_none());
+        assert!(!pool.is_reserved(&31));
+
+        client.open_round(&31, &commitment, &AnswerNormalization::None, &100, &1_001);
+        assert!(pool.is_reserved(&31));
+    }
+
+    #[test]
+    fn test_daily_attempt_limit_resets_next_day() {
+        let env = Env::default();
+        let (client, _admin, player, _trivia_id, _balance) = setup(&env);
+        assert_eq!(client.remaining_daily_attempts(&player), None);
+
+        let payload = Bytes::from_array(&env, &[5]);
+        let commitment = hash_answer(&env, &payload);
+        for round_id in 20..24u64 {
+            client.open_round(&round_id, &commitment, &AnswerNormalization::None, &100, &0);
+        }
+        client.set_max_daily_attempts(&2);
+        assert_eq!(client.remaining_daily_attempts(&player), Some(2));
+
+        client.submit_answer(&player, &20, &payload);
+        client.submit_answer(&player, &21, &payload);
+        assert_eq!(client.remaining_daily_attempts(&player), Some(0));
+        assert_eq!(
+            client.try_submit_answer(&player, &22, &payload),
+            Err(Ok(Error::DailyLimitReached))
+        );
+
+        // Another player has their own counter.
+        let other = Address::generate(&env);
+        client.submit_answer(&other, &22, &payload);
+
+        env.ledger().with_mut(|li| li.timestamp += SECONDS_PER_DAY);
+        assert_eq!(client.remaining_daily_attempts(&player), Some(2));
+        client.submit_answer(&player, &23, &payload);
+        assert_eq!(client.remaining_daily_attempts(&player), Some(1));
+    }
+
     #[test]
     fn test_round_snapshot_no_round() {
         let env = Env::default();
@@ -724,7 +1840,7 @@ mod test {
 
         let payload = Bytes::from_array(&env, &[3, 1, 4]);
         let commitment = hash_answer(&env, &payload);
-        client.open_round(&7, &commitment, &250);
+        client.open_round(&7, &commitment, &AnswerNormalization::None, &250, &0);
         client.submit_answer(&player, &7, &payload);
 
         let snapshot = client.get_round_snapshot();
@@ -744,7 +1860,7 @@ mod test {
 
         let payload = Bytes::from_array(&env, &[6, 2]);
         let commitment = hash_answer(&env, &payload);
-        client.open_round(&8, &commitment, &300);
+        client.open_round(&8, &commitment, &AnswerNormalization::None, &300, &0);
         client.submit_answer(&player, &8, &payload);
         client.close_round(&8);
 
@@ -1,7 +1,7 @@
 #![no_std]
 use soroban_sdk::{
     contract, contracterror, contractevent, contractimpl, contracttype,
-    Address, Env, String,
+    Address, Env, String, TryFromVal, Val, Vec,
 };
 
 #[contracterror]
@@ -13,6 +13,9 @@ pub enum Error {
     InsufficientBalance = 3,
     InvalidAmount = 4,
     Overflow = 5,
+    InsufficientAllowance = 6,
+    InvalidExpiration = 7,
+    NotInitialized = 8,
 }
 
 #[contracttype]
@@ -24,6 +27,29 @@ pub enum DataKey {
     Decimals,
     Balance(Address),
     TotalSupply,
+    Allowance(Address, Address),
+    NonCirculating,
+    /// The `n`th balance checkpoint of an account, oldest first.
+    Checkpoint(Address, u32),
+    /// Number of balance checkpoints written for an account.
+    CheckpointCount(Address),
+}
+
+/// An account's balance as of the end of `ledger`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalanceCheckpoint {
+    pub ledger: u32,
+    pub balance: i128,
+}
+
+/// Stored allowance for a `(from, spender)` pair. The allowance is only
+/// usable while `env.ledger().sequence() <= expiration_ledger`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
 }
 
 // ── Events ────────────────────────────────────────────────────────
@@ -59,6 +85,16 @@ pub struct TokenTransferred {
     pub amount: i128,
 }
 
+#[contractevent]
+pub struct TokenApproved {
+    #[topic]
+    pub from: Address,
+    #[topic]
+    pub spender: Address,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
 #[contract]
 pub struct GovernanceToken;
 
@@ -108,7 +144,7 @@ impl GovernanceToken {
 
         let balance = Self::balance(env.clone(), to.clone());
         let new_balance = balance.checked_add(amount).ok_or(Error::Overflow)?;
-        env.storage().persistent().set(&DataKey::Balance(to.clone()), &new_balance);
+        Self::write_balance(&env, &to, new_balance);
 
         let total_supply = Self::total_supply(env.clone());
         let new_total_supply = total_supply.checked_add(amount).ok_or(Error::Overflow)?;
@@ -134,7 +170,7 @@ impl GovernanceToken {
         }
 
         let new_balance = balance.checked_sub(amount).ok_or(Error::Overflow)?;
-        env.storage().persistent().set(&DataKey::Balance(from.clone()), &new_balance);
+        Self::write_balance(&env, &from, new_balance);
 
         let total_supply = Self::total_supply(env.clone());
         let new_total_supply = total_supply.checked_sub(amount).ok_or(Error::Overflow)?;
@@ -157,11 +193,115 @@ impl GovernanceToken {
         }
 
         let new_balance_from = balance_from.checked_sub(amount).ok_or(Error::Overflow)?;
-        env.storage().persistent().set(&DataKey::Balance(from.clone()), &new_balance_from);
+        Self::write_balance(&env, &from, new_balance_from);
+
+        let balance_to = Self::balance(env.clone(), to.clone());
+        let new_balance_to = balance_to.checked_add(amount).ok_or(Error::Overflow)?;
+        Self::write_balance(&env, &to, new_balance_to);
+
+        TokenTransferred { from, to, amount }.publish(&env);
+        Ok(())
+    }
+
+    /// Sets `spender`'s allowance over `from`'s tokens. The allowance expires
+    /// once the ledger sequence passes `expiration_ledger`. A zero amount may
+    /// use any expiration; a positive amount must not already be expired.
+    pub fn approve(
+        env: Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) -> Result<(), Error> {
+        if amount < 0 {
+            return Err(Error::InvalidAmount);
+        }
+        from.require_auth();
+
+        if amount > 0 && expiration_ledger < env.ledger().sequence() {
+            return Err(Error::InvalidExpiration);
+        }
+
+        Self::write_allowance(&env, from, spender, amount, expiration_ledger);
+        Ok(())
+    }
+
+    /// Sets several allowances for `from` under a single authorization.
+    /// Each `(spender, amount, expiration_ledger)` entry follows the same
+    /// rules as `approve`; all entries are validated before any is stored.
+    pub fn approve_batch(
+        env: Env,
+        from: Address,
+        approvals: Vec<(Address, i128, u32)>,
+    ) -> Result<(), Error> {
+        for (_, amount, _) in approvals.iter() {
+            if amount < 0 {
+                return Err(Error::InvalidAmount);
+            }
+        }
+        from.require_auth();
+
+        let sequence = env.ledger().sequence();
+        for (_, amount, expiration_ledger) in approvals.iter() {
+            if amount > 0 && expiration_ledger < sequence {
+                return Err(Error::InvalidExpiration);
+            }
+        }
+
+        for (spender, amount, expiration_ledger) in approvals.iter() {
+            Self::write_allowance(&env, from.clone(), spender, amount, expiration_ledger);
+        }
+        Ok(())
+    }
+
+    /// Returns the spendable allowance. Expired allowances read as zero.
+    pub fn allowance(env: Env, from: Address, spender: Address) -> i128 {
+        let key = DataKey::Allowance(from, spender);
+        match env.storage().persistent().get::<_, AllowanceValue>(&key) {
+            Some(value) if env.ledger().sequence() <= value.expiration_ledger => value.amount,
+            _ => 0,
+        }
+    }
+
+    /// Transfers tokens on behalf of `from` using the spender's allowance.
+    /// An expired allowance is treated as zero.
+    pub fn transfer_from(
+        env: Env,
+        spender: Address,
+        from: Address,
+        to: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        spender.require_auth();
+
+        let key = DataKey::Allowance(from.clone(), spender);
+        let mut allowance: AllowanceValue = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .filter(|a: &AllowanceValue| env.ledger().sequence() <= a.expiration_ledger)
+            .ok_or(Error::InsufficientAllowance)?;
+        if allowance.amount < amount {
+            return Err(Error::InsufficientAllowance);
+        }
+
+        let balance_from = Self::balance(env.clone(), from.clone());
+        if balance_from < amount {
+            return Err(Error::InsufficientBalance);
+        }
+
+        allowance.amount = allowance.amount.checked_sub(amount).ok_or(Error::Overflow)?;
+        env.storage().persistent().set(&key, &allowance);
+
+        let new_balance_from = balance_from.checked_sub(amount).ok_or(Error::Overflow)?;
+        Self::write_balance(&env, &from, new_balance_from);
 
         let balance_to = Self::balance(env.clone(), to.clone());
         let new_balance_to = balance_to.checked_add(amount).ok_or(Error::Overflow)?;
-        env.storage().persistent().set(&DataKey::Balance(to.clone()), &new_balance_to);
+        Self::write_balance(&env, &to, new_balance_to);
 
         TokenTransferred { from, to, amount }.publish(&env);
         Ok(())
@@ -171,28 +311,155 @@ impl GovernanceToken {
         env.storage().persistent().get(&DataKey::Balance(id)).unwrap_or(0i128)
     }
 
+    /// Returns `id`'s balance as of the end of `ledger`, for vote-weight
+    /// snapshots. The current ledger's balance can still change, so callers
+    /// should ask about a ledger that has already closed.
+    pub fn balance_at(env: Env, id: Address, ledger: u32) -> i128 {
+        let count: u32 =
+            env.storage().persistent().get(&DataKey::CheckpointCount(id.clone())).unwrap_or(0);
+        if count == 0 {
+            // Never changed since checkpointing began
+            return Self::balance(env, id);
+        }
+
+        // Find the first checkpoint written after `ledger`; the one before it holds
+        // the balance at `ledger`.
+        let (mut low, mut high) = (0u32, count);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if Self::checkpoint(&env, &id, mid).ledger <= ledger {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        if low == 0 {
+            return 0;
+        }
+        Self::checkpoint(&env, &id, low - 1).balance
+    }
+
     pub fn total_supply(env: Env) -> i128 {
         env.storage().instance().get(&DataKey::TotalSupply).unwrap_or(0i128)
     }
 
-    pub fn name(env: Env) -> String {
-        env.storage().instance().get(&DataKey::Name).unwrap()
+    /// Adds or removes an address (treasury, frozen account, ...) from the
+    /// non-circulating set. Only admin can call.
+    pub fn set_non_circulating(env: Env, account: Address, excluded: bool) -> Result<(), Error> {
+        let admin: Address =
+            env.storage().instance().get(&DataKey::Admin).ok_or(Error::NotInitialized)?;
+        admin.require_auth();
+
+        let mut accounts = Self::non_circulating(env.clone());
+        match (accounts.first_index_of(&account), excluded) {
+            (None, true) => accounts.push_back(account),
+            (Some(index), false) => {
+                accounts.remove(index);
+            }
+            _ => return Ok(()),
+        }
+        env.storage().instance().set(&DataKey::NonCirculating, &accounts);
+        Ok(())
+    }
+
+    /// Returns the addresses excluded from circulating supply.
+    pub fn non_circulating(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::NonCirculating)
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Total supply minus the balances held by non-circulating addresses.
+    pub fn circulating_supply(env: Env) -> i128 {
+        let mut circulating = Self::total_supply(env.clone());
+        for account in Self::non_circulating(env.clone()).iter() {
+            circulating -= Self::balance(env.clone(), account);
+        }
+        circulating
+    }
+
+    pub fn name(env: Env) -> Result<String, Error> {
+        Self::metadata(&env, &DataKey::Name)
+    }
+
+    pub fn symbol(env: Env) -> Result<String, Error> {
+        Self::metadata(&env, &DataKey::Symbol)
+    }
+
+    pub fn decimals(env: Env) -> Result<u32, Error> {
+        Self::metadata(&env, &DataKey::Decimals)
+    }
+
+    fn write_allowance(
+        env: &Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) {
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        env.storage().persistent().set(
+            &key,
+            &AllowanceValue {
+                amount,
+                expiration_ledger,
+            },
+        );
+
+        TokenApproved {
+            from,
+            spender,
+            amount,
+            expiration_ledger,
+        }
+        .publish(env);
+    }
+
+    /// Stores `id`'s new balance and checkpoints it at the current ledger.
+    /// Several changes within one ledger share a single checkpoint.
+    fn write_balance(env: &Env, id: &Address, balance: i128) {
+        let previous = Self::balance(env.clone(), id.clone());
+        env.storage().persistent().set(&DataKey::Balance(id.clone()), &balance);
+
+        let ledger = env.ledger().sequence();
+        let count_key = DataKey::CheckpointCount(id.clone());
+        let mut count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        if count == 0 && previous != 0 {
+            // A balance held before checkpointing began counts as held since ledger 0
+            let first = BalanceCheckpoint { ledger: 0, balance: previous };
+            env.storage().persistent().set(&DataKey::Checkpoint(id.clone(), 0), &first);
+            count = 1;
+        }
+
+        // Overwrite this ledger's checkpoint if there is one, else append
+        let mut index = count;
+        if count > 0 && Self::checkpoint(env, id, count - 1).ledger == ledger {
+            index = count - 1;
+        } else {
+            count += 1;
+        }
+        let checkpoint = BalanceCheckpoint { ledger, balance };
+        env.storage().persistent().set(&DataKey::Checkpoint(id.clone(), index), &checkpoint);
+        env.storage().persistent().set(&count_key, &count);
     }
 
-    pub fn symbol(env: Env) -> String {
-        env.storage().instance().get(&DataKey::Symbol).unwrap()
+    fn checkpoint(env: &Env, id: &Address, index: u32) -> BalanceCheckpoint {
+        env.storage().persistent().get(&DataKey::Checkpoint(id.clone(), index)).unwrap()
     }
 
-    pub fn decimals(env: Env) -> u32 {
-        env.storage().instance().get(&DataKey::Decimals).unwrap()
+    /// Reads a metadata entry written by `init`, failing with
+    /// `NotInitialized` instead of a host panic when it is absent.
+    fn metadata<V: TryFromVal<Env, Val>>(env: &Env, key: &DataKey) -> Result<V, Error> {
+        env.storage().instance().get(key).ok_or(Error::NotInitialized)
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::testutils::{Address as _, MockAuth, MockAuthInvoke};
-    use soroban_sdk::{IntoVal};
+    use soroban_sdk::testutils::{Address as _, Events as _, Ledger, MockAuth, MockAuthInvoke};
+    use soroban_sdk::{Event, IntoVal};
 
     #[test]
     fn test_token_flow() {
@@ -226,6 +493,70 @@ mod test {
         assert_eq!(client.total_supply(), 900);
     }
 
+    #[test]
+    fn test_balance_at_reads_past_ledgers() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let user1 = Address::generate(&env);
+        let user2 = Address::generate(&env);
+
+        let contract_id = env.register(GovernanceToken, ());
+        let client = GovernanceTokenClient::new(&env, &contract_id);
+        client.init(
+            &admin,
+            &String::from_str(&env, "Test"),
+            &String::from_str(&env, "T"),
+            &0,
+        );
+
+        env.ledger().set_sequence_number(10);
+        client.mint(&user1, &1000);
+        env.ledger().set_sequence_number(20);
+        client.transfer(&user1, &user2, &400);
+        // Same-ledger changes collapse into one checkpoint
+        client.transfer(&user1, &user2, &100);
+        env.ledger().set_sequence_number(30);
+        client.burn(&user2, &500);
+
+        assert_eq!(client.balance_at(&user1, &9), 0);
+        assert_eq!(client.balance_at(&user1, &10), 1000);
+        assert_eq!(client.balance_at(&user1, &19), 1000);
+        assert_eq!(client.balance_at(&user1, &20), 500);
+        assert_eq!(client.balance_at(&user1, &u32::MAX), 500);
+        assert_eq!(client.balance_at(&user2, &19), 0);
+        assert_eq!(client.balance_at(&user2, &25), 500);
+        assert_eq!(client.balance_at(&user2, &30), 0);
+
+        // Accounts that never held tokens have no history
+        assert_eq!(client.balance_at(&admin, &30), 0);
+    }
+
+    #[test]
+    fn test_metadata_before_init() {
+        let env = Env::default();
+        let contract_id = env.register(GovernanceToken, ());
+        let client = GovernanceTokenClient::new(&env, &contract_id);
+
+        assert_eq!(client.try_name(), Err(Ok(Error::NotInitialized)));
+        assert_eq!(client.try_symbol(), Err(Ok(Error::NotInitialized)));
+        assert_eq!(client.try_decimals(), Err(Ok(Error::NotInitialized)));
+        assert_eq!(client.total_supply(), 0);
+
+        env.mock_all_auths();
+        let admin = Address::generate(&env);
+        client.init(
+            &admin,
+            &String::from_str(&env, "StellarCade Governance"),
+            &String::from_str(&env, "SCG"),
+            &7,
+        );
+        assert_eq!(client.name(), String::from_str(&env, "StellarCade Governance"));
+        assert_eq!(client.symbol(), String::from_str(&env, "SCG"));
+        assert_eq!(client.decimals(), 7);
+    }
+
     #[test]
     #[should_panic(expected = "Error(Auth, InvalidAction)")]
     fn test_unauthorized_mint() {
@@ -258,4 +589,135 @@ mod test {
 
         client.mint(&user, &1000);
     }
+
+    #[test]
+    fn test_allowance_expires() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let spender = Address::generate(&env);
+        let recipient = Address::generate(&env);
+
+        let contract_id = env.register(GovernanceToken, ());
+        let client = GovernanceTokenClient::new(&env, &contract_id);
+        client.init(
+            &admin,
+            &String::from_str(&env, "Test"),
+            &String::from_str(&env, "T"),
+            &0,
+        );
+        client.mint(&owner, &1000);
+
+        let now = env.ledger().sequence();
+        client.approve(&owner, &spender, &500, &(now + 10));
+        assert_eq!(client.allowance(&owner, &spender), 500);
+
+        client.transfer_from(&spender, &owner, &recipient, &100);
+        assert_eq!(client.allowance(&owner, &spender), 400);
+        assert_eq!(client.balance(&recipient), 100);
+
+        // Still usable on the expiration ledger itself.
+        env.ledger().set_sequence_number(now + 10);
+        assert_eq!(client.allowance(&owner, &spender), 400);
+
+        env.ledger().set_sequence_number(now + 11);
+        assert_eq!(client.allowance(&owner, &spender), 0);
+
+        let result = client.try_transfer_from(&spender, &owner, &recipient, &1);
+        assert_eq!(result, Err(Ok(Error::InsufficientAllowance)));
+        assert_eq!(client.balance(&owner), 900);
+    }
+
+    #[test]
+    fn test_approve_batch() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let owner = Address::generate(&env);
+        let spender_a = Address::generate(&env);
+        let spender_b = Address::generate(&env);
+
+        let contract_id = env.register(GovernanceToken, ());
+        let client = GovernanceTokenClient::new(&env, &contract_id);
+        client.init(
+            &admin,
+            &String::from_str(&env, "Test"),
+            &String::from_str(&env, "T"),
+            &0,
+        );
+
+        let now = env.ledger().sequence();
+        let mut approvals = Vec::new(&env);
+        approvals.push_back((spender_a.clone(), 300i128, now + 10));
+        approvals.push_back((spender_b.clone(), 50i128, now + 20));
+        client.approve_batch(&owner, &approvals);
+
+        assert_eq!(
+            env.events().all().filter_by_contract(&contract_id),
+            [
+                TokenApproved {
+                    from: owner.clone(),
+                    spender: spender_a.clone(),
+                    amount: 300,
+                    expiration_ledger: now + 10,
+                }
+                .to_xdr(&env, &contract_id),
+                TokenApproved {
+                    from: owner.clone(),
+                    spender: spender_b.clone(),
+                    amount: 50,
+                    expiration_ledger: now + 20,
+                }
+                .to_xdr(&env, &contract_id),
+            ]
+        );
+        assert_eq!(client.allowance(&owner, &spender_a), 300);
+        assert_eq!(client.allowance(&owner, &spender_b), 50);
+
+        // A negative entry rejects the whole batch.
+        let mut bad = Vec::new(&env);
+        bad.push_back((spender_a.clone(), 10i128, now + 10));
+        bad.push_back((spender_b.clone(), -1i128, now + 10));
+        let result = client.try_approve_batch(&owner, &bad);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+        assert_eq!(client.allowance(&owner, &spender_a), 300);
+    }
+
+    #[test]
+    fn test_circulating_supply_excludes_designated_accounts() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let admin = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let user = Address::generate(&env);
+
+        let contract_id = env.register(GovernanceToken, ());
+        let client = GovernanceTokenClient::new(&env, &contract_id);
+        client.init(
+            &admin,
+            &String::from_str(&env, "Test"),
+            &String::from_str(&env, "T"),
+            &0,
+        );
+        client.mint(&treasury, &700);
+        client.mint(&user, &300);
+        assert_eq!(client.circulating_supply(), 1000);
+
+        client.set_non_circulating(&treasury, &true);
+        client.set_non_circulating(&treasury, &true);
+        assert_eq!(client.non_circulating().len(), 1);
+        assert_eq!(client.total_supply(), 1000);
+        assert_eq!(client.circulating_supply(), 300);
+
+        // Tokens leaving the treasury enter circulation.
+        client.transfer(&treasury, &user, &200);
+        assert_eq!(client.circulating_supply(), 500);
+
+        client.set_non_circulating(&treasury, &false);
+        assert_eq!(client.circulating_supply(), 1000);
+    }
 }
@@ -4,7 +4,7 @@
 //! vote on proposals. Passed proposals enter a timelock queue before execution.
 //!
 //! ## Governance Flow
-//! 1. Proposer calls `propose` with proposal_id and payload_hash
+//! 1. Proposer calls `propose` with proposal_id, payload_hash and description_hash
 //! 2. Token holders call `vote` with support (for/against) weighted by holdings
 //! 3. After voting period ends, if quorum + threshold met: anyone calls `queue`
 //! 4. After timelock delay: anyone calls `execute` with payload
@@ -19,10 +19,24 @@
 #![allow(unexpected_cfgs)]
 
 use soroban_sdk::{
-    contract, contracterror, contractevent, contractimpl, contracttype, token::TokenClient,
-    Address, BytesN, Env,
+    contract, contractclient, contracterror, contractevent, contractimpl, contracttype, vec,
+    Address, BytesN, Env, IntoVal, Symbol,
 };
 
+// ---------------------------------------------------------------------------
+// External contract clients
+// ---------------------------------------------------------------------------
+
+#[contractclient(name = "VotesTokenClient")]
+pub trait VotesToken {
+    fn balance_at(env: Env, id: Address, ledger: u32) -> i128;
+}
+
+#[contractclient(name = "StakingClient")]
+pub trait StakingContract {
+    fn staked_at(env: Env, user: Address, ledger: u32) -> i128;
+}
+
 // ---------------------------------------------------------------------------
 // Constants
 // ---------------------------------------------------------------------------
@@ -60,6 +74,11 @@ pub enum Error {
     AlreadyVoted = 12,
     InvalidPayload = 13,
     Overflow = 14,
+    WeightSourceUnavailable = 15,
+    PreconditionFailed = 16,
+    AlreadyExtended = 17,
+    InvalidExtension = 18,
+    ExtensionTooLong = 19,
 }
 
 // ---------------------------------------------------------------------------
@@ -75,8 +94,32 @@ pub enum DataKey {
     TimelockDelay, // ledgers
     QuorumBps,     // basis points of total supply
     ThresholdBps,  // basis points of votes cast
+    StakingContract,
     Proposal(u64),
     Vote(u64, Address), // (proposal_id, voter)
+    Guard(u64),         // execution precondition, if any
+}
+
+/// Where a proposal's vote weight comes from.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum WeightSource {
+    /// Governance token balance
+    Token = 0,
+    /// Amount staked in the configured staking contract
+    Staked = 1,
+    /// Token balance plus staked amount
+    Sum = 2,
+}
+
+/// On-chain precondition re-checked by `execute`: `contract.selector(proposal_id)`
+/// must return `true`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalGuard {
+    pub contract: Address,
+    pub selector: Symbol,
 }
 
 #[contracttype]
@@ -84,12 +127,20 @@ pub enum DataKey {
 pub struct Proposal {
     pub proposer: Address,
     pub payload_hash: BytesN<32>,
+    /// SHA-256 of the off-chain proposal description document.
+    pub description_hash: BytesN<32>,
     pub start_ledger: u32,
     pub end_ledger: u32,
     pub for_votes: i128,
     pub against_votes: i128,
     pub state: u32,
     pub eta: u32, // execution timestamp (ledger) after queueing
+    pub weight_source: WeightSource,
+    /// Set once `extend_voting` has been used; a proposal is extended at most once
+    pub extended: bool,
+    /// Vote weight is read as of the end of this ledger, the one before the
+    /// proposal was created, so tokens moved afterwards cannot vote twice
+    pub snapshot_ledger: u32,
 }
 
 #[contracttype]
@@ -108,6 +159,28 @@ pub struct ProposalSummary {
     pub execution_eta: u32,
 }
 
+/// Countdown view of a proposal's phases, measured in ledgers.
+///
+/// All `*_in` fields are ledgers remaining from the current ledger and clamp
+/// to zero once the boundary has passed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProposalTiming {
+    pub proposal_id: u64,
+    pub state: u32,
+    pub current_ledger: u32,
+    /// Ledger at which voting closes (`end_ledger`)
+    pub voting_end_ledger: u32,
+    /// Ledgers until voting closes
+    pub voting_ends_in: u32,
+    /// Ledgers until `queue` can be called
+    pub queueable_in: u32,
+    /// Queued `eta`, or the projected eta if queued right at voting end
+    pub execution_eta: u32,
+    /// Ledgers until the timelock elapses and `execute` can be called
+    pub executable_in: u32,
+}
+
 // ---------------------------------------------------------------------------
 // Events
 // ---------------------------------------------------------------------------
@@ -118,6 +191,7 @@ pub struct ProposalCreated {
     pub proposal_id: u64,
     pub proposer: Address,
     pub payload_hash: BytesN<32>,
+    pub description_hash: BytesN<32>,
 }
 
 #[contractevent]
@@ -130,6 +204,14 @@ pub struct VoteCast {
     pub weight: i128,
 }
 
+#[contractevent]
+pub struct VotingExtended {
+    #[topic]
+    pub proposal_id: u64,
+    pub additional: u32,
+    pub end_ledger: u32,
+}
+
 #[contractevent]
 pub struct ProposalQueued {
     #[topic]
@@ -197,66 +279,72 @@ impl Governance {
         Ok(())
     }
 
+    /// Set the staking contract used for `Staked` and `Sum` vote weights.
+    pub fn set_staking_contract(env: Env, admin: Address, staking: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::StakingContract, &staking);
+        Ok(())
+    }
+
     /// Create a new proposal. Anyone can propose.
     ///
     /// `payload_hash`: SHA-256 of the action to execute (verified at execution)
+    /// `description_hash`: SHA-256 of the human-readable proposal description
+    /// `weight_source`: how votes on this proposal are weighted; `Staked` and
+    /// `Sum` require a configured staking contract
     pub fn propose(
         env: Env,
         proposer: Address,
         proposal_id: u64,
         payload_hash: BytesN<32>,
+        description_hash: BytesN<32>,
+        weight_source: WeightSource,
     ) -> Result<(), Error> {
-        require_initialized(&env)?;
-        proposer.require_auth();
-
-        let key = DataKey::Proposal(proposal_id);
-        if env.storage().persistent().has(&key) {
-            return Err(Error::ProposalAlreadyExists);
-        }
-
-        let voting_period: u32 = env
-            .storage()
-            .instance()
-            .get(&DataKey::VotingPeriod)
-            .unwrap();
-        let current_ledger = env.ledger().sequence();
-        let start_ledger = current_ledger;
-        let end_ledger = current_ledger
-            .checked_add(voting_period)
-            .ok_or(Error::Overflow)?;
-
-        let proposal = Proposal {
-            proposer: proposer.clone(),
-            payload_hash: payload_hash.clone(),
-            start_ledger,
-            end_ledger,
-            for_votes: 0,
-            against_votes: 0,
-            state: STATE_ACTIVE,
-            eta: 0,
-        };
-
-        env.storage().persistent().set(&key, &proposal);
-        env.storage().persistent().extend_ttl(
-            &key,
-            PERSISTENT_BUMP_LEDGERS,
-            PERSISTENT_BUMP_LEDGERS,
-        );
-
-        ProposalCreated {
-            proposal_id,
+        create_proposal(
+            &env,
             proposer,
+            proposal_id,
             payload_hash,
-        }
-        .publish(&env);
+            description_hash,
+            weight_source,
+            None,
+        )
+    }
 
-        Ok(())
+    /// Create a proposal whose execution is gated by an on-chain precondition.
+    ///
+    /// `execute` calls `guard.contract.<guard.selector>(proposal_id)` and
+    /// aborts with `PreconditionFailed` unless it returns `true`, so a
+    /// proposal can be tied to conditions such as a minimum treasury
+    /// balance that may have changed during the timelock.
+    pub fn propose_guarded(
+        env: Env,
+        proposer: Address,
+        proposal_id: u64,
+        payload_hash: BytesN<32>,
+        description_hash: BytesN<32>,
+        weight_source: WeightSource,
+        guard: ProposalGuard,
+    ) -> Result<(), Error> {
+        create_proposal(
+            &env,
+            proposer,
+            proposal_id,
+            payload_hash,
+            description_hash,
+            weight_source,
+            Some(guard),
+        )
     }
 
     /// Cast a vote on an active proposal.
     ///
     /// `support`: true = for, false = against
-    /// `weight`: voter's token balance at time of vote (verified on-chain)
+    /// `weight`: voter's token balance, staked amount or both, per the
+    /// proposal's `weight_source`, as of the proposal's `snapshot_ledger`
+    /// (verified on-chain)
     pub fn vote(env: Env, proposal_id: u64, voter: Address, support: bool) -> Result<(), Error> {
         require_initialized(&env)?;
         voter.require_auth();
@@ -283,13 +371,12 @@ impl Governance {
             return Err(Error::AlreadyVoted);
         }
 
-        // Get voter's token balance as voting weight
-        let token: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GovernanceToken)
-            .unwrap();
-        let weight = TokenClient::new(&env, &token).balance(&voter);
+        let weight = vote_weight(
+            &env,
+            proposal.weight_source,
+            &voter,
+            proposal.snapshot_ledger,
+        )?;
 
         if weight <= 0 {
             return Err(Error::NotAuthorized);
@@ -314,6 +401,7 @@ impl Governance {
             PERSISTENT_BUMP_LEDGERS,
             PERSISTENT_BUMP_LEDGERS,
         );
+        extend_guard_ttl(&env, proposal_id);
 
         // Mark voter as voted
         env.storage().persistent().set(&vote_key, &true);
@@ -334,6 +422,74 @@ impl Governance {
         Ok(())
     }
 
+    /// Push back the end of a proposal's voting period by `additional` ledgers.
+    /// Admin only.
+    ///
+    /// Only allowed while voting is still open, only once per proposal, and by
+    /// at most the configured voting period, so it cannot be used to stall
+    /// execution indefinitely.
+    pub fn extend_voting(
+        env: Env,
+        admin: Address,
+        proposal_id: u64,
+        additional: u32,
+    ) -> Result<(), Error> {
+        require_initialized(&env)?;
+        require_admin(&env, &admin)?;
+
+        if additional == 0 {
+            return Err(Error::InvalidExtension);
+        }
+        let voting_period: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::VotingPeriod)
+            .unwrap();
+        if additional > voting_period {
+            return Err(Error::ExtensionTooLong);
+        }
+
+        let proposal_key = DataKey::Proposal(proposal_id);
+        let mut proposal: Proposal = env
+            .storage()
+            .persistent()
+            .get(&proposal_key)
+            .ok_or(Error::ProposalNotFound)?;
+
+        if proposal.state != STATE_ACTIVE {
+            return Err(Error::InvalidProposalState);
+        }
+        if env.ledger().sequence() >= proposal.end_ledger {
+            return Err(Error::VotingPeriodEnded);
+        }
+        if proposal.extended {
+            return Err(Error::AlreadyExtended);
+        }
+
+        proposal.end_ledger = proposal
+            .end_ledger
+            .checked_add(additional)
+            .ok_or(Error::Overflow)?;
+        proposal.extended = true;
+
+        env.storage().persistent().set(&proposal_key, &proposal);
+        env.storage().persistent().extend_ttl(
+            &proposal_key,
+            PERSISTENT_BUMP_LEDGERS,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+        extend_guard_ttl(&env, proposal_id);
+
+        VotingExtended {
+            proposal_id,
+            additional,
+            end_ledger: proposal.end_ledger,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
     /// Queue a succeeded proposal into the timelock. Anyone can call.
     ///
     /// Requirements: voting ended, quorum reached, threshold met
@@ -407,6 +563,7 @@ impl Governance {
             PERSISTENT_BUMP_LEDGERS,
             PERSISTENT_BUMP_LEDGERS,
         );
+        extend_guard_ttl(&env, proposal_id);
 
         Ok(())
     }
@@ -414,6 +571,8 @@ impl Governance {
     /// Execute a queued proposal after timelock. Anyone can call.
     ///
     /// `payload_hash_verify`: must match stored hash (prevents bait-and-switch)
+    /// A guarded proposal also needs its guard to return `true`; otherwise it
+    /// stays queued and `execute` can be retried.
     pub fn execute(
         env: Env,
         proposal_id: u64,
@@ -441,6 +600,17 @@ impl Governance {
             return Err(Error::TimelockNotExpired);
         }
 
+        if let Some(guard) = Self::get_proposal_guard(env.clone(), proposal_id) {
+            let holds: bool = env.invoke_contract(
+                &guard.contract,
+                &guard.selector,
+                vec![&env, proposal_id.into_val(&env)],
+            );
+            if !holds {
+                return Err(Error::PreconditionFailed);
+            }
+        }
+
         proposal.state = STATE_EXECUTED;
         env.storage().persistent().set(&proposal_key, &proposal);
         env.storage().persistent().extend_ttl(
@@ -448,6 +618,7 @@ impl Governance {
             PERSISTENT_BUMP_LEDGERS,
             PERSISTENT_BUMP_LEDGERS,
         );
+        extend_guard_ttl(&env, proposal_id);
 
         ProposalExecuted { proposal_id }.publish(&env);
 
@@ -481,6 +652,7 @@ impl Governance {
             PERSISTENT_BUMP_LEDGERS,
             PERSISTENT_BUMP_LEDGERS,
         );
+        extend_guard_ttl(&env, proposal_id);
 
         ProposalCancelled { proposal_id }.publish(&env);
 
@@ -547,6 +719,7 @@ impl Governance {
             PERSISTENT_BUMP_LEDGERS,
             PERSISTENT_BUMP_LEDGERS,
         );
+        extend_guard_ttl(&env, proposal_id);
 
         ProposalCancelled { proposal_id }.publish(&env);
 
@@ -625,6 +798,52 @@ impl Governance {
         }
     }
 
+    /// Return ledger countdowns for each phase of a proposal.
+    ///
+    /// Queueing is possible as soon as voting ends, so `queueable_in` tracks
+    /// `voting_ends_in` until the proposal leaves the active state. For
+    /// proposals not yet queued, `execution_eta` is projected from the voting
+    /// end plus the configured timelock delay.
+    pub fn proposal_timing(env: Env, proposal_id: u64) -> Result<ProposalTiming, Error> {
+        require_initialized(&env)?;
+
+        let proposal: Proposal = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(Error::ProposalNotFound)?;
+
+        let current_ledger = env.ledger().sequence();
+        let total_votes = proposal
+            .for_votes
+            .checked_add(proposal.against_votes)
+            .unwrap_or(i128::MAX);
+        let state = effective_proposal_state(&env, &proposal, total_votes);
+        let voting_ends_in = proposal.end_ledger.saturating_sub(current_ledger);
+        let queueable_in = if proposal.state == STATE_ACTIVE {
+            voting_ends_in
+        } else {
+            0
+        };
+        let execution_eta = proposal_execution_eta(&env, &proposal);
+
+        Ok(ProposalTiming {
+            proposal_id,
+            state,
+            current_ledger,
+            voting_end_ledger: proposal.end_ledger,
+            voting_ends_in,
+            queueable_in,
+            execution_eta,
+            executable_in: execution_eta.saturating_sub(current_ledger),
+        })
+    }
+
+    /// Return the execution precondition attached by `propose_guarded`.
+    pub fn get_proposal_guard(env: Env, proposal_id: u64) -> Option<ProposalGuard> {
+        env.storage().persistent().get(&DataKey::Guard(proposal_id))
+    }
+
     /// Check if an address has voted on a proposal
     pub fn has_voted(env: Env, proposal_id: u64, voter: Address) -> bool {
         env.storage()
@@ -657,6 +876,136 @@ fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
     Ok(())
 }
 
+/// Weight of `voter` as of the end of `snapshot_ledger`.
+///
+/// A token or staking contract without checkpoint reads (e.g. a plain SAC)
+/// yields `WeightSourceUnavailable` instead of trapping the vote.
+fn vote_weight(
+    env: &Env,
+    source: WeightSource,
+    voter: &Address,
+    snapshot_ledger: u32,
+) -> Result<i128, Error> {
+    let token_weight = || -> Result<i128, Error> {
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::GovernanceToken)
+            .unwrap();
+        match VotesTokenClient::new(env, &token).try_balance_at(voter, &snapshot_ledger) {
+            Ok(Ok(balance)) => Ok(balance),
+            _ => Err(Error::WeightSourceUnavailable),
+        }
+    };
+    let staked_weight = || -> Result<i128, Error> {
+        let staking: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::StakingContract)
+            .ok_or(Error::WeightSourceUnavailable)?;
+        match StakingClient::new(env, &staking).try_staked_at(voter, &snapshot_ledger) {
+            Ok(Ok(amount)) => Ok(amount),
+            _ => Err(Error::WeightSourceUnavailable),
+        }
+    };
+
+    match source {
+        WeightSource::Token => token_weight(),
+        WeightSource::Staked => staked_weight(),
+        WeightSource::Sum => token_weight()?
+            .checked_add(staked_weight()?)
+            .ok_or(Error::Overflow),
+    }
+}
+
+/// Keeps a proposal's guard alive alongside the proposal, so `execute` can
+/// still read it after a long voting period and timelock.
+fn extend_guard_ttl(env: &Env, proposal_id: u64) {
+    let guard_key = DataKey::Guard(proposal_id);
+    if env.storage().persistent().has(&guard_key) {
+        env.storage().persistent().extend_ttl(
+            &guard_key,
+            PERSISTENT_BUMP_LEDGERS,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+    }
+}
+
+fn create_proposal(
+    env: &Env,
+    proposer: Address,
+    proposal_id: u64,
+    payload_hash: BytesN<32>,
+    description_hash: BytesN<32>,
+    weight_source: WeightSource,
+    guard: Option<ProposalGuard>,
+) -> Result<(), Error> {
+    require_initialized(env)?;
+    proposer.require_auth();
+
+    if weight_source != WeightSource::Token
+        && !env.storage().instance().has(&DataKey::StakingContract)
+    {
+        return Err(Error::WeightSourceUnavailable);
+    }
+
+    let key = DataKey::Proposal(proposal_id);
+    if env.storage().persistent().has(&key) {
+        return Err(Error::ProposalAlreadyExists);
+    }
+
+    let voting_period: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::VotingPeriod)
+        .unwrap();
+    let current_ledger = env.ledger().sequence();
+    let start_ledger = current_ledger;
+    let end_ledger = current_ledger
+        .checked_add(voting_period)
+        .ok_or(Error::Overflow)?;
+
+    let proposal = Proposal {
+        proposer: proposer.clone(),
+        payload_hash: payload_hash.clone(),
+        description_hash: description_hash.clone(),
+        start_ledger,
+        end_ledger,
+        for_votes: 0,
+        against_votes: 0,
+        state: STATE_ACTIVE,
+        eta: 0,
+        weight_source,
+        extended: false,
+        snapshot_ledger: current_ledger.saturating_sub(1),
+    };
+
+    env.storage().persistent().set(&key, &proposal);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, PERSISTENT_BUMP_LEDGERS, PERSISTENT_BUMP_LEDGERS);
+
+    if let Some(guard) = guard {
+        let guard_key = DataKey::Guard(proposal_id);
+        env.storage().persistent().set(&guard_key, &guard);
+        env.storage().persistent().extend_ttl(
+            &guard_key,
+            PERSISTENT_BUMP_LEDGERS,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+    }
+
+    ProposalCreated {
+        proposal_id,
+        proposer,
+        payload_hash,
+        description_hash,
+    }
+    .publish(env);
+
+    Ok(())
+}
+
 fn quorum_votes_required(env: &Env) -> i128 {
     let quorum_bps: u32 = env.storage().instance().get(&DataKey::QuorumBps).unwrap();
     if quorum_bps == 0 {
@@ -2,27 +2,43 @@
 
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger},
+    symbol_short,
+    testutils::{storage::Persistent as _, Address as _, Ledger},
     token::StellarAssetClient,
-    Address, Bytes, BytesN, Env,
+    Address, Bytes, BytesN, Env, String, Symbol,
 };
+use stellarcade_governance_token::{GovernanceToken, GovernanceTokenClient};
 
 // -------------------------------------------------------------------
 // Helpers
 // -------------------------------------------------------------------
 
-fn create_token<'a>(env: &'a Env, admin: &Address) -> (Address, StellarAssetClient<'a>) {
-    let contract = env.register_stellar_asset_contract_v2(admin.clone());
-    let client = StellarAssetClient::new(env, &contract.address());
-    (contract.address(), client)
+/// Governance needs a token with `balance_at` checkpoints for snapshot
+/// voting, so tests use the StellarCade governance token.
+fn create_token<'a>(env: &'a Env, admin: &Address) -> (Address, GovernanceTokenClient<'a>) {
+    let contract = env.register(GovernanceToken, ());
+    let client = GovernanceTokenClient::new(env, &contract);
+    env.mock_all_auths();
+    client.init(
+        admin,
+        &String::from_str(env, "StellarCade Governance"),
+        &String::from_str(env, "SCG"),
+        &7,
+    );
+    (contract, client)
 }
 
 fn hash(env: &Env, data: &[u8]) -> BytesN<32> {
     env.crypto().sha256(&Bytes::from_slice(env, data)).into()
 }
 
+fn description(env: &Env) -> BytesN<32> {
+    hash(env, b"description:proposal")
+}
+
 struct Setup<'a> {
     gov_client: GovernanceClient<'a>,
+    token: GovernanceTokenClient<'a>,
     admin: Address,
     voter1: Address,
     voter2: Address,
@@ -34,7 +50,7 @@ fn setup(env: &Env) -> Setup<'_> {
     let voter1 = Address::generate(env);
     let voter2 = Address::generate(env);
 
-    let (token_addr, token_sac) = create_token(env, &token_admin);
+    let (token_addr, token) = create_token(env, &token_admin);
 
     let gov_id = env.register(Governance, ());
     let gov_client = GovernanceClient::new(env, &gov_id);
@@ -45,11 +61,15 @@ fn setup(env: &Env) -> Setup<'_> {
     gov_client.init(&admin, &token_addr, &100u32, &50u32, &1000u32, &6000u32);
 
     // Mint tokens to voters
-    token_sac.mint(&voter1, &1000);
-    token_sac.mint(&voter2, &500);
+    token.mint(&voter1, &1000);
+    token.mint(&voter2, &500);
+    // Close the minting ledger so proposals snapshot these balances
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 1);
 
     Setup {
         gov_client,
+        token,
         admin,
         voter1,
         voter2,
@@ -86,11 +106,18 @@ fn test_propose_creates_proposal() {
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:update_fee");
 
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     let proposal = s.gov_client.get_proposal(&1u64);
     assert_eq!(proposal.proposer, proposer);
     assert_eq!(proposal.payload_hash, payload);
+    assert_eq!(proposal.description_hash, description(&env));
     assert_eq!(proposal.state, STATE_ACTIVE);
     assert_eq!(proposal.for_votes, 0);
     assert_eq!(proposal.against_votes, 0);
@@ -105,7 +132,13 @@ fn test_summary_for_active_proposal() {
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:active_summary");
 
-    s.gov_client.propose(&proposer, &7u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &7u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     let proposal = s.gov_client.get_proposal(&7u64);
     let summary = s.gov_client.get_proposal_summary(&7u64);
@@ -130,7 +163,13 @@ fn test_summary_for_succeeded_proposal_before_queue() {
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:succeeded_summary");
 
-    s.gov_client.propose(&proposer, &8u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &8u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
     s.gov_client.vote(&8u64, &s.voter1, &true);
     s.gov_client.vote(&8u64, &s.voter2, &true);
 
@@ -159,7 +198,13 @@ fn test_summary_for_queued_proposal() {
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:queued_summary");
 
-    s.gov_client.propose(&proposer, &9u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &9u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
     s.gov_client.vote(&9u64, &s.voter1, &true);
     s.gov_client.vote(&9u64, &s.voter2, &true);
 
@@ -190,6 +235,60 @@ fn test_summary_for_missing_proposal() {
     assert_eq!(summary.execution_eta, 0);
 }
 
+#[test]
+fn test_proposal_timing_counts_down_each_phase() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let proposer = Address::generate(&env);
+    let payload = hash(&env, b"action:timing");
+    let start = env.ledger().sequence();
+
+    s.gov_client.propose(
+        &proposer,
+        &11u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
+    s.gov_client.vote(&11u64, &s.voter1, &true);
+
+    let timing = s.gov_client.proposal_timing(&11u64);
+    assert_eq!(timing.state, STATE_ACTIVE);
+    assert_eq!(timing.voting_end_ledger, start + 100);
+    assert_eq!(timing.voting_ends_in, 100);
+    assert_eq!(timing.queueable_in, 100);
+    assert_eq!(timing.execution_eta, start + 150);
+    assert_eq!(timing.executable_in, 150);
+
+    env.ledger().set_sequence_number(start + 40);
+    let timing = s.gov_client.proposal_timing(&11u64);
+    assert_eq!(timing.voting_ends_in, 60);
+    assert_eq!(timing.executable_in, 110);
+
+    env.ledger().set_sequence_number(start + 120);
+    s.gov_client.queue(&11u64);
+    let timing = s.gov_client.proposal_timing(&11u64);
+    assert_eq!(timing.state, STATE_QUEUED);
+    assert_eq!(timing.voting_ends_in, 0);
+    assert_eq!(timing.queueable_in, 0);
+    assert_eq!(timing.execution_eta, start + 170);
+    assert_eq!(timing.executable_in, 50);
+
+    env.ledger().set_sequence_number(start + 200);
+    assert_eq!(s.gov_client.proposal_timing(&11u64).executable_in, 0);
+}
+
+#[test]
+fn test_proposal_timing_missing_proposal() {
+    let env = Env::default();
+    let s = setup(&env);
+
+    let result = s.gov_client.try_proposal_timing(&404u64);
+    assert_eq!(result, Err(Ok(Error::ProposalNotFound)));
+}
+
 #[test]
 fn test_duplicate_proposal_rejected() {
     let env = Env::default();
@@ -199,8 +298,20 @@ fn test_duplicate_proposal_rejected() {
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
 
-    s.gov_client.propose(&proposer, &1u64, &payload);
-    let result = s.gov_client.try_propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
+    let result = s.gov_client.try_propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
     assert!(result.is_err());
 }
 
@@ -216,7 +327,13 @@ fn test_vote_for() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     s.gov_client.vote(&1u64, &s.voter1, &true); // for
 
@@ -234,7 +351,13 @@ fn test_vote_against() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     s.gov_client.vote(&1u64, &s.voter2, &false); // against
 
@@ -251,7 +374,13 @@ fn test_vote_multiple_voters() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     s.gov_client.vote(&1u64, &s.voter1, &true);
     s.gov_client.vote(&1u64, &s.voter2, &false);
@@ -269,7 +398,13 @@ fn test_double_vote_rejected() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     s.gov_client.vote(&1u64, &s.voter1, &true);
     let result = s.gov_client.try_vote(&1u64, &s.voter1, &true);
@@ -284,7 +419,13 @@ fn test_vote_after_period_rejected() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     // Advance ledger past voting period (100 ledgers)
     env.ledger()
@@ -306,7 +447,13 @@ fn test_queue_succeeded_proposal() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     // Vote with 100% for
     s.gov_client.vote(&1u64, &s.voter1, &true);
@@ -331,7 +478,13 @@ fn test_queue_defeated_proposal() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     // Vote with more against than for (doesn't meet 60% threshold)
     s.gov_client.vote(&1u64, &s.voter1, &false); // 1000 against
@@ -356,7 +509,13 @@ fn test_queue_before_voting_ends_rejected() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     s.gov_client.vote(&1u64, &s.voter1, &true);
 
@@ -377,7 +536,13 @@ fn test_execute_queued_proposal() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     s.gov_client.vote(&1u64, &s.voter1, &true);
     s.gov_client.vote(&1u64, &s.voter2, &true);
@@ -405,7 +570,13 @@ fn test_execute_before_timelock_rejected() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     s.gov_client.vote(&1u64, &s.voter1, &true);
     s.gov_client.vote(&1u64, &s.voter2, &true);
@@ -427,7 +598,13 @@ fn test_execute_wrong_payload_rejected() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     s.gov_client.vote(&1u64, &s.voter1, &true);
     s.gov_client.vote(&1u64, &s.voter2, &true);
@@ -457,7 +634,13 @@ fn test_admin_cancel() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     s.gov_client.cancel(&s.admin, &1u64);
 
@@ -473,7 +656,13 @@ fn test_non_admin_cannot_cancel() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     let stranger = Address::generate(&env);
     let result = s.gov_client.try_cancel(&stranger, &1u64);
@@ -492,7 +681,13 @@ fn test_cancel_stale_queued_proposal() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     // Vote with 100% for
     s.gov_client.vote(&1u64, &s.voter1, &true);
@@ -528,7 +723,13 @@ fn test_cancel_stale_premature_rejected() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     // Vote with 100% for
     s.gov_client.vote(&1u64, &s.voter1, &true);
@@ -560,7 +761,13 @@ fn test_cancel_stale_active_proposal_rejected() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     // Try to cancel stale while proposal is still active
     let result = s.gov_client.try_cancel_stale(&1u64);
@@ -575,7 +782,13 @@ fn test_cancel_stale_executed_proposal_rejected() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     // Vote with 100% for
     s.gov_client.vote(&1u64, &s.voter1, &true);
@@ -604,7 +817,13 @@ fn test_cancel_stale_already_cancelled_rejected() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     // Admin cancels first
     s.gov_client.cancel(&s.admin, &1u64);
@@ -622,7 +841,13 @@ fn test_execute_after_cancel_stale_rejected() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     // Vote with 100% for
     s.gov_client.vote(&1u64, &s.voter1, &true);
@@ -651,7 +876,13 @@ fn test_cancel_stale_defeated_proposal_rejected() {
 
     let proposer = Address::generate(&env);
     let payload = hash(&env, b"action:test");
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
 
     // Vote with more against than for (doesn't meet threshold)
     s.gov_client.vote(&1u64, &s.voter1, &false);
@@ -685,7 +916,13 @@ fn test_full_governance_lifecycle() {
     let payload = hash(&env, b"action:upgrade_contract");
 
     // 1. Propose
-    s.gov_client.propose(&proposer, &1u64, &payload);
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
     assert_eq!(s.gov_client.get_proposal(&1u64).state, STATE_ACTIVE);
 
     // 2. Vote
@@ -704,3 +941,338 @@ fn test_full_governance_lifecycle() {
     s.gov_client.execute(&1u64, &payload);
     assert_eq!(s.gov_client.get_proposal(&1u64).state, STATE_EXECUTED);
 }
+
+// -------------------------------------------------------------------
+// 9. Weight sources
+// -------------------------------------------------------------------
+
+#[contract]
+struct MockStaking;
+
+#[contractimpl]
+impl MockStaking {
+    /// Stakes `amount` for `user` from the current ledger on.
+    pub fn set_stake(env: Env, user: Address, amount: i128) {
+        env.storage()
+            .persistent()
+            .set(&user, &(env.ledger().sequence(), amount));
+    }
+
+    pub fn staked_at(env: Env, user: Address, ledger: u32) -> i128 {
+        match env.storage().persistent().get::<_, (u32, i128)>(&user) {
+            Some((since, amount)) if since <= ledger => amount,
+            _ => 0,
+        }
+    }
+}
+
+#[test]
+fn test_staked_source_requires_staking_contract() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let proposer = Address::generate(&env);
+    let payload = hash(&env, b"action:staked");
+    for source in [WeightSource::Staked, WeightSource::Sum] {
+        let result =
+            s.gov_client
+                .try_propose(&proposer, &1u64, &payload, &description(&env), &source);
+        assert_eq!(result, Err(Ok(Error::WeightSourceUnavailable)));
+    }
+}
+
+#[test]
+fn test_vote_weight_follows_proposal_source() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let staking_id = env.register(MockStaking, ());
+    let staking = MockStakingClient::new(&env, &staking_id);
+    staking.set_stake(&s.voter1, &300);
+    s.gov_client.set_staking_contract(&s.admin, &staking_id);
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 1);
+
+    let proposer = Address::generate(&env);
+    let payload = hash(&env, b"action:weights");
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Staked,
+    );
+    s.gov_client.propose(
+        &proposer,
+        &2u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Sum,
+    );
+    assert_eq!(
+        s.gov_client.get_proposal(&1u64).weight_source,
+        WeightSource::Staked
+    );
+
+    s.gov_client.vote(&1u64, &s.voter1, &true);
+    s.gov_client.vote(&2u64, &s.voter1, &true);
+    assert_eq!(s.gov_client.get_proposal(&1u64).for_votes, 300);
+    assert_eq!(s.gov_client.get_proposal(&2u64).for_votes, 1300);
+
+    // voter2 holds tokens but has nothing staked
+    let result = s.gov_client.try_vote(&1u64, &s.voter2, &true);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+}
+
+#[test]
+fn test_vote_weight_is_read_at_snapshot_ledger() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let proposer = Address::generate(&env);
+    let late = Address::generate(&env);
+    let start_ledger = env.ledger().sequence();
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &hash(&env, b"action:snapshot"),
+        &description(&env),
+        &WeightSource::Token,
+    );
+    assert_eq!(
+        s.gov_client.get_proposal(&1u64).snapshot_ledger,
+        start_ledger - 1
+    );
+
+    // Tokens received after the snapshot carry no weight, and the sender
+    // keeps the weight it held at the snapshot.
+    s.token.transfer(&s.voter1, &late, &600);
+    s.token.mint(&late, &100);
+    let result = s.gov_client.try_vote(&1u64, &late, &true);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    s.gov_client.vote(&1u64, &s.voter1, &true);
+    assert_eq!(s.gov_client.get_proposal(&1u64).for_votes, 1000);
+}
+
+#[test]
+fn test_vote_with_non_checkpointing_token_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    // A plain Stellar Asset Contract has no `balance_at`
+    let token_admin = Address::generate(&env);
+    let sac = env.register_stellar_asset_contract_v2(token_admin);
+    let voter = Address::generate(&env);
+    StellarAssetClient::new(&env, &sac.address()).mint(&voter, &1000);
+
+    let admin = Address::generate(&env);
+    let gov_id = env.register(Governance, ());
+    let gov_client = GovernanceClient::new(&env, &gov_id);
+    gov_client.init(&admin, &sac.address(), &100u32, &50u32, &1000u32, &6000u32);
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 1);
+
+    let proposer = Address::generate(&env);
+    gov_client.propose(
+        &proposer,
+        &1u64,
+        &hash(&env, b"action:sac"),
+        &description(&env),
+        &WeightSource::Token,
+    );
+    assert_eq!(
+        gov_client.try_vote(&1u64, &voter, &true),
+        Err(Ok(Error::WeightSourceUnavailable))
+    );
+}
+
+// -------------------------------------------------------------------
+// 10. Execution guards
+// -------------------------------------------------------------------
+
+#[contract]
+struct MockGuard;
+
+#[contractimpl]
+impl MockGuard {
+    pub fn set_ok(env: Env, ok: bool) {
+        env.storage().instance().set(&symbol_short!("ok"), &ok);
+    }
+
+    pub fn treasury_ok(env: Env, _proposal_id: u64) -> bool {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("ok"))
+            .unwrap_or(false)
+    }
+}
+
+#[test]
+fn test_execute_rechecks_guard() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let guard_id = env.register(MockGuard, ());
+    let guard = MockGuardClient::new(&env, &guard_id);
+    guard.set_ok(&true);
+
+    let proposer = Address::generate(&env);
+    let payload = hash(&env, b"action:guarded");
+    let proposal_guard = ProposalGuard {
+        contract: guard_id.clone(),
+        selector: Symbol::new(&env, "treasury_ok"),
+    };
+    s.gov_client.propose_guarded(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+        &proposal_guard,
+    );
+    assert_eq!(s.gov_client.get_proposal_guard(&1u64), Some(proposal_guard));
+
+    s.gov_client.vote(&1u64, &s.voter1, &true);
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 101);
+    s.gov_client.queue(&1u64);
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 51);
+
+    // The precondition stopped holding during the timelock.
+    guard.set_ok(&false);
+    let result = s.gov_client.try_execute(&1u64, &payload);
+    assert_eq!(result, Err(Ok(Error::PreconditionFailed)));
+    assert_eq!(s.gov_client.get_proposal(&1u64).state, STATE_QUEUED);
+
+    guard.set_ok(&true);
+    s.gov_client.execute(&1u64, &payload);
+    assert_eq!(s.gov_client.get_proposal(&1u64).state, STATE_EXECUTED);
+}
+
+#[test]
+fn test_guard_ttl_is_extended_with_proposal() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let proposer = Address::generate(&env);
+    s.gov_client.propose_guarded(
+        &proposer,
+        &1u64,
+        &hash(&env, b"action:guard_ttl"),
+        &description(&env),
+        &WeightSource::Token,
+        &ProposalGuard {
+            contract: Address::generate(&env),
+            selector: Symbol::new(&env, "treasury_ok"),
+        },
+    );
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 50);
+    s.gov_client.vote(&1u64, &s.voter1, &true);
+
+    env.as_contract(&s.gov_client.address, || {
+        let storage = env.storage().persistent();
+        assert_eq!(storage.get_ttl(&DataKey::Guard(1)), PERSISTENT_BUMP_LEDGERS);
+        assert_eq!(
+            storage.get_ttl(&DataKey::Guard(1)),
+            storage.get_ttl(&DataKey::Proposal(1))
+        );
+    });
+}
+
+// -------------------------------------------------------------------
+// 11. Voting extension
+// -------------------------------------------------------------------
+
+#[test]
+fn test_extend_voting_moves_deadline_once() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let proposer = Address::generate(&env);
+    let payload = hash(&env, b"action:extend");
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
+    let original_end = s.gov_client.get_proposal(&1u64).end_ledger;
+
+    assert_eq!(
+        s.gov_client.try_extend_voting(&s.admin, &1u64, &0u32),
+        Err(Ok(Error::InvalidExtension))
+    );
+    // Extensions are capped at the configured voting period (100 ledgers)
+    assert_eq!(
+        s.gov_client.try_extend_voting(&s.admin, &1u64, &101u32),
+        Err(Ok(Error::ExtensionTooLong))
+    );
+    assert_eq!(
+        s.gov_client.try_extend_voting(&s.admin, &1u64, &u32::MAX),
+        Err(Ok(Error::ExtensionTooLong))
+    );
+
+    s.gov_client.extend_voting(&s.admin, &1u64, &40u32);
+    let proposal = s.gov_client.get_proposal(&1u64);
+    assert_eq!(proposal.end_ledger, original_end + 40);
+    assert!(proposal.extended);
+
+    // A second extension is rejected
+    assert_eq!(
+        s.gov_client.try_extend_voting(&s.admin, &1u64, &40u32),
+        Err(Ok(Error::AlreadyExtended))
+    );
+
+    // Votes are still accepted past the original deadline
+    env.ledger().set_sequence_number(original_end + 10);
+    s.gov_client.vote(&1u64, &s.voter1, &true);
+    assert_eq!(
+        s.gov_client.try_queue(&1u64),
+        Err(Ok(Error::VotingPeriodActive))
+    );
+
+    env.ledger().set_sequence_number(original_end + 40);
+    s.gov_client.queue(&1u64);
+    assert_eq!(s.gov_client.get_proposal(&1u64).state, STATE_QUEUED);
+}
+
+#[test]
+fn test_extend_voting_requires_open_voting() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.mock_all_auths();
+
+    let proposer = Address::generate(&env);
+    let payload = hash(&env, b"action:extend_late");
+    s.gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description(&env),
+        &WeightSource::Token,
+    );
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        s.gov_client.try_extend_voting(&outsider, &1u64, &10u32),
+        Err(Ok(Error::NotAuthorized))
+    );
+
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 100);
+    assert_eq!(
+        s.gov_client.try_extend_voting(&s.admin, &1u64, &10u32),
+        Err(Ok(Error::VotingPeriodEnded))
+    );
+}
@@ -50,11 +50,21 @@ fn test_governance_with_custom_governance_token() {
     let voter2 = Address::generate(&env);
     token_client.mint(&voter1, &1000);
     token_client.mint(&voter2, &500);
+    // Proposals snapshot the previous ledger, so close the minting ledger
+    env.ledger()
+        .set_sequence_number(env.ledger().sequence() + 1);
 
     // 4. Propose
     let proposer = Address::generate(&env);
     let payload = local_hash(&env, b"action:upgrade");
-    gov_client.propose(&proposer, &1u64, &payload);
+    let description = local_hash(&env, b"description:upgrade");
+    gov_client.propose(
+        &proposer,
+        &1u64,
+        &payload,
+        &description,
+        &WeightSource::Token,
+    );
 
     // 5. Vote
     gov_client.vote(&1u64, &voter1, &true); // 1000 votes for
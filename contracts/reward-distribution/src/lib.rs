@@ -1,9 +1,19 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractevent, contractimpl, contracttype, Address, BytesN, Env,
+    contract, contractclient, contracterror, contractevent, contractimpl, contracttype,
+    symbol_short, token, Address, BytesN, Env, Symbol, Vec,
 };
 
+// ---------------------------------------------------------------------------
+// External contract clients
+// ---------------------------------------------------------------------------
+
+#[contractclient(name = "BalanceClient")]
+pub trait UserBalanceContract {
+    fn credit(env: Env, game: Address, user: Address, amount: i128, reason: Symbol);
+}
+
 // ---------------------------------------------------------------------------
 // TTL / storage constants
 // ---------------------------------------------------------------------------
@@ -13,6 +23,12 @@ const PERSISTENT_BUMP_LEDGERS: u32 = 518_400;
 /// Threshold at which a persistent entry is renewed (~7 days from expiry)
 const PERSISTENT_BUMP_THRESHOLD: u32 = PERSISTENT_BUMP_LEDGERS - 100_800;
 
+/// Multiplier denominator; a multiplier of `BPS_DENOMINATOR` is 1x
+const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Category recorded for campaigns defined without one
+pub const DEFAULT_CATEGORY: Symbol = symbol_short!("unknown");
+
 // ---------------------------------------------------------------------------
 // Errors
 // ---------------------------------------------------------------------------
@@ -32,6 +48,22 @@ pub enum Error {
     AlreadyClaimed = 9,
     InvalidAmount = 10,
     Overflow = 11,
+    BatchInvalid = 12,
+    CampaignPaused = 13,
+    CampaignExpired = 14,
+    CampaignNotExpired = 15,
+    ContractPaused = 16,
+    AlreadyPaused = 17,
+    NotPaused = 18,
+    ContractKilled = 19,
+    SameCampaign = 20,
+    UserCapExceeded = 21,
+    NotVesting = 22,
+    BelowMinimumClaim = 23,
+    TokenMismatch = 24,
+    InsufficientReserve = 25,
+    NotTokenCampaign = 26,
+    AlreadySwept = 27,
 }
 
 // ---------------------------------------------------------------------------
@@ -45,12 +77,36 @@ pub enum DataKey {
     Admin,
     TreasuryContract,
     BalanceContract,
+    /// Contract-wide emergency flags — instance storage
+    Paused,
+    Killed,
+    /// Lowest id `define_reward_campaign_auto` may allocate next — instance
+    CampaignCounter,
+    /// Smallest payout `claim_reward` accepts; absent or 0 = no minimum — instance
+    MinClaim,
+    /// Accrued-but-unclaimed total across all campaigns — instance
+    TotalOutstanding,
+    /// Accrued-but-unclaimed total of every campaign settling through a
+    /// balance contract — persistent, keyed by that contract
+    Outstanding(Address),
     /// Per-campaign state — persistent, keyed by campaign_id
     Campaign(u32),
     /// Accrued reward for (campaign, user) before claim — persistent
     Accrued(u32, Address),
-    /// Claim flag for (campaign, user) — persistent (reentrancy guard)
-    Claimed(u32, Address),
+    /// Cumulative amount claimed by (campaign, user) — persistent
+    ClaimedTotal(u32, Address),
+    /// In-call reentrancy guard for (campaign, user) — temporary, cleared
+    /// before `claim_reward` returns
+    ClaimLock(u32, Address),
+    /// Account allowed to claim on a user's behalf — persistent
+    ClaimDelegate(Address),
+    /// Accrual multiplier in bps for (campaign, user) — persistent; absent = 1x
+    Multiplier(u32, Address),
+    /// Distinct users with an `Accrued` entry in a campaign — persistent
+    RecipientCount(u32),
+    /// Tokens deposited for a `reward_token` campaign and not yet paid
+    /// out — persistent
+    Reserve(u32),
 }
 
 // ---------------------------------------------------------------------------
@@ -75,6 +131,42 @@ pub struct CampaignData {
     /// Remaining distributable balance (budget − already accrued)
     pub remaining: i128,
     pub status: CampaignStatus,
+    /// Paused campaigns reject new accruals
+    pub paused: bool,
+    /// When set, a pause also blocks claims of previously accrued rewards
+    pub block_claims_on_pause: bool,
+    /// Ledger timestamp at which the campaign was defined
+    pub defined_at: u64,
+    /// Ledger timestamp after which accruals are rejected; 0 means no expiry
+    pub expires_at: u64,
+    /// Net amount accrued to users (revocations subtracted)
+    pub total_accrued: i128,
+    /// Amount actually paid out by `claim_reward`
+    pub total_claimed: i128,
+    /// Most a single user may ever accrue (claimed + unclaimed); 0 = unlimited
+    pub per_user_cap: i128,
+    /// Ledger timestamp at which accruals are fully vested; 0 = no vesting
+    pub vesting_end: u64,
+    /// Share of the unvested balance `claim_early` forfeits, in bps
+    pub early_forfeit_bps: u32,
+    /// Off-chain classification (airdrop, referral, ...); `unknown` if unset
+    pub category: Symbol,
+    /// SEP-41 token this campaign pays out in, from its funded reserve;
+    /// `None` = credits through the global `balance_contract`
+    pub reward_token: Option<Address>,
+    /// Set by `sweep_expired`; a campaign is swept at most once
+    pub swept: bool,
+}
+
+/// Financial view of a campaign returned by `campaign_snapshot`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CampaignSnapshot {
+    pub budget: i128,
+    pub remaining: i128,
+    pub total_accrued: i128,
+    pub total_claimed: i128,
+    pub status: CampaignStatus,
 }
 
 // ---------------------------------------------------------------------------
@@ -89,13 +181,104 @@ pub struct ContractInitialized {
     pub balance_contract: Address,
 }
 
+#[contractevent]
+pub struct PauseChanged {
+    pub paused: bool,
+    pub admin: Address,
+}
+
+#[contractevent]
+pub struct KillSwitchTriggered {
+    pub admin: Address,
+    pub reason_hash: BytesN<32>,
+}
+
+#[contractevent]
+pub struct MinClaimChanged {
+    pub min_claim: i128,
+    pub admin: Address,
+}
+
 #[contractevent]
 pub struct CampaignDefined {
     #[topic]
     pub campaign_id: u32,
+    #[topic]
+    pub category: Symbol,
     pub budget: i128,
 }
 
+#[contractevent]
+pub struct CampaignPauseChanged {
+    #[topic]
+    pub campaign_id: u32,
+    pub paused: bool,
+    pub block_claims_on_pause: bool,
+}
+
+#[contractevent]
+pub struct UserCapChanged {
+    #[topic]
+    pub campaign_id: u32,
+    pub per_user_cap: i128,
+}
+
+#[contractevent]
+pub struct VestingChanged {
+    #[topic]
+    pub campaign_id: u32,
+    pub vesting_end: u64,
+    pub early_forfeit_bps: u32,
+}
+
+#[contractevent]
+pub struct MultiplierChanged {
+    #[topic]
+    pub campaign_id: u32,
+    pub user: Address,
+    pub bps: u32,
+}
+
+#[contractevent]
+pub struct CampaignToppedUp {
+    #[topic]
+    pub campaign_id: u32,
+    pub additional: i128,
+    pub new_budget: i128,
+}
+
+#[contractevent]
+pub struct CampaignFunded {
+    #[topic]
+    pub campaign_id: u32,
+    pub amount: i128,
+    pub reserve: i128,
+}
+
+#[contractevent]
+pub struct CampaignClosed {
+    #[topic]
+    pub campaign_id: u32,
+    /// Unaccrued budget that can be returned to the treasury
+    pub reclaimable: i128,
+}
+
+#[contractevent]
+pub struct CampaignSwept {
+    #[topic]
+    pub campaign_id: u32,
+    pub treasury: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct CampaignExhausted {
+    #[topic]
+    pub campaign_id: u32,
+    /// `total_accrued` at the moment the budget ran out
+    pub final_total: i128,
+}
+
 #[contractevent]
 pub struct RewardAccrued {
     #[topic]
@@ -105,12 +288,50 @@ pub struct RewardAccrued {
     pub new_total: i128,
 }
 
+#[contractevent]
+pub struct RewardRevoked {
+    #[topic]
+    pub campaign_id: u32,
+    pub user: Address,
+    pub amount: i128,
+    pub new_total: i128,
+}
+
+#[contractevent]
+pub struct AccrualMigrated {
+    #[topic]
+    pub from_campaign: u32,
+    #[topic]
+    pub to_campaign: u32,
+    pub user: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct ClaimDelegateChanged {
+    #[topic]
+    pub user: Address,
+    pub delegate: Option<Address>,
+}
+
 #[contractevent]
 pub struct RewardClaimed {
     #[topic]
     pub campaign_id: u32,
     pub user: Address,
     pub amount: i128,
+    /// Rules document that authorised the payout
+    pub rules_hash: BytesN<32>,
+}
+
+#[contractevent]
+pub struct EarlyClaimed {
+    #[topic]
+    pub campaign_id: u32,
+    pub user: Address,
+    pub amount: i128,
+    /// Unvested amount returned to the campaign's `remaining` budget
+    pub forfeited: i128,
 }
 
 // ---------------------------------------------------------------------------
@@ -158,6 +379,79 @@ impl RewardDistribution {
         Ok(())
     }
 
+    // -----------------------------------------------------------------------
+    // Emergency controls
+    // -----------------------------------------------------------------------
+
+    /// Pause every mutating method contract-wide.  Admin only.
+    ///
+    /// Reads such as `campaign_state` stay live while paused.
+    pub fn pause(env: Env, admin: Address) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        Self::require_not_killed(&env)?;
+
+        if Self::is_paused(env.clone()) {
+            return Err(Error::AlreadyPaused);
+        }
+
+        env.storage().instance().set(&DataKey::Paused, &true);
+        PauseChanged {
+            paused: true,
+            admin,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Lift a contract-wide pause.  Admin only; impossible once killed.
+    pub fn unpause(env: Env, admin: Address) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        Self::require_not_killed(&env)?;
+
+        if !Self::is_paused(env.clone()) {
+            return Err(Error::NotPaused);
+        }
+
+        env.storage().instance().set(&DataKey::Paused, &false);
+        PauseChanged {
+            paused: false,
+            admin,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Permanently halt all mutations.  Admin only.
+    ///
+    /// `reason_hash` is the SHA-256 of the off-chain incident report.
+    pub fn trigger_kill_switch(
+        env: Env,
+        admin: Address,
+        reason_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+
+        env.storage().instance().set(&DataKey::Killed, &true);
+        env.storage().instance().set(&DataKey::Paused, &true);
+
+        KillSwitchTriggered { admin, reason_hash }.publish(&env);
+        Ok(())
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
+    pub fn is_killed(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Killed)
+            .unwrap_or(false)
+    }
+
     // -----------------------------------------------------------------------
     // Campaign management
     // -----------------------------------------------------------------------
@@ -167,461 +461,3366 @@ impl RewardDistribution {
     /// * `campaign_id` — unique numeric identifier.
     /// * `rules_hash`  — SHA-256 of the off-chain eligibility rules document.
     /// * `budget`      — maximum tokens distributable; must be > 0.
+    /// * `expires_at`  — ledger timestamp after which accruals are rejected;
+    ///                   0 means the campaign never expires.
     pub fn define_reward_campaign(
         env: Env,
         campaign_id: u32,
         rules_hash: BytesN<32>,
         budget: i128,
+        expires_at: u64,
     ) -> Result<(), Error> {
-        let admin = Self::require_initialized(&env)?;
+        Self::define_categorized_campaign(
+            env,
+            campaign_id,
+            rules_hash,
+            budget,
+            expires_at,
+            DEFAULT_CATEGORY,
+        )
+    }
+
+    /// Same as `define_reward_campaign`, tagging the campaign with a
+    /// `category` (e.g. `airdrop`, `referral`, `seasonal`).  The category is
+    /// a topic of `CampaignDefined` so indexers can filter on it.
+    pub fn define_categorized_campaign(
+        env: Env,
+        campaign_id: u32,
+        rules_hash: BytesN<32>,
+        budget: i128,
+        expires_at: u64,
+        category: Symbol,
+    ) -> Result<(), Error> {
+        Self::define_campaign(
+            &env,
+            campaign_id,
+            rules_hash,
+            budget,
+            expires_at,
+            category,
+            None,
+        )
+    }
+
+    /// Same as `define_categorized_campaign`, paying claims in the SEP-41
+    /// `reward_token` instead of crediting the global balance contract.  Used
+    /// for promotions denominated in a different token.  Accruals are capped
+    /// by the tokens deposited with `fund_campaign`, so the campaign never
+    /// owes more of that token than it holds.
+    pub fn define_token_campaign(
+        env: Env,
+        campaign_id: u32,
+        rules_hash: BytesN<32>,
+        budget: i128,
+        expires_at: u64,
+        category: Symbol,
+        reward_token: Address,
+    ) -> Result<(), Error> {
+        Self::define_campaign(
+            &env,
+            campaign_id,
+            rules_hash,
+            budget,
+            expires_at,
+            category,
+            Some(reward_token),
+        )
+    }
+
+    /// Define a new reward campaign under the next free id.  Admin only.
+    ///
+    /// Same as `define_reward_campaign`, but the id is allocated from a
+    /// monotonically increasing counter (starting at 1), skipping ids already
+    /// taken by explicit definitions.  Returns the allocated id.
+    pub fn define_reward_campaign_auto(
+        env: Env,
+        rules_hash: BytesN<32>,
+        budget: i128,
+        expires_at: u64,
+    ) -> Result<u32, Error> {
+        let admin = Self::require_ready_for_mutation(&env)?;
         admin.require_auth();
 
         if budget <= 0 {
             return Err(Error::InvalidAmount);
         }
 
-        let key = DataKey::Campaign(campaign_id);
-        if env.storage().persistent().has(&key) {
-            return Err(Error::CampaignAlreadyExists);
-        }
+        let campaign_id = Self::next_campaign_id(env.clone())?;
+        let next = campaign_id.checked_add(1).ok_or(Error::Overflow)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::CampaignCounter, &next);
 
-        let campaign = CampaignData {
+        Self::store_new_campaign(
+            &env,
+            campaign_id,
             rules_hash,
             budget,
-            remaining: budget,
-            status: CampaignStatus::Active,
-        };
-
-        env.storage().persistent().set(&key, &campaign);
-        env.storage().persistent().extend_ttl(
-            &key,
-            PERSISTENT_BUMP_THRESHOLD,
-            PERSISTENT_BUMP_LEDGERS,
+            expires_at,
+            DEFAULT_CATEGORY,
+            None,
         );
 
-        CampaignDefined { campaign_id, budget }.publish(&env);
-
-        Ok(())
+        Ok(campaign_id)
     }
 
-    // -----------------------------------------------------------------------
-    // Reward accrual
-    // -----------------------------------------------------------------------
+    /// Return the id `define_reward_campaign_auto` would allocate next.
+    pub fn next_campaign_id(env: Env) -> Result<u32, Error> {
+        let mut campaign_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::CampaignCounter)
+            .unwrap_or(1);
+        while env
+            .storage()
+            .persistent()
+            .has(&DataKey::Campaign(campaign_id))
+        {
+            campaign_id = campaign_id.checked_add(1).ok_or(Error::Overflow)?;
+        }
+        Ok(campaign_id)
+    }
 
-    /// Record a pending reward for `user`.  Admin only.
+    /// Define several campaigns in one call.  Admin only.
     ///
-    /// The call is additive — repeated calls accumulate until the user claims.
-    /// The campaign's `remaining` balance is decremented immediately to uphold
-    /// the invariant `Σ accrued ≤ budget`.
-    pub fn accrue_reward(
+    /// Every entry `(campaign_id, rules_hash, budget)` is validated before any
+    /// campaign is stored, so a single bad entry aborts the whole batch.
+    /// Batch-defined campaigns never expire.
+    /// Emits one `CampaignDefined` per campaign.
+    pub fn define_campaigns_batch(
         env: Env,
-        user: Address,
-        campaign_id: u32,
-        amount: i128,
+        entries: Vec<(u32, BytesN<32>, i128)>,
     ) -> Result<(), Error> {
-        let admin = Self::require_initialized(&env)?;
+        let admin = Self::require_ready_for_mutation(&env)?;
         admin.require_auth();
 
-        if amount <= 0 {
-            return Err(Error::InvalidAmount);
+        if entries.is_empty() {
+            return Err(Error::BatchInvalid);
         }
 
-        // Load and validate campaign
-        let campaign_key = DataKey::Campaign(campaign_id);
-        let mut campaign: CampaignData = env
-            .storage()
-            .persistent()
-            .get(&campaign_key)
-            .ok_or(Error::CampaignNotFound)?;
+        for (i, (campaign_id, _, budget)) in entries.iter().enumerate() {
+            if budget <= 0 {
+                return Err(Error::InvalidAmount);
+            }
+            if env
+                .storage()
+                .persistent()
+                .has(&DataKey::Campaign(campaign_id))
+            {
+                return Err(Error::CampaignAlreadyExists);
+            }
+            // Reject ids repeated within the batch itself.
+            for (other_id, _, _) in entries.iter().skip(i + 1) {
+                if other_id == campaign_id {
+                    return Err(Error::CampaignAlreadyExists);
+                }
+            }
+        }
 
-        if campaign.status != CampaignStatus::Active {
-            return Err(Error::CampaignNotActive);
+        for (campaign_id, rules_hash, budget) in entries.iter() {
+            Self::store_new_campaign(
+                &env,
+                campaign_id,
+                rules_hash,
+                budget,
+                0,
+                DEFAULT_CATEGORY,
+                None,
+            );
         }
 
-        let new_remaining = campaign
-            .remaining
-            .checked_sub(amount)
-            .ok_or(Error::Overflow)?;
-        if new_remaining < 0 {
-            return Err(Error::CampaignExhausted);
+        Ok(())
+    }
+
+    /// Pause a campaign.  Admin only.
+    ///
+    /// A paused campaign accepts no new accruals.  Claims of rewards accrued
+    /// before the pause stay open unless `block_claims` is set, so a pause
+    /// never strands funds by default.
+    pub fn pause_campaign(env: Env, campaign_id: u32, block_claims: bool) -> Result<(), Error> {
+        Self::set_campaign_pause(&env, campaign_id, true, block_claims)
+    }
+
+    /// Resume accruals (and claims) on a paused campaign.  Admin only.
+    pub fn unpause_campaign(env: Env, campaign_id: u32) -> Result<(), Error> {
+        Self::set_campaign_pause(&env, campaign_id, false, false)
+    }
+
+    /// Limit how much a single user may accrue in a campaign.  Admin only.
+    ///
+    /// The cap applies to everything ever accrued to the user (claimed plus
+    /// unclaimed), so claiming does not free headroom.  0 removes the cap.
+    /// Existing balances above a newly lowered cap are left untouched.
+    pub fn set_per_user_cap(env: Env, campaign_id: u32, per_user_cap: i128) -> Result<(), Error> {
+        let admin = Self::require_ready_for_mutation(&env)?;
+        admin.require_auth();
+
+        if per_user_cap < 0 {
+            return Err(Error::InvalidAmount);
         }
 
-        // Accumulate user's pending balance
-        let accrued_key = DataKey::Accrued(campaign_id, user.clone());
-        let current_accrued: i128 = env
+        let key = DataKey::Campaign(campaign_id);
+        let mut campaign: CampaignData = env
             .storage()
             .persistent()
-            .get(&accrued_key)
-            .unwrap_or(0i128);
-        let new_accrued = current_accrued.checked_add(amount).ok_or(Error::Overflow)?;
-
-        // Commit campaign state
-        campaign.remaining = new_remaining;
-        if campaign.remaining == 0 {
-            campaign.status = CampaignStatus::Exhausted;
-        }
+            .get(&key)
+            .ok_or(Error::CampaignNotFound)?;
 
-        env.storage().persistent().set(&campaign_key, &campaign);
-        env.storage().persistent().extend_ttl(
-            &campaign_key,
-            PERSISTENT_BUMP_THRESHOLD,
-            PERSISTENT_BUMP_LEDGERS,
-        );
+        campaign.per_user_cap = per_user_cap;
 
-        // Commit accrued balance
-        env.storage().persistent().set(&accrued_key, &new_accrued);
+        env.storage().persistent().set(&key, &campaign);
         env.storage().persistent().extend_ttl(
-            &accrued_key,
+            &key,
             PERSISTENT_BUMP_THRESHOLD,
             PERSISTENT_BUMP_LEDGERS,
         );
 
-        RewardAccrued { campaign_id, user, amount, new_total: new_accrued }.publish(&env);
+        UserCapChanged {
+            campaign_id,
+            per_user_cap,
+        }
+        .publish(&env);
 
         Ok(())
     }
 
-    // -----------------------------------------------------------------------
-    // Claim
-    // -----------------------------------------------------------------------
+    /// Set the smallest payout `claim_reward` will settle, to avoid dust
+    /// claims that cost more in fees than they pay.  0 disables the check.
+    pub fn set_min_claim(env: Env, admin: Address, amount: i128) -> Result<(), Error> {
+        Self::require_ready_for_mutation(&env)?;
+        Self::require_admin(&env, &admin)?;
 
-    /// Claim all accrued rewards for `user` in a campaign.
+        if amount < 0 {
+            return Err(Error::InvalidAmount);
+        }
+        env.storage().instance().set(&DataKey::MinClaim, &amount);
+
+        MinClaimChanged {
+            min_claim: amount,
+            admin,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Return the minimum claim threshold; 0 when unset.
+    pub fn min_claim(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinClaim)
+            .unwrap_or(0)
+    }
+
+    /// Put a campaign's accruals on a linear vesting schedule.  Admin only.
     ///
-    /// * The user must authenticate.
-    /// * The reentrancy guard (`Claimed` flag) is set **before** any external
-    ///   settlement call.
-    /// * Returns the amount of tokens claimed.
-    pub fn claim_reward(env: Env, user: Address, campaign_id: u32) -> Result<i128, Error> {
-        Self::require_initialized(&env)?;
-        user.require_auth();
+    /// Balances vest from `defined_at` to `vesting_end`; `claim_reward` pays
+    /// only the vested part.  `claim_early` pays the rest minus
+    /// `early_forfeit_bps` of the unvested part.  `vesting_end == 0` turns
+    /// vesting off.
+    pub fn set_campaign_vesting(
+        env: Env,
+        campaign_id: u32,
+        vesting_end: u64,
+        early_forfeit_bps: u32,
+    ) -> Result<(), Error> {
+        let admin = Self::require_ready_for_mutation(&env)?;
+        admin.require_auth();
 
-        // Duplicate-claim guard
-        let claimed_key = DataKey::Claimed(campaign_id, user.clone());
-        if env.storage().persistent().has(&claimed_key) {
-            return Err(Error::AlreadyClaimed);
+        let key = DataKey::Campaign(campaign_id);
+        let mut campaign: CampaignData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::CampaignNotFound)?;
+
+        if early_forfeit_bps > BPS_DENOMINATOR
+            || (vesting_end != 0 && vesting_end <= campaign.defined_at)
+        {
+            return Err(Error::InvalidAmount);
         }
 
-        let accrued_key = DataKey::Accrued(campaign_id, user.clone());
-        let accrued: i128 = env
+        campaign.vesting_end = vesting_end;
+        campaign.early_forfeit_bps = early_forfeit_bps;
+
+        env.storage().persistent().set(&key, &campaign);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_BUMP_THRESHOLD,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+
+        VestingChanged {
+            campaign_id,
+            vesting_end,
+            early_forfeit_bps,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Return the share of the unvested balance `claim_early` forfeits, in bps.
+    pub fn early_forfeit_bps(env: Env, campaign_id: u32) -> Result<u32, Error> {
+        let campaign = Self::campaign_state(env, campaign_id).ok_or(Error::CampaignNotFound)?;
+        if campaign.vesting_end == 0 {
+            return Err(Error::NotVesting);
+        }
+        Ok(campaign.early_forfeit_bps)
+    }
+
+    /// Set a user's accrual multiplier for a campaign.  Admin only.
+    ///
+    /// `bps` is out of 10_000, so 15_000 is a 1.5x tier.  Setting 10_000
+    /// restores the default.
+    pub fn set_user_multiplier(
+        env: Env,
+        campaign_id: u32,
+        user: Address,
+        bps: u32,
+    ) -> Result<(), Error> {
+        let admin = Self::require_ready_for_mutation(&env)?;
+        admin.require_auth();
+
+        if bps == 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if !env
             .storage()
             .persistent()
-            .get(&accrued_key)
-            .unwrap_or(0i128);
+            .has(&DataKey::Campaign(campaign_id))
+        {
+            return Err(Error::CampaignNotFound);
+        }
 
-        if accrued <= 0 {
-            return Err(Error::NothingToClaim);
+        let key = DataKey::Multiplier(campaign_id, user.clone());
+        if bps == BPS_DENOMINATOR {
+            env.storage().persistent().remove(&key);
+        } else {
+            env.storage().persistent().set(&key, &bps);
+            env.storage().persistent().extend_ttl(
+                &key,
+                PERSISTENT_BUMP_THRESHOLD,
+                PERSISTENT_BUMP_LEDGERS,
+            );
+        }
+
+        MultiplierChanged {
+            campaign_id,
+            user,
+            bps,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Return a user's accrual multiplier in bps; 10_000 when unset.
+    pub fn user_multiplier(env: Env, campaign_id: u32, user: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Multiplier(campaign_id, user))
+            .unwrap_or(BPS_DENOMINATOR)
+    }
+
+    /// Add funds to an existing campaign.  Admin only.
+    ///
+    /// `additional` is added to both `budget` and `remaining`; an `Exhausted`
+    /// campaign becomes `Active` again.  `Closed` campaigns cannot be topped up.
+    pub fn top_up_campaign(env: Env, campaign_id: u32, additional: i128) -> Result<(), Error> {
+        let admin = Self::require_ready_for_mutation(&env)?;
+        admin.require_auth();
+
+        if additional <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let key = DataKey::Campaign(campaign_id);
+        let mut campaign: CampaignData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::CampaignNotFound)?;
+
+        if campaign.status == CampaignStatus::Closed {
+            return Err(Error::CampaignNotActive);
+        }
+
+        campaign.budget = campaign
+            .budget
+            .checked_add(additional)
+            .ok_or(Error::Overflow)?;
+        campaign.remaining = campaign
+            .remaining
+            .checked_add(additional)
+            .ok_or(Error::Overflow)?;
+        if campaign.status == CampaignStatus::Exhausted {
+            campaign.status = CampaignStatus::Active;
         }
 
-        // ── Reentrancy guard: set Claimed BEFORE any external call ──────────
-        env.storage().persistent().set(&claimed_key, &true);
+        env.storage().persistent().set(&key, &campaign);
         env.storage().persistent().extend_ttl(
-            &claimed_key,
+            &key,
+            PERSISTENT_BUMP_THRESHOLD,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+
+        CampaignToppedUp {
+            campaign_id,
+            additional,
+            new_budget: campaign.budget,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Deposit `amount` of a `reward_token` campaign's token, transferred
+    /// from `from` into this contract, to fund its payouts.  Admin only;
+    /// `from` must authorise the transfer.  Returns the new reserve.
+    ///
+    /// Accruals fail with `InsufficientReserve` once the campaign's unclaimed
+    /// liability would exceed the reserve; claims draw it down.  The reserve
+    /// may not exceed what the campaign can still pay out (`remaining` plus
+    /// unclaimed accruals).
+    pub fn fund_campaign(
+        env: Env,
+        campaign_id: u32,
+        from: Address,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        let admin = Self::require_ready_for_mutation(&env)?;
+        admin.require_auth();
+        from.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let campaign: CampaignData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Campaign(campaign_id))
+            .ok_or(Error::CampaignNotFound)?;
+        let Some(reward_token) = campaign.reward_token.clone() else {
+            return Err(Error::NotTokenCampaign);
+        };
+        if campaign.status == CampaignStatus::Closed {
+            return Err(Error::CampaignNotActive);
+        }
+
+        let reserve = Self::campaign_reserve(env.clone(), campaign_id)
+            .checked_add(amount)
+            .ok_or(Error::Overflow)?;
+        let payable = campaign
+            .remaining
+            .checked_add(Self::campaign_liability(&campaign))
+            .ok_or(Error::Overflow)?;
+        if reserve > payable {
+            return Err(Error::InvalidAmount);
+        }
+
+        token::Client::new(&env, &reward_token).transfer(
+            &from,
+            env.current_contract_address(),
+            &amount,
+        );
+        Self::set_reserve(&env, campaign_id, reserve);
+
+        CampaignFunded {
+            campaign_id,
+            amount,
+            reserve,
+        }
+        .publish(&env);
+
+        Ok(reserve)
+    }
+
+    /// Close a campaign for good.  Admin only.
+    ///
+    /// No further accruals are accepted, but balances accrued before the close
+    /// stay claimable.  Only the unaccrued `remaining` budget is reclaimable —
+    /// accrued-but-unclaimed rewards remain reserved for their users.
+    /// Returns the reclaimable amount and emits `CampaignClosed` with it;
+    /// `sweep_expired` releases it.
+    pub fn close_campaign(env: Env, campaign_id: u32) -> Result<i128, Error> {
+        let admin = Self::require_ready_for_mutation(&env)?;
+        admin.require_auth();
+
+        let key = DataKey::Campaign(campaign_id);
+        let mut campaign: CampaignData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::CampaignNotFound)?;
+
+        if campaign.status == CampaignStatus::Closed {
+            return Err(Error::CampaignNotActive);
+        }
+
+        let reclaimable = campaign.remaining;
+        campaign.status = CampaignStatus::Closed;
+
+        env.storage().persistent().set(&key, &campaign);
+        env.storage().persistent().extend_ttl(
+            &key,
             PERSISTENT_BUMP_THRESHOLD,
             PERSISTENT_BUMP_LEDGERS,
         );
 
-        // Zero out the accrued balance
-        env.storage().persistent().set(&accrued_key, &0i128);
+        CampaignClosed {
+            campaign_id,
+            reclaimable,
+        }
+        .publish(&env);
+
+        Ok(reclaimable)
+    }
+
+    /// Release an expired or closed campaign's unaccrued budget and mark it
+    /// `Closed`.  Callable by anyone once the campaign has expired or been
+    /// closed, since nothing is paid to the caller.
+    ///
+    /// The `remaining` budget is set to zero.  It was never taken from the
+    /// treasury, so nothing is credited for it.  For a `reward_token`
+    /// campaign the deposited tokens beyond what users are still owed are
+    /// transferred back to the treasury.  Accrued-but-unclaimed rewards stay
+    /// claimable.  A campaign can be swept only once.  Returns the released
+    /// amount.
+    pub fn sweep_expired(env: Env, campaign_id: u32) -> Result<i128, Error> {
+        Self::require_ready_for_mutation(&env)?;
+
+        let key = DataKey::Campaign(campaign_id);
+        let mut campaign: CampaignData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::CampaignNotFound)?;
+
+        if campaign.swept {
+            return Err(Error::AlreadySwept);
+        }
+        if campaign.status != CampaignStatus::Closed && !Self::is_expired(&env, &campaign) {
+            return Err(Error::CampaignNotExpired);
+        }
+
+        let amount = if campaign.reward_token.is_some() {
+            let liability = Self::campaign_liability(&campaign);
+            let reserve = Self::campaign_reserve(env.clone(), campaign_id);
+            Self::set_reserve(&env, campaign_id, liability);
+            (reserve - liability).max(0)
+        } else {
+            campaign.remaining
+        };
+        campaign.remaining = 0;
+        campaign.status = CampaignStatus::Closed;
+        campaign.swept = true;
+
+        env.storage().persistent().set(&key, &campaign);
         env.storage().persistent().extend_ttl(
-            &accrued_key,
+            &key,
             PERSISTENT_BUMP_THRESHOLD,
             PERSISTENT_BUMP_LEDGERS,
         );
 
-        // ── Placeholder for cross-contract token transfer ────────────────────
-        // In production: balance_contract.transfer(user, accrued)
-        // Stored for composability — the balance_contract address is available
-        // via `env.storage().instance().get(&DataKey::BalanceContract)`.
+        let treasury: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TreasuryContract)
+            .ok_or(Error::NotInitialized)?;
+        if amount > 0 && campaign.reward_token.is_some() {
+            Self::pay_out(&env, &campaign, &treasury, amount, symbol_short!("sweep"))?;
+        }
+
+        CampaignSwept {
+            campaign_id,
+            treasury,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(amount)
+    }
+
+    // -----------------------------------------------------------------------
+    // Reward accrual
+    // -----------------------------------------------------------------------
+
+    /// Record a pending reward for `user`.  Admin only.
+    ///
+    /// The call is additive — repeated calls accumulate until the user claims.
+    /// `amount` is scaled by the user's multiplier first; the campaign's
+    /// `remaining` balance is decremented by the scaled amount immediately to
+    /// uphold the invariant `Σ accrued ≤ budget`.
+    pub fn accrue_reward(
+        env: Env,
+        user: Address,
+        campaign_id: u32,
+        amount: i128,
+    ) -> Result<(), Error> {
+        let admin = Self::require_ready_for_mutation(&env)?;
+        admin.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let amount = Self::apply_multiplier(&env, campaign_id, &user, amount)?;
+        Self::debit_campaign(&env, campaign_id, amount)?;
+        Self::credit_accrual(&env, campaign_id, user, amount)
+    }
+
+    /// Record pending rewards for many users in one call.  Admin only.
+    ///
+    /// Each amount is scaled by its user's multiplier.  The scaled batch total
+    /// is checked against the campaign's `remaining` budget up front, so
+    /// either every entry is applied or none is.  A user may appear more than
+    /// once; amounts accumulate.  Emits one `RewardAccrued` per entry.
+    pub fn accrue_rewards_batch(
+        env: Env,
+        campaign_id: u32,
+        entries: Vec<(Address, i128)>,
+    ) -> Result<(), Error> {
+        let admin = Self::require_ready_for_mutation(&env)?;
+        admin.require_auth();
+
+        if entries.is_empty() {
+            return Err(Error::BatchInvalid);
+        }
+
+        let mut scaled = Vec::new(&env);
+        let mut total = 0i128;
+        for (user, amount) in entries.iter() {
+            if amount <= 0 {
+                return Err(Error::InvalidAmount);
+            }
+            let amount = Self::apply_multiplier(&env, campaign_id, &user, amount)?;
+            total = total.checked_add(amount).ok_or(Error::Overflow)?;
+            scaled.push_back((user, amount));
+        }
+
+        Self::debit_campaign(&env, campaign_id, total)?;
+        for (user, amount) in scaled.iter() {
+            Self::credit_accrual(&env, campaign_id, user, amount)?;
+        }
+
+        Ok(())
+    }
+
+    /// Claw back part of a user's unclaimed accrual.  Admin only.
+    ///
+    /// `amount` is returned to the campaign's `remaining` budget; an
+    /// `Exhausted` campaign becomes `Active` again.  A `Closed` campaign
+    /// stays closed.
+    pub fn revoke_accrual(
+        env: Env,
+        user: Address,
+        campaign_id: u32,
+        amount: i128,
+    ) -> Result<(), Error> {
+        let admin = Self::require_ready_for_mutation(&env)?;
+        admin.require_auth();
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Campaign(campaign_id))
+        {
+            return Err(Error::CampaignNotFound);
+        }
+
+        let accrued_key = DataKey::Accrued(campaign_id, user.clone());
+        let current_accrued: i128 = env
+            .storage()
+            .persistent()
+            .get(&accrued_key)
+            .unwrap_or(0i128);
+        if amount <= 0 || amount > current_accrued {
+            return Err(Error::InvalidAmount);
+        }
+        let new_accrued = current_accrued - amount;
+
+        Self::refund_campaign(&env, campaign_id, amount)?;
+
+        if new_accrued == 0 && Self::claimed_total(env.clone(), user.clone(), campaign_id) == 0 {
+            Self::drop_recipient(&env, campaign_id, &user);
+        } else {
+            env.storage().persistent().set(&accrued_key, &new_accrued);
+            env.storage().persistent().extend_ttl(
+                &accrued_key,
+                PERSISTENT_BUMP_THRESHOLD,
+                PERSISTENT_BUMP_LEDGERS,
+            );
+        }
+
+        RewardRevoked {
+            campaign_id,
+            user,
+            amount,
+            new_total: new_accrued,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Move a user's whole unclaimed accrual from one campaign to another.
+    /// Admin only.
+    ///
+    /// Used when consolidating campaigns.  The destination must be able to
+    /// accept the accrual (active, unpaused, unexpired, enough `remaining`);
+    /// the source gets the amount back in `remaining` exactly as with
+    /// `revoke_accrual`.  Refused once the user has claimed from the source.
+    /// Returns the amount moved.
+    pub fn migrate_accrual(
+        env: Env,
+        admin: Address,
+        user: Address,
+        from_campaign: u32,
+        to_campaign: u32,
+    ) -> Result<i128, Error> {
+        Self::require_ready_for_mutation(&env)?;
+        Self::require_admin(&env, &admin)?;
+
+        if from_campaign == to_campaign {
+            return Err(Error::SameCampaign);
+        }
+        let source: CampaignData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Campaign(from_campaign))
+            .ok_or(Error::CampaignNotFound)?;
+        let destination: CampaignData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Campaign(to_campaign))
+            .ok_or(Error::CampaignNotFound)?;
+        if Self::settlement_contract(&env, &source)?
+            != Self::settlement_contract(&env, &destination)?
+        {
+            return Err(Error::TokenMismatch);
+        }
+        if Self::claimed_total(env.clone(), user.clone(), from_campaign) > 0 {
+            return Err(Error::AlreadyClaimed);
+        }
+
+        let amount = Self::accrued_for(env.clone(), user.clone(), from_campaign);
+        if amount <= 0 {
+            return Err(Error::NothingToClaim);
+        }
+
+        Self::debit_campaign(&env, to_campaign, amount)?;
+        Self::refund_campaign(&env, from_campaign, amount)?;
+
+        Self::drop_recipient(&env, from_campaign, &user);
+        Self::add_accrued(&env, to_campaign, &user, amount)?;
+
+        AccrualMigrated {
+            from_campaign,
+            to_campaign,
+            user,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(amount)
+    }
+
+    // -----------------------------------------------------------------------
+    // Claim
+    // -----------------------------------------------------------------------
+
+    /// Let `delegate` claim on `user`'s behalf, replacing any previous
+    /// delegate.  Requires `user`'s auth.  Payouts still go to `user`.
+    pub fn set_claim_delegate(env: Env, user: Address, delegate: Address) -> Result<(), Error> {
+        Self::require_ready_for_mutation(&env)?;
+        user.require_auth();
+
+        let key = DataKey::ClaimDelegate(user.clone());
+        env.storage().persistent().set(&key, &delegate);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_BUMP_THRESHOLD,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+
+        ClaimDelegateChanged {
+            user,
+            delegate: Some(delegate),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Revoke `user`'s claim delegate.  Requires `user`'s auth.
+    pub fn clear_claim_delegate(env: Env, user: Address) -> Result<(), Error> {
+        Self::require_ready_for_mutation(&env)?;
+        user.require_auth();
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ClaimDelegate(user.clone()));
+
+        ClaimDelegateChanged {
+            user,
+            delegate: None,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Return the account allowed to claim on `user`'s behalf, if any.
+    pub fn claim_delegate(env: Env, user: Address) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ClaimDelegate(user))
+    }
+
+    /// Claim all accrued rewards for `user` in a campaign.
+    ///
+    /// * `caller` must be `user` or `user`'s registered claim delegate, and
+    ///   must authenticate.  The payout always goes to `user`.
+    /// * Claims stay open on a paused campaign unless it was paused with
+    ///   `block_claims` set.
+    /// * Claiming deducts the payout from the accrued balance; later accruals
+    ///   in the same campaign can be claimed again.
+    /// * On a vesting campaign only the vested part is paid out.
+    /// * Payouts below `min_claim` fail with `BelowMinimumClaim`.
+    /// * A transient `ClaimLock` guard is held across the external settlement
+    ///   call, so a reentrant claim fails with `AlreadyClaimed`.
+    /// * The payout is credited through the balance contract, or transferred
+    ///   from the reserve of a `reward_token` campaign; if that call fails
+    ///   the whole claim reverts.
+    /// * Returns the amount of tokens claimed.
+    pub fn claim_reward(
+        env: Env,
+        caller: Address,
+        user: Address,
+        campaign_id: u32,
+    ) -> Result<i128, Error> {
+        Self::require_ready_for_mutation(&env)?;
+        if caller != user && Self::claim_delegate(env.clone(), user.clone()) != Some(caller.clone())
+        {
+            return Err(Error::NotAuthorized);
+        }
+        caller.require_auth();
+
+        let campaign =
+            Self::campaign_state(env.clone(), campaign_id).ok_or(Error::CampaignNotFound)?;
+        let payout = Self::claimable(&env, &campaign, campaign_id, &user)?;
+
+        let campaign = Self::settle_claim(&env, campaign_id, &user, payout, payout)?;
+
+        RewardClaimed {
+            campaign_id,
+            user,
+            amount: payout,
+            rules_hash: campaign.rules_hash,
+        }
+        .publish(&env);
+
+        Ok(payout)
+    }
+
+    /// Exit a vesting campaign early.  `user` must authenticate.
+    ///
+    /// Pays the vested part of the accrued balance plus the unvested part
+    /// minus `early_forfeit_bps` of it; the forfeited amount goes back to the
+    /// campaign's `remaining` budget.  The accrued balance ends at zero.
+    /// Returns the amount paid out.
+    pub fn claim_early(env: Env, user: Address, campaign_id: u32) -> Result<i128, Error> {
+        Self::require_ready_for_mutation(&env)?;
+        user.require_auth();
+
+        let campaign =
+            Self::campaign_state(env.clone(), campaign_id).ok_or(Error::CampaignNotFound)?;
+        if campaign.vesting_end == 0 {
+            return Err(Error::NotVesting);
+        }
+        if campaign.paused && campaign.block_claims_on_pause {
+            return Err(Error::CampaignPaused);
+        }
+        if env
+            .storage()
+            .temporary()
+            .has(&DataKey::ClaimLock(campaign_id, user.clone()))
+        {
+            return Err(Error::AlreadyClaimed);
+        }
+
+        let accrued = Self::accrued_for(env.clone(), user.clone(), campaign_id);
+        if accrued <= 0 {
+            return Err(Error::NothingToClaim);
+        }
+        let vested = Self::vested_amount(&env, &campaign, campaign_id, &user, accrued)?;
+        let forfeited = (accrued - vested)
+            .checked_mul(campaign.early_forfeit_bps as i128)
+            .ok_or(Error::Overflow)?
+            / BPS_DENOMINATOR as i128;
+        let payout = accrued - forfeited;
+
+        if forfeited > 0 {
+            Self::refund_campaign(&env, campaign_id, forfeited)?;
+        }
+        if payout > 0 {
+            Self::settle_claim(&env, campaign_id, &user, payout, accrued)?;
+        } else {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Accrued(campaign_id, user.clone()), &0i128);
+        }
+
+        EarlyClaimed {
+            campaign_id,
+            user,
+            amount: payout,
+            forfeited,
+        }
+        .publish(&env);
+
+        Ok(payout)
+    }
+
+    // -----------------------------------------------------------------------
+    // Queries
+    // -----------------------------------------------------------------------
+
+    /// Return `(admin, treasury_contract, balance_contract)` as stored by
+    /// `init`, so deployments can verify their wiring.
+    pub fn config(env: Env) -> Result<(Address, Address, Address), Error> {
+        let admin = Self::require_initialized(&env)?;
+        let treasury_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::TreasuryContract)
+            .ok_or(Error::NotInitialized)?;
+        let balance_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::BalanceContract)
+            .ok_or(Error::NotInitialized)?;
+
+        Ok((admin, treasury_contract, balance_contract))
+    }
+
+    /// Return the current state of a campaign, or `None` if it does not exist.
+    pub fn campaign_state(env: Env, campaign_id: u32) -> Option<CampaignData> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Campaign(campaign_id))
+    }
+
+    /// Return the contract a campaign's claims settle through: its
+    /// `reward_token` if set, otherwise the global `balance_contract`.
+    pub fn campaign_token(env: Env, campaign_id: u32) -> Result<Address, Error> {
+        let campaign: CampaignData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Campaign(campaign_id))
+            .ok_or(Error::CampaignNotFound)?;
+        Self::settlement_contract(&env, &campaign)
+    }
+
+    /// Return `(budget, total_accrued, total_claimed)` for a campaign, or
+    /// `None` if it does not exist.  `claimed ≤ accrued ≤ budget` always holds.
+    pub fn campaign_accounting(env: Env, campaign_id: u32) -> Option<(i128, i128, i128)> {
+        let campaign: CampaignData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Campaign(campaign_id))?;
+
+        Some((
+            campaign.budget,
+            campaign.total_accrued,
+            campaign.total_claimed,
+        ))
+    }
+
+    /// Return budget, remaining, accrued, claimed and status of a campaign in
+    /// a single read, or `None` if it does not exist.  All fields come from
+    /// the same stored record, so the snapshot is always self-consistent.
+    pub fn campaign_snapshot(env: Env, campaign_id: u32) -> Option<CampaignSnapshot> {
+        let campaign: CampaignData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Campaign(campaign_id))?;
+
+        Some(CampaignSnapshot {
+            budget: campaign.budget,
+            remaining: campaign.remaining,
+            total_accrued: campaign.total_accrued,
+            total_claimed: campaign.total_claimed,
+            status: campaign.status,
+        })
+    }
+
+    /// Return `(defined_at, ends_at, seconds_remaining)` for a campaign, or
+    /// `None` if it does not exist.
+    ///
+    /// `ends_at` is the campaign's `expires_at`.  `seconds_remaining` counts
+    /// down to it, clamped at zero; a campaign without expiry reports
+    /// `ends_at = 0` and an unbounded `seconds_remaining` of `u64::MAX`.
+    pub fn campaign_timing(env: Env, campaign_id: u32) -> Option<(u64, u64, u64)> {
+        let campaign: CampaignData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Campaign(campaign_id))?;
+
+        let seconds_remaining = if campaign.expires_at == 0 {
+            u64::MAX
+        } else {
+            campaign.expires_at.saturating_sub(env.ledger().timestamp())
+        };
+
+        Some((campaign.defined_at, campaign.expires_at, seconds_remaining))
+    }
+
+    /// Return the unclaimed accrued balance for `user` in a campaign.
+    pub fn accrued_for(env: Env, user: Address, campaign_id: u32) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Accrued(campaign_id, user))
+            .unwrap_or(0i128)
+    }
+
+    /// Return whether `user` has claimed from `campaign_id` at least once.
+    pub fn has_claimed(env: Env, user: Address, campaign_id: u32) -> bool {
+        Self::claimed_total(env, user, campaign_id) > 0
+    }
+
+    /// Return the cumulative amount `user` has claimed from a campaign.
+    pub fn claimed_total(env: Env, user: Address, campaign_id: u32) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ClaimedTotal(campaign_id, user))
+            .unwrap_or(0i128)
+    }
+
+    /// Return exactly what `claim_reward` would pay `user` right now, without
+    /// side effects.  Returns 0 whenever the claim would fail (contract
+    /// paused or killed, unknown campaign, claims blocked, claim in progress,
+    /// nothing accrued).
+    pub fn preview_claim(env: Env, user: Address, campaign_id: u32) -> i128 {
+        if Self::require_ready_for_mutation(&env).is_err() {
+            return 0;
+        }
+        let Some(campaign) = Self::campaign_state(env.clone(), campaign_id) else {
+            return 0;
+        };
+        Self::claimable(&env, &campaign, campaign_id, &user).unwrap_or(0)
+    }
+
+    /// Return how many distinct users hold an accrual in a campaign.
+    ///
+    /// Repeated accruals to the same user count once.  Claiming does not
+    /// lower the count; a revocation does only when it takes a never-claimed
+    /// user back to zero.
+    pub fn recipient_count(env: Env, campaign_id: u32) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RecipientCount(campaign_id))
+            .unwrap_or(0)
+    }
+
+    /// Return the total accrued but not yet claimed across every campaign —
+    /// exactly what must be funded to cover every possible claim.
+    pub fn outstanding_liability(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalOutstanding)
+            .unwrap_or(0)
+    }
+
+    /// Same as `outstanding_liability`, restricted to campaigns settling
+    /// through `token` (see `campaign_token`), so amounts in different tokens
+    /// are not added together.
+    pub fn outstanding_liability_for(env: Env, token: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Outstanding(token))
+            .unwrap_or(0)
+    }
+
+    /// Return the tokens deposited for a `reward_token` campaign and not yet
+    /// paid out; 0 for other or unknown campaigns.
+    pub fn campaign_reserve(env: Env, campaign_id: u32) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Reserve(campaign_id))
+            .unwrap_or(0)
+    }
+
+    /// Return `(accrued, claimed)` for `user` in a campaign, read in a single
+    /// call so support tooling sees a consistent snapshot.
+    pub fn user_state(env: Env, user: Address, campaign_id: u32) -> (i128, bool) {
+        (
+            Self::accrued_for(env.clone(), user.clone(), campaign_id),
+            Self::has_claimed(env, user, campaign_id),
+        )
+    }
+
+    // -----------------------------------------------------------------------
+    // Internal helpers
+    // -----------------------------------------------------------------------
+
+    fn require_initialized(env: &Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)
+    }
+
+    fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
+        let admin = Self::require_initialized(env)?;
+        caller.require_auth();
+        if admin != *caller {
+            return Err(Error::NotAuthorized);
+        }
+        Ok(())
+    }
+
+    fn require_not_killed(env: &Env) -> Result<(), Error> {
+        if Self::is_killed(env.clone()) {
+            return Err(Error::ContractKilled);
+        }
+        Ok(())
+    }
+
+    /// Gate for every mutating method; returns the admin address.
+    fn require_ready_for_mutation(env: &Env) -> Result<Address, Error> {
+        let admin = Self::require_initialized(env)?;
+        Self::require_not_killed(env)?;
+        if Self::is_paused(env.clone()) {
+            return Err(Error::ContractPaused);
+        }
+        Ok(admin)
+    }
+
+    fn set_campaign_pause(
+        env: &Env,
+        campaign_id: u32,
+        paused: bool,
+        block_claims_on_pause: bool,
+    ) -> Result<(), Error> {
+        let admin = Self::require_ready_for_mutation(env)?;
+        admin.require_auth();
+
+        let key = DataKey::Campaign(campaign_id);
+        let mut campaign: CampaignData = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::CampaignNotFound)?;
+
+        campaign.paused = paused;
+        campaign.block_claims_on_pause = block_claims_on_pause;
+
+        env.storage().persistent().set(&key, &campaign);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_BUMP_THRESHOLD,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+
+        CampaignPauseChanged {
+            campaign_id,
+            paused,
+            block_claims_on_pause,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    fn is_expired(env: &Env, campaign: &CampaignData) -> bool {
+        campaign.expires_at != 0 && env.ledger().timestamp() > campaign.expires_at
+    }
+
+    /// Take `amount` out of an active campaign's `remaining` budget, marking
+    /// it `Exhausted` (and emitting `CampaignExhausted`) when the budget
+    /// reaches zero.
+    fn debit_campaign(env: &Env, campaign_id: u32, amount: i128) -> Result<(), Error> {
+        let campaign_key = DataKey::Campaign(campaign_id);
+        let mut campaign: CampaignData = env
+            .storage()
+            .persistent()
+            .get(&campaign_key)
+            .ok_or(Error::CampaignNotFound)?;
+
+        if campaign.status != CampaignStatus::Active {
+            return Err(Error::CampaignNotActive);
+        }
+        if campaign.paused {
+            return Err(Error::CampaignPaused);
+        }
+        if Self::is_expired(env, &campaign) {
+            return Err(Error::CampaignExpired);
+        }
+
+        let new_remaining = campaign
+            .remaining
+            .checked_sub(amount)
+            .ok_or(Error::Overflow)?;
+        if new_remaining < 0 {
+            return Err(Error::CampaignExhausted);
+        }
+        if campaign.reward_token.is_some() {
+            let liability = Self::campaign_liability(&campaign)
+                .checked_add(amount)
+                .ok_or(Error::Overflow)?;
+            if liability > Self::campaign_reserve(env.clone(), campaign_id) {
+                return Err(Error::InsufficientReserve);
+            }
+        }
+
+        campaign.remaining = new_remaining;
+        campaign.total_accrued = campaign
+            .total_accrued
+            .checked_add(amount)
+            .ok_or(Error::Overflow)?;
+        if campaign.remaining == 0 {
+            campaign.status = CampaignStatus::Exhausted;
+            CampaignExhausted {
+                campaign_id,
+                final_total: campaign.total_accrued,
+            }
+            .publish(env);
+        }
+
+        env.storage().persistent().set(&campaign_key, &campaign);
+        env.storage().persistent().extend_ttl(
+            &campaign_key,
+            PERSISTENT_BUMP_THRESHOLD,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+
+        let token = Self::settlement_contract(env, &campaign)?;
+        Self::adjust_outstanding(env, &token, amount)
+    }
+
+    /// Checks shared by `claim_reward` and `preview_claim`; returns the
+    /// amount a claim would pay out.
+    fn claimable(
+        env: &Env,
+        campaign: &CampaignData,
+        campaign_id: u32,
+        user: &Address,
+    ) -> Result<i128, Error> {
+        if campaign.paused && campaign.block_claims_on_pause {
+            return Err(Error::CampaignPaused);
+        }
+
+        // Reentrancy guard
+        if env
+            .storage()
+            .temporary()
+            .has(&DataKey::ClaimLock(campaign_id, user.clone()))
+        {
+            return Err(Error::AlreadyClaimed);
+        }
+
+        let accrued = Self::accrued_for(env.clone(), user.clone(), campaign_id);
+        let payout = if accrued > 0 {
+            Self::vested_amount(env, campaign, campaign_id, user, accrued)?
+        } else {
+            0
+        };
+        if payout <= 0 {
+            return Err(Error::NothingToClaim);
+        }
+        if payout < Self::min_claim(env.clone()) {
+            return Err(Error::BelowMinimumClaim);
+        }
+
+        Ok(payout)
+    }
+
+    /// Part of `accrued` that has vested.  Everything the user ever accrued
+    /// (claimed + unclaimed) vests linearly from `defined_at` to
+    /// `vesting_end`; earlier claims count against the vested share.
+    fn vested_amount(
+        env: &Env,
+        campaign: &CampaignData,
+        campaign_id: u32,
+        user: &Address,
+        accrued: i128,
+    ) -> Result<i128, Error> {
+        let now = env.ledger().timestamp();
+        if campaign.vesting_end == 0 || now >= campaign.vesting_end {
+            return Ok(accrued);
+        }
+
+        let claimed = Self::claimed_total(env.clone(), user.clone(), campaign_id);
+        let lifetime = claimed.checked_add(accrued).ok_or(Error::Overflow)?;
+        let elapsed = now.saturating_sub(campaign.defined_at) as i128;
+        let span = (campaign.vesting_end - campaign.defined_at) as i128;
+        let vested = lifetime.checked_mul(elapsed).ok_or(Error::Overflow)? / span;
+
+        Ok((vested - claimed).clamp(0, accrued))
+    }
+
+    /// Scale `amount` by the user's multiplier, rounding down.  A result that
+    /// rounds to zero is rejected with `InvalidAmount`.
+    fn apply_multiplier(
+        env: &Env,
+        campaign_id: u32,
+        user: &Address,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        let bps = Self::user_multiplier(env.clone(), campaign_id, user.clone());
+        let scaled =
+            amount.checked_mul(bps as i128).ok_or(Error::Overflow)? / BPS_DENOMINATOR as i128;
+        if scaled <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        Ok(scaled)
+    }
+
+    /// Pay `payout` to `user` and take `debit` off their accrued balance.
+    ///
+    /// The `ClaimLock` guard is taken before the external settlement call and
+    /// released after it returns.  Returns the updated campaign.
+    fn settle_claim(
+        env: &Env,
+        campaign_id: u32,
+        user: &Address,
+        payout: i128,
+        debit: i128,
+    ) -> Result<CampaignData, Error> {
+        let campaign_key = DataKey::Campaign(campaign_id);
+        let mut campaign: CampaignData = env
+            .storage()
+            .persistent()
+            .get(&campaign_key)
+            .ok_or(Error::CampaignNotFound)?;
+
+        let lock_key = DataKey::ClaimLock(campaign_id, user.clone());
+        let accrued_key = DataKey::Accrued(campaign_id, user.clone());
+        let claimed_key = DataKey::ClaimedTotal(campaign_id, user.clone());
+        let accrued = Self::accrued_for(env.clone(), user.clone(), campaign_id);
+        let new_claimed_total = Self::claimed_total(env.clone(), user.clone(), campaign_id)
+            .checked_add(payout)
+            .ok_or(Error::Overflow)?;
+
+        // ── Reentrancy guard: take the lock BEFORE any external call ────────
+        env.storage().temporary().set(&lock_key, &true);
+
+        campaign.total_claimed = campaign
+            .total_claimed
+            .checked_add(payout)
+            .ok_or(Error::Overflow)?;
+        env.storage().persistent().set(&campaign_key, &campaign);
+        env.storage().persistent().extend_ttl(
+            &campaign_key,
+            PERSISTENT_BUMP_THRESHOLD,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+
+        env.storage()
+            .persistent()
+            .set(&claimed_key, &new_claimed_total);
+        env.storage().persistent().extend_ttl(
+            &claimed_key,
+            PERSISTENT_BUMP_THRESHOLD,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+
+        env.storage()
+            .persistent()
+            .set(&accrued_key, &(accrued - debit));
+        env.storage().persistent().extend_ttl(
+            &accrued_key,
+            PERSISTENT_BUMP_THRESHOLD,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+
+        let balance_contract = Self::settlement_contract(env, &campaign)?;
+        Self::adjust_outstanding(env, &balance_contract, -payout)?;
+        if campaign.reward_token.is_some() {
+            // Never pay out more tokens than were deposited for the campaign.
+            let reserve = Self::campaign_reserve(env.clone(), campaign_id)
+                .checked_sub(payout)
+                .ok_or(Error::Overflow)?;
+            if reserve < 0 {
+                return Err(Error::InsufficientReserve);
+            }
+            Self::set_reserve(env, campaign_id, reserve);
+        }
+
+        // ── Settlement ───────────────────────────────────────────────────────
+        Self::pay_out(env, &campaign, user, payout, symbol_short!("reward"))?;
+
+        env.storage().temporary().remove(&lock_key);
+
+        Ok(campaign)
+    }
+
+    /// Send `amount` of `campaign`'s rewards to `to`: a transfer out of this
+    /// contract for a `reward_token` campaign, otherwise a credit through the
+    /// global balance contract.
+    fn pay_out(
+        env: &Env,
+        campaign: &CampaignData,
+        to: &Address,
+        amount: i128,
+        reason: Symbol,
+    ) -> Result<(), Error> {
+        let settlement = Self::settlement_contract(env, campaign)?;
+        if campaign.reward_token.is_some() {
+            token::Client::new(env, &settlement).transfer(
+                &env.current_contract_address(),
+                to,
+                &amount,
+            );
+        } else {
+            BalanceClient::new(env, &settlement).credit(
+                &env.current_contract_address(),
+                to,
+                &amount,
+                &reason,
+            );
+        }
+        Ok(())
+    }
+
+    /// Contract that pays out `campaign`'s rewards.
+    fn settlement_contract(env: &Env, campaign: &CampaignData) -> Result<Address, Error> {
+        match &campaign.reward_token {
+            Some(token) => Ok(token.clone()),
+            None => env
+                .storage()
+                .instance()
+                .get(&DataKey::BalanceContract)
+                .ok_or(Error::NotInitialized),
+        }
+    }
+
+    /// Return `amount` to a campaign's `remaining` budget, reviving it if it
+    /// was `Exhausted`.  A `Closed` campaign stays closed.
+    fn refund_campaign(env: &Env, campaign_id: u32, amount: i128) -> Result<(), Error> {
+        let campaign_key = DataKey::Campaign(campaign_id);
+        let mut campaign: CampaignData = env
+            .storage()
+            .persistent()
+            .get(&campaign_key)
+            .ok_or(Error::CampaignNotFound)?;
+
+        campaign.remaining = campaign
+            .remaining
+            .checked_add(amount)
+            .ok_or(Error::Overflow)?;
+        campaign.total_accrued = campaign
+            .total_accrued
+            .checked_sub(amount)
+            .ok_or(Error::Overflow)?;
+        if campaign.status == CampaignStatus::Exhausted {
+            campaign.status = CampaignStatus::Active;
+        }
+
+        env.storage().persistent().set(&campaign_key, &campaign);
+        env.storage().persistent().extend_ttl(
+            &campaign_key,
+            PERSISTENT_BUMP_THRESHOLD,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+
+        let token = Self::settlement_contract(env, &campaign)?;
+        Self::adjust_outstanding(env, &token, -amount)
+    }
+
+    /// Move the contract-wide outstanding liability and the one in `token`
+    /// by `delta`.
+    fn adjust_outstanding(env: &Env, token: &Address, delta: i128) -> Result<(), Error> {
+        let total = Self::outstanding_liability(env.clone())
+            .checked_add(delta)
+            .ok_or(Error::Overflow)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalOutstanding, &total);
+
+        let token_total = Self::outstanding_liability_for(env.clone(), token.clone())
+            .checked_add(delta)
+            .ok_or(Error::Overflow)?;
+        let key = DataKey::Outstanding(token.clone());
+        env.storage().persistent().set(&key, &token_total);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_BUMP_THRESHOLD,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+        Ok(())
+    }
+
+    /// Accrued-but-unclaimed total of a single campaign.
+    fn campaign_liability(campaign: &CampaignData) -> i128 {
+        campaign.total_accrued - campaign.total_claimed
+    }
+
+    fn set_reserve(env: &Env, campaign_id: u32, reserve: i128) {
+        let key = DataKey::Reserve(campaign_id);
+        env.storage().persistent().set(&key, &reserve);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_BUMP_THRESHOLD,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+    }
+
+    /// Add `amount` to a user's pending balance and emit `RewardAccrued`.
+    fn credit_accrual(
+        env: &Env,
+        campaign_id: u32,
+        user: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        let new_accrued = Self::add_accrued(env, campaign_id, &user, amount)?;
+
+        RewardAccrued {
+            campaign_id,
+            user,
+            amount,
+            new_total: new_accrued,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Add `amount` to a user's pending balance and return the new total.
+    /// Enforces the campaign's `per_user_cap`.
+    fn add_accrued(
+        env: &Env,
+        campaign_id: u32,
+        user: &Address,
+        amount: i128,
+    ) -> Result<i128, Error> {
+        let accrued_key = DataKey::Accrued(campaign_id, user.clone());
+        let current_accrued: i128 = env
+            .storage()
+            .persistent()
+            .get(&accrued_key)
+            .unwrap_or(0i128);
+        let new_accrued = current_accrued.checked_add(amount).ok_or(Error::Overflow)?;
+
+        let campaign: CampaignData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Campaign(campaign_id))
+            .ok_or(Error::CampaignNotFound)?;
+        if campaign.per_user_cap > 0 {
+            let lifetime = Self::claimed_total(env.clone(), user.clone(), campaign_id)
+                .checked_add(new_accrued)
+                .ok_or(Error::Overflow)?;
+            if lifetime > campaign.per_user_cap {
+                return Err(Error::UserCapExceeded);
+            }
+        }
+
+        if !env.storage().persistent().has(&accrued_key) {
+            Self::set_recipient_count(
+                env,
+                campaign_id,
+                Self::recipient_count(env.clone(), campaign_id) + 1,
+            );
+        }
+
+        env.storage().persistent().set(&accrued_key, &new_accrued);
+        env.storage().persistent().extend_ttl(
+            &accrued_key,
+            PERSISTENT_BUMP_THRESHOLD,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+
+        Ok(new_accrued)
+    }
+
+    /// Remove a user's `Accrued` entry so they no longer count as a
+    /// recipient of the campaign.
+    fn drop_recipient(env: &Env, campaign_id: u32, user: &Address) {
+        let accrued_key = DataKey::Accrued(campaign_id, user.clone());
+        if env.storage().persistent().has(&accrued_key) {
+            env.storage().persistent().remove(&accrued_key);
+            Self::set_recipient_count(
+                env,
+                campaign_id,
+                Self::recipient_count(env.clone(), campaign_id).saturating_sub(1),
+            );
+        }
+    }
+
+    fn set_recipient_count(env: &Env, campaign_id: u32, count: u32) {
+        let key = DataKey::RecipientCount(campaign_id);
+        env.storage().persistent().set(&key, &count);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_BUMP_THRESHOLD,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+    }
+
+    /// Validate and store an explicitly numbered campaign.  Admin only.
+    fn define_campaign(
+        env: &Env,
+        campaign_id: u32,
+        rules_hash: BytesN<32>,
+        budget: i128,
+        expires_at: u64,
+        category: Symbol,
+        reward_token: Option<Address>,
+    ) -> Result<(), Error> {
+        let admin = Self::require_ready_for_mutation(env)?;
+        admin.require_auth();
+
+        if budget <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Campaign(campaign_id))
+        {
+            return Err(Error::CampaignAlreadyExists);
+        }
+
+        Self::store_new_campaign(
+            env,
+            campaign_id,
+            rules_hash,
+            budget,
+            expires_at,
+            category,
+            reward_token,
+        );
+
+        Ok(())
+    }
+
+    /// Persist a freshly validated campaign and emit `CampaignDefined`.
+    fn store_new_campaign(
+        env: &Env,
+        campaign_id: u32,
+        rules_hash: BytesN<32>,
+        budget: i128,
+        expires_at: u64,
+        category: Symbol,
+        reward_token: Option<Address>,
+    ) {
+        let key = DataKey::Campaign(campaign_id);
+        let campaign = CampaignData {
+            rules_hash,
+            budget,
+            remaining: budget,
+            status: CampaignStatus::Active,
+            paused: false,
+            block_claims_on_pause: false,
+            defined_at: env.ledger().timestamp(),
+            expires_at,
+            total_accrued: 0,
+            total_claimed: 0,
+            per_user_cap: 0,
+            vesting_end: 0,
+            early_forfeit_bps: 0,
+            category: category.clone(),
+            reward_token,
+            swept: false,
+        };
+
+        env.storage().persistent().set(&key, &campaign);
+        env.storage().persistent().extend_ttl(
+            &key,
+            PERSISTENT_BUMP_THRESHOLD,
+            PERSISTENT_BUMP_LEDGERS,
+        );
+
+        CampaignDefined {
+            campaign_id,
+            category,
+            budget,
+        }
+        .publish(env);
+    }
+}
+
+// ===========================================================================
+// Tests
+// ===========================================================================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{
+        contract, contractimpl,
+        testutils::{Address as _, Events as _, Ledger as _},
+        vec, Address, BytesN, Env, Event,
+    };
+
+    // ── Mock balance contract ───────────────────────────────────────────────
+
+    #[contract]
+    pub struct MockBalance;
+
+    #[contracttype]
+    pub enum BalanceKey {
+        Balance(Address),
+        Failing,
+    }
+
+    #[contractimpl]
+    impl MockBalance {
+        pub fn set_failing(env: Env, failing: bool) {
+            env.storage().instance().set(&BalanceKey::Failing, &failing);
+        }
+
+        pub fn credit(env: Env, _game: Address, user: Address, amount: i128, _reason: Symbol) {
+            if env
+                .storage()
+                .instance()
+                .get(&BalanceKey::Failing)
+                .unwrap_or(false)
+            {
+                panic!("credit failed");
+            }
+            let bal = Self::balance_of(env.clone(), user.clone());
+            env.storage()
+                .persistent()
+                .set(&BalanceKey::Balance(user), &(bal + amount));
+        }
+
+        pub fn balance_of(env: Env, user: Address) -> i128 {
+            env.storage()
+                .persistent()
+                .get(&BalanceKey::Balance(user))
+                .unwrap_or(0)
+        }
+    }
+
+    // ── Helpers ─────────────────────────────────────────────────────────────
+
+    fn rules_hash(env: &Env) -> BytesN<32> {
+        BytesN::from_array(env, &[0u8; 32])
+    }
+
+    /// Register a SEP-41 token for token campaigns and mint `amount` of it
+    /// to a fresh funder.  Returns `(token, funder)`.
+    fn promo_token(env: &Env, amount: i128) -> (Address, Address) {
+        let issuer = Address::generate(env);
+        let token = env.register_stellar_asset_contract_v2(issuer).address();
+        let funder = Address::generate(env);
+        token::StellarAssetClient::new(env, &token).mint(&funder, &amount);
+        (token, funder)
+    }
+
+    struct Setup {
+        env: Env,
+        client: RewardDistributionClient<'static>,
+        admin: Address,
+        treasury: Address,
+        balance: Address,
+    }
+
+    fn setup() -> Setup {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(RewardDistribution, ());
+        let client = RewardDistributionClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let balance = env.register(MockBalance, ());
+
+        client.init(&admin, &treasury, &balance);
+
+        // SAFETY: client borrows env by reference; env is moved into Setup and
+        // lives as long as all accesses through client.
+        let client: RewardDistributionClient<'static> = unsafe { core::mem::transmute(client) };
+
+        Setup {
+            env,
+            client,
+            admin,
+            treasury,
+            balance,
+        }
+    }
+
+    // ── init ────────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_init_succeeds() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(RewardDistribution, ());
+        let client = RewardDistributionClient::new(&env, &contract_id);
+
+        let admin = Address::generate(&env);
+        let treasury = Address::generate(&env);
+        let balance = Address::generate(&env);
+
+        client.init(&admin, &treasury, &balance);
+    }
+
+    #[test]
+    fn test_init_twice_fails() {
+        let s = setup();
+        let result = s.client.try_init(&s.admin, &s.treasury, &s.balance);
+        assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
+    }
+
+    #[test]
+    fn test_config_returns_init_wiring() {
+        let s = setup();
+        assert_eq!(
+            s.client.config(),
+            (s.admin.clone(), s.treasury.clone(), s.balance.clone())
+        );
+
+        let contract_id = s.env.register(RewardDistribution, ());
+        let uninit = RewardDistributionClient::new(&s.env, &contract_id);
+        assert_eq!(uninit.try_config(), Err(Ok(Error::NotInitialized)));
+    }
+
+    // ── define_reward_campaign ───────────────────────────────────────────────
+
+    #[test]
+    fn test_define_campaign_succeeds() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &1_000i128, &0u64);
+
+        let state = s.client.campaign_state(&1u32).unwrap();
+        assert_eq!(state.budget, 1_000);
+        assert_eq!(state.remaining, 1_000);
+        assert_eq!(state.status, CampaignStatus::Active);
+    }
+
+    #[test]
+    fn test_define_campaign_duplicate_fails() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
+        let result = s
+            .client
+            .try_define_reward_campaign(&1u32, &hash, &500i128, &0u64);
+        assert_eq!(result, Err(Ok(Error::CampaignAlreadyExists)));
+    }
+
+    #[test]
+    fn test_define_campaign_zero_budget_fails() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        let result = s
+            .client
+            .try_define_reward_campaign(&1u32, &hash, &0i128, &0u64);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+    }
+
+    #[test]
+    fn test_define_campaign_negative_budget_fails() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        let result = s
+            .client
+            .try_define_reward_campaign(&1u32, &hash, &(-1i128), &0u64);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+    }
+
+    #[test]
+    fn test_category_is_stored_and_emitted_as_topic() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        let airdrop = symbol_short!("airdrop");
+        s.client
+            .define_categorized_campaign(&1u32, &hash, &500i128, &0u64, &airdrop);
+        assert_eq!(
+            s.env.events().all().filter_by_contract(&s.client.address),
+            [CampaignDefined {
+                campaign_id: 1,
+                category: airdrop.clone(),
+                budget: 500
+            }
+            .to_xdr(&s.env, &s.client.address)]
+        );
+        assert_eq!(s.client.campaign_state(&1u32).unwrap().category, airdrop);
+
+        // Callers that don't pass a category get the default.
+        s.client
+            .define_reward_campaign(&2u32, &hash, &500i128, &0u64);
+        assert_eq!(
+            s.client.campaign_state(&2u32).unwrap().category,
+            DEFAULT_CATEGORY
+        );
+    }
+
+    // ── define_reward_campaign_auto ──────────────────────────────────────────
+
+    #[test]
+    fn test_auto_ids_increase_and_skip_explicit_ids() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        assert_eq!(s.client.next_campaign_id(), 1);
+
+        assert_eq!(
+            s.client.define_reward_campaign_auto(&hash, &100i128, &0u64),
+            1
+        );
+        // An explicit definition takes id 2; the counter steps over it.
+        s.client
+            .define_reward_campaign(&2u32, &hash, &100i128, &0u64);
+        assert_eq!(s.client.next_campaign_id(), 3);
+        assert_eq!(
+            s.client.define_reward_campaign_auto(&hash, &250i128, &0u64),
+            3
+        );
+
+        assert_eq!(s.client.campaign_state(&3u32).unwrap().budget, 250);
+        assert_eq!(s.client.next_campaign_id(), 4);
+    }
+
+    #[test]
+    fn test_auto_id_not_consumed_on_failure() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        let result = s
+            .client
+            .try_define_reward_campaign_auto(&hash, &0i128, &0u64);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+        assert_eq!(s.client.next_campaign_id(), 1);
+    }
+
+    // ── define_campaigns_batch ───────────────────────────────────────────────
+
+    #[test]
+    fn test_define_campaigns_batch_succeeds() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        let entries = vec![
+            &s.env,
+            (1u32, hash.clone(), 100i128),
+            (2u32, hash.clone(), 200i128),
+            (3u32, hash.clone(), 300i128),
+        ];
+        s.client.define_campaigns_batch(&entries);
+
+        assert_eq!(s.client.campaign_state(&1u32).unwrap().budget, 100);
+        assert_eq!(s.client.campaign_state(&2u32).unwrap().budget, 200);
+        assert_eq!(s.client.campaign_state(&3u32).unwrap().remaining, 300);
+    }
+
+    #[test]
+    fn test_define_campaigns_batch_bad_entry_aborts_all() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+
+        let entries = vec![
+            &s.env,
+            (1u32, hash.clone(), 100i128),
+            (2u32, hash.clone(), 0i128),
+        ];
+        let result = s.client.try_define_campaigns_batch(&entries);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+        assert!(s.client.campaign_state(&1u32).is_none());
+
+        let entries = vec![
+            &s.env,
+            (1u32, hash.clone(), 100i128),
+            (1u32, hash.clone(), 50i128),
+        ];
+        let result = s.client.try_define_campaigns_batch(&entries);
+        assert_eq!(result, Err(Ok(Error::CampaignAlreadyExists)));
+        assert!(s.client.campaign_state(&1u32).is_none());
+
+        s.client
+            .define_reward_campaign(&9u32, &hash, &10i128, &0u64);
+        let entries = vec![
+            &s.env,
+            (1u32, hash.clone(), 100i128),
+            (9u32, hash.clone(), 50i128),
+        ];
+        let result = s.client.try_define_campaigns_batch(&entries);
+        assert_eq!(result, Err(Ok(Error::CampaignAlreadyExists)));
+        assert!(s.client.campaign_state(&1u32).is_none());
+    }
+
+    #[test]
+    fn test_define_campaigns_batch_empty_fails() {
+        let s = setup();
+        let result = s.client.try_define_campaigns_batch(&Vec::new(&s.env));
+        assert_eq!(result, Err(Ok(Error::BatchInvalid)));
+    }
+
+    // ── accrue_reward ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_accrue_succeeds_and_accumulates() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &1_000i128, &0u64);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &300i128);
+        s.client.accrue_reward(&user, &1u32, &200i128);
+
+        assert_eq!(s.client.accrued_for(&user, &1u32), 500i128);
+
+        let state = s.client.campaign_state(&1u32).unwrap();
+        assert_eq!(state.remaining, 500i128);
+    }
+
+    #[test]
+    fn test_accrue_exhausts_campaign() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&2u32, &hash, &100i128, &0u64);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &2u32, &100i128);
+
+        let state = s.client.campaign_state(&2u32).unwrap();
+        assert_eq!(state.status, CampaignStatus::Exhausted);
+        assert_eq!(state.remaining, 0);
+    }
+
+    #[test]
+    fn test_exhausting_accrual_emits_campaign_exhausted_once() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&2u32, &hash, &100i128, &0u64);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &2u32, &60i128);
+        assert_eq!(
+            s.env.events().all().filter_by_contract(&s.client.address),
+            [RewardAccrued {
+                campaign_id: 2,
+                user: user.clone(),
+                amount: 60,
+                new_total: 60
+            }
+            .to_xdr(&s.env, &s.client.address)]
+        );
+
+        s.client.accrue_reward(&user, &2u32, &40i128);
+        assert_eq!(
+            s.env.events().all().filter_by_contract(&s.client.address),
+            [
+                CampaignExhausted {
+                    campaign_id: 2,
+                    final_total: 100
+                }
+                .to_xdr(&s.env, &s.client.address),
+                RewardAccrued {
+                    campaign_id: 2,
+                    user,
+                    amount: 40,
+                    new_total: 100
+                }
+                .to_xdr(&s.env, &s.client.address),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_accrue_over_budget_fails() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&3u32, &hash, &50i128, &0u64);
+
+        let user = Address::generate(&s.env);
+        let result = s.client.try_accrue_reward(&user, &3u32, &51i128);
+        assert_eq!(result, Err(Ok(Error::CampaignExhausted)));
+    }
+
+    #[test]
+    fn test_accrue_unknown_campaign_fails() {
+        let s = setup();
+        let user = Address::generate(&s.env);
+        let result = s.client.try_accrue_reward(&user, &99u32, &10i128);
+        assert_eq!(result, Err(Ok(Error::CampaignNotFound)));
+    }
+
+    #[test]
+    fn test_accrue_zero_amount_fails() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&4u32, &hash, &100i128, &0u64);
+        let user = Address::generate(&s.env);
+        let result = s.client.try_accrue_reward(&user, &4u32, &0i128);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+    }
+
+    #[test]
+    fn test_accrue_on_exhausted_campaign_fails() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&5u32, &hash, &10i128, &0u64);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &5u32, &10i128);
+
+        let other = Address::generate(&s.env);
+        let result = s.client.try_accrue_reward(&other, &5u32, &1i128);
+        assert_eq!(result, Err(Ok(Error::CampaignNotActive)));
+    }
+
+    // ── revoke_accrual ───────────────────────────────────────────────────────
+
+    #[test]
+    fn test_revoke_reactivates_exhausted_campaign() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &100i128, &0u64);
+
+        let wrong_user = Address::generate(&s.env);
+        s.client.accrue_reward(&wrong_user, &1u32, &100i128);
+        assert_eq!(
+            s.client.campaign_state(&1u32).unwrap().status,
+            CampaignStatus::Exhausted
+        );
+
+        s.client.revoke_accrual(&wrong_user, &1u32, &40i128);
+        assert_eq!(
+            s.env.events().all().filter_by_contract(&s.client.address),
+            [RewardRevoked {
+                campaign_id: 1,
+                user: wrong_user.clone(),
+                amount: 40,
+                new_total: 60,
+            }
+            .to_xdr(&s.env, &s.client.address)]
+        );
+
+        let state = s.client.campaign_state(&1u32).unwrap();
+        assert_eq!(state.status, CampaignStatus::Active);
+        assert_eq!(state.remaining, 40i128);
+        assert_eq!(s.client.accrued_for(&wrong_user, &1u32), 60i128);
+
+        // The freed budget can be accrued to the intended user.
+        let right_user = Address::generate(&s.env);
+        s.client.accrue_reward(&right_user, &1u32, &40i128);
+        assert_eq!(
+            s.client.campaign_state(&1u32).unwrap().status,
+            CampaignStatus::Exhausted
+        );
+    }
+
+    #[test]
+    fn test_revoke_more_than_accrued_fails() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &100i128, &0u64);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &30i128);
+
+        let result = s.client.try_revoke_accrual(&user, &1u32, &31i128);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+        let result = s.client.try_revoke_accrual(&user, &1u32, &0i128);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+        assert_eq!(s.client.accrued_for(&user, &1u32), 30i128);
+    }
+
+    // ── migrate_accrual ──────────────────────────────────────────────────────
+
+    #[test]
+    fn test_migrate_accrual_moves_balance_between_campaigns() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &100i128, &0u64);
+        s.client
+            .define_reward_campaign(&2u32, &hash, &500i128, &0u64);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &100i128);
+        s.client.accrue_reward(&user, &2u32, &50i128);
+
+        assert_eq!(s.client.migrate_accrual(&s.admin, &user, &1u32, &2u32), 100);
+        assert_eq!(
+            s.env.events().all().filter_by_contract(&s.client.address),
+            [AccrualMigrated {
+                from_campaign: 1,
+                to_campaign: 2,
+                user: user.clone(),
+                amount: 100,
+            }
+            .to_xdr(&s.env, &s.client.address)]
+        );
+
+        assert_eq!(s.client.accrued_for(&user, &1u32), 0);
+        assert_eq!(s.client.accrued_for(&user, &2u32), 150);
+        let source = s.client.campaign_state(&1u32).unwrap();
+        assert_eq!(source.remaining, 100);
+        assert_eq!(source.status, CampaignStatus::Active);
+        assert_eq!(s.client.campaign_accounting(&1u32), Some((100, 0, 0)));
+        assert_eq!(s.client.campaign_accounting(&2u32), Some((500, 150, 0)));
+        assert_eq!(s.client.campaign_state(&2u32).unwrap().remaining, 350);
+    }
+
+    #[test]
+    fn test_migrate_accrual_cannot_overdraw_destination() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &100i128, &0u64);
+        s.client
+            .define_reward_campaign(&2u32, &hash, &50i128, &0u64);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &80i128);
+
+        let result = s.client.try_migrate_accrual(&s.admin, &user, &1u32, &2u32);
+        assert_eq!(result, Err(Ok(Error::CampaignExhausted)));
+        assert_eq!(s.client.accrued_for(&user, &1u32), 80);
+        assert_eq!(s.client.campaign_state(&2u32).unwrap().remaining, 50);
+    }
+
+    #[test]
+    fn test_migrate_accrual_refused_after_claim() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &100i128, &0u64);
+        s.client
+            .define_reward_campaign(&2u32, &hash, &100i128, &0u64);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &30i128);
+        s.client.claim_reward(&user, &user, &1u32);
+        s.client.accrue_reward(&user, &1u32, &20i128);
+
+        let result = s.client.try_migrate_accrual(&s.admin, &user, &1u32, &2u32);
+        assert_eq!(result, Err(Ok(Error::AlreadyClaimed)));
+        let result = s.client.try_migrate_accrual(&s.admin, &user, &2u32, &2u32);
+        assert_eq!(result, Err(Ok(Error::SameCampaign)));
+        let stranger = Address::generate(&s.env);
+        let result = s
+            .client
+            .try_migrate_accrual(&stranger, &stranger, &2u32, &1u32);
+        assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+    }
+
+    // ── accrue_rewards_batch ─────────────────────────────────────────────────
+
+    #[test]
+    fn test_accrue_batch_applies_all_entries() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
+
+        let alice = Address::generate(&s.env);
+        let bob = Address::generate(&s.env);
+        s.client.accrue_rewards_batch(
+            &1u32,
+            &vec![&s.env, (alice.clone(), 100i128), (bob.clone(), 150i128)],
+        );
+
+        assert_eq!(
+            s.env.events().all().filter_by_contract(&s.client.address),
+            [
+                RewardAccrued {
+                    campaign_id: 1,
+                    user: alice.clone(),
+                    amount: 100,
+                    new_total: 100,
+                }
+                .to_xdr(&s.env, &s.client.address),
+                RewardAccrued {
+                    campaign_id: 1,
+                    user: bob.clone(),
+                    amount: 150,
+                    new_total: 150,
+                }
+                .to_xdr(&s.env, &s.client.address),
+            ]
+        );
+        assert_eq!(s.client.accrued_for(&alice, &1u32), 100i128);
+        assert_eq!(s.client.accrued_for(&bob, &1u32), 150i128);
+        assert_eq!(s.client.campaign_state(&1u32).unwrap().remaining, 250i128);
+    }
+
+    #[test]
+    fn test_accrue_batch_over_budget_reverts_all() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &200i128, &0u64);
+
+        let alice = Address::generate(&s.env);
+        let bob = Address::generate(&s.env);
+        let result = s.client.try_accrue_rewards_batch(
+            &1u32,
+            &vec![&s.env, (alice.clone(), 150i128), (bob.clone(), 51i128)],
+        );
+        assert_eq!(result, Err(Ok(Error::CampaignExhausted)));
+
+        assert_eq!(s.client.accrued_for(&alice, &1u32), 0i128);
+        assert_eq!(s.client.campaign_state(&1u32).unwrap().remaining, 200i128);
+    }
+
+    #[test]
+    fn test_accrue_batch_rejects_empty_and_non_positive() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &200i128, &0u64);
+
+        let result = s.client.try_accrue_rewards_batch(&1u32, &vec![&s.env]);
+        assert_eq!(result, Err(Ok(Error::BatchInvalid)));
+
+        let user = Address::generate(&s.env);
+        let result = s
+            .client
+            .try_accrue_rewards_batch(&1u32, &vec![&s.env, (user, 0i128)]);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+    }
+
+    // ── claim_reward ─────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_claim_succeeds() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &250i128);
+
+        let claimed = s.client.claim_reward(&user, &user, &1u32);
+        assert_eq!(claimed, 250i128);
+
+        assert_eq!(s.client.accrued_for(&user, &1u32), 0i128);
+        assert!(s.client.has_claimed(&user, &1u32));
+
+        let balance = MockBalanceClient::new(&s.env, &s.balance);
+        assert_eq!(balance.balance_of(&user), 250i128);
+    }
+
+    #[test]
+    fn test_token_campaign_settles_in_its_own_token() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        let (promo_token, funder) = promo_token(&s.env, 40);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
+        s.client.define_token_campaign(
+            &2u32,
+            &hash,
+            &500i128,
+            &0u64,
+            &symbol_short!("promo"),
+            &promo_token,
+        );
+
+        assert_eq!(s.client.campaign_token(&1u32), s.balance);
+        assert_eq!(s.client.campaign_token(&2u32), promo_token);
+        assert_eq!(
+            s.client.try_campaign_token(&9u32),
+            Err(Ok(Error::CampaignNotFound))
+        );
+
+        let user = Address::generate(&s.env);
+        s.client.fund_campaign(&2u32, &funder, &40i128);
+        let promo = token::Client::new(&s.env, &promo_token);
+        assert_eq!(promo.balance(&funder), 0i128);
+        assert_eq!(promo.balance(&s.client.address), 40i128);
+
+        s.client.accrue_reward(&user, &1u32, &100i128);
+        s.client.accrue_reward(&user, &2u32, &40i128);
+        s.client.claim_reward(&user, &user, &1u32);
+        s.client.claim_reward(&user, &user, &2u32);
+
+        let global = MockBalanceClient::new(&s.env, &s.balance);
+        assert_eq!(global.balance_of(&user), 100i128);
+        assert_eq!(promo.balance(&user), 40i128);
+        assert_eq!(promo.balance(&s.client.address), 0i128);
+    }
+
+    #[test]
+    fn test_token_campaign_accruals_capped_by_funded_reserve() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        let (promo_token, funder) = promo_token(&s.env, 1_000);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
+        s.client.define_token_campaign(
+            &2u32,
+            &hash,
+            &500i128,
+            &0u64,
+            &DEFAULT_CATEGORY,
+            &promo_token,
+        );
+        assert_eq!(
+            s.client.try_fund_campaign(&1u32, &funder, &100i128),
+            Err(Ok(Error::NotTokenCampaign))
+        );
+
+        // Nothing funded yet, and funding beyond the budget is refused.
+        let user = Address::generate(&s.env);
+        assert_eq!(
+            s.client.try_accrue_reward(&user, &2u32, &10i128),
+            Err(Ok(Error::InsufficientReserve))
+        );
+        assert_eq!(
+            s.client.try_fund_campaign(&2u32, &funder, &501i128),
+            Err(Ok(Error::InvalidAmount))
+        );
+
+        assert_eq!(s.client.fund_campaign(&2u32, &funder, &100i128), 100i128);
+        assert_eq!(
+            s.env.events().all().filter_by_contract(&s.client.address),
+            [CampaignFunded {
+                campaign_id: 2,
+                amount: 100,
+                reserve: 100,
+            }
+            .to_xdr(&s.env, &s.client.address)]
+        );
+        s.client.accrue_reward(&user, &2u32, &80i128);
+        assert_eq!(
+            s.client.try_accrue_reward(&user, &2u32, &30i128),
+            Err(Ok(Error::InsufficientReserve))
+        );
+
+        // Claims draw the reserve down; the unowed rest is what a sweep returns.
+        s.client.claim_reward(&user, &user, &2u32);
+        assert_eq!(s.client.campaign_reserve(&2u32), 20i128);
+        s.client.accrue_reward(&user, &2u32, &15i128);
+        s.client.close_campaign(&2u32);
+        assert_eq!(s.client.sweep_expired(&2u32), 5i128);
+        assert_eq!(s.client.campaign_reserve(&2u32), 15i128);
+        let promo = token::Client::new(&s.env, &promo_token);
+        assert_eq!(promo.balance(&s.treasury), 5i128);
+        assert_eq!(s.client.claim_reward(&user, &user, &2u32), 15i128);
+        assert_eq!(s.client.campaign_reserve(&2u32), 0i128);
+        assert_eq!(promo.balance(&user), 95i128);
+        assert_eq!(promo.balance(&s.client.address), 0i128);
+    }
+
+    #[test]
+    fn test_migrate_accrual_refused_across_tokens() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        let (promo_token, _) = promo_token(&s.env, 0);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
+        s.client.define_token_campaign(
+            &2u32,
+            &hash,
+            &500i128,
+            &0u64,
+            &DEFAULT_CATEGORY,
+            &promo_token,
+        );
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &100i128);
+
+        let result = s.client.try_migrate_accrual(&s.admin, &user, &1u32, &2u32);
+        assert_eq!(result, Err(Ok(Error::TokenMismatch)));
+        assert_eq!(s.client.accrued_for(&user, &1u32), 100);
+    }
+
+    #[test]
+    fn test_claim_reverts_when_settlement_fails() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &250i128);
+
+        let balance = MockBalanceClient::new(&s.env, &s.balance);
+        balance.set_failing(&true);
+        assert!(s.client.try_claim_reward(&user, &user, &1u32).is_err());
+
+        // Nothing was committed: the user can retry once settlement recovers.
+        assert!(!s.client.has_claimed(&user, &1u32));
+        assert_eq!(s.client.accrued_for(&user, &1u32), 250i128);
+
+        balance.set_failing(&false);
+        assert_eq!(s.client.claim_reward(&user, &user, &1u32), 250i128);
+        assert_eq!(balance.balance_of(&user), 250i128);
+    }
+
+    #[test]
+    fn test_claim_event_carries_rules_hash() {
+        let s = setup();
+        let hash = BytesN::from_array(&s.env, &[7u8; 32]);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &120i128);
+        s.client.claim_reward(&user, &user, &1u32);
+
+        assert_eq!(
+            s.env.events().all().filter_by_contract(&s.client.address),
+            [RewardClaimed {
+                campaign_id: 1,
+                user,
+                amount: 120,
+                rules_hash: hash,
+            }
+            .to_xdr(&s.env, &s.client.address)]
+        );
+    }
+
+    #[test]
+    fn test_claim_twice_without_new_accrual_fails() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &100i128);
+        s.client.claim_reward(&user, &user, &1u32);
+
+        let result = s.client.try_claim_reward(&user, &user, &1u32);
+        assert_eq!(result, Err(Ok(Error::NothingToClaim)));
+    }
+
+    #[test]
+    fn test_reaccrual_after_claim_can_be_claimed_again() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &100i128);
+        assert_eq!(s.client.claim_reward(&user, &user, &1u32), 100i128);
+
+        s.client.accrue_reward(&user, &1u32, &60i128);
+        assert_eq!(s.client.accrued_for(&user, &1u32), 60i128);
+        assert_eq!(s.client.claim_reward(&user, &user, &1u32), 60i128);
+
+        assert_eq!(s.client.accrued_for(&user, &1u32), 0i128);
+        assert_eq!(s.client.claimed_total(&user, &1u32), 160i128);
+        let balance = MockBalanceClient::new(&s.env, &s.balance);
+        assert_eq!(balance.balance_of(&user), 160i128);
+    }
+
+    #[test]
+    fn test_claim_nothing_accrued_fails() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
+
+        let user = Address::generate(&s.env);
+        let result = s.client.try_claim_reward(&user, &user, &1u32);
+        assert_eq!(result, Err(Ok(Error::NothingToClaim)));
+    }
+
+    // ── campaign pause ────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_pause_blocks_accrual() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
+        s.client.pause_campaign(&1u32, &false);
+
+        let user = Address::generate(&s.env);
+        let result = s.client.try_accrue_reward(&user, &1u32, &10i128);
+        assert_eq!(result, Err(Ok(Error::CampaignPaused)));
+
+        s.client.unpause_campaign(&1u32);
+        s.client.accrue_reward(&user, &1u32, &10i128);
+        assert_eq!(s.client.accrued_for(&user, &1u32), 10);
+    }
+
+    #[test]
+    fn test_claim_allowed_while_paused_by_default() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &200i128);
+        s.client.pause_campaign(&1u32, &false);
+
+        let claimed = s.client.claim_reward(&user, &user, &1u32);
+        assert_eq!(claimed, 200);
+        assert!(s.client.has_claimed(&user, &1u32));
+    }
+
+    #[test]
+    fn test_claim_blocked_when_pause_blocks_claims() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &200i128);
+        s.client.pause_campaign(&1u32, &true);
+
+        let result = s.client.try_claim_reward(&user, &user, &1u32);
+        assert_eq!(result, Err(Ok(Error::CampaignPaused)));
+        assert_eq!(s.client.accrued_for(&user, &1u32), 200);
+
+        // Accrued funds are released once the campaign is unpaused
+        s.client.unpause_campaign(&1u32);
+        assert_eq!(s.client.claim_reward(&user, &user, &1u32), 200);
+    }
+
+    #[test]
+    fn test_pause_unknown_campaign_fails() {
+        let s = setup();
+        let result = s.client.try_pause_campaign(&9u32, &false);
+        assert_eq!(result, Err(Ok(Error::CampaignNotFound)));
+    }
+
+    // ── multipliers ───────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_tier_multiplier_scales_accrual() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &1_000i128, &0u64);
+
+        let base = Address::generate(&s.env);
+        let gold = Address::generate(&s.env);
+        s.client.set_user_multiplier(&1u32, &gold, &15_000u32);
+        assert_eq!(s.client.user_multiplier(&1u32, &gold), 15_000);
+        assert_eq!(s.client.user_multiplier(&1u32, &base), 10_000);
+
+        s.client.accrue_reward(&base, &1u32, &100i128);
+        s.client.accrue_reward(&gold, &1u32, &100i128);
+        assert_eq!(
+            s.env.events().all().filter_by_contract(&s.client.address),
+            [RewardAccrued {
+                campaign_id: 1,
+                user: gold.clone(),
+                amount: 150,
+                new_total: 150,
+            }
+            .to_xdr(&s.env, &s.client.address)]
+        );
+
+        assert_eq!(s.client.accrued_for(&base, &1u32), 100);
+        assert_eq!(s.client.accrued_for(&gold, &1u32), 150);
+        assert_eq!(s.client.campaign_state(&1u32).unwrap().remaining, 750);
+
+        // The batch path applies the same multiplier
+        s.client
+            .accrue_rewards_batch(&1u32, &vec![&s.env, (gold.clone(), 10i128)]);
+        assert_eq!(s.client.accrued_for(&gold, &1u32), 165);
+    }
+
+    #[test]
+    fn test_multiplier_checks_budget_and_overflow() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &100i128, &0u64);
+
+        let gold = Address::generate(&s.env);
+        s.client.set_user_multiplier(&1u32, &gold, &20_000u32);
+        let result = s.client.try_accrue_reward(&gold, &1u32, &60i128);
+        assert_eq!(result, Err(Ok(Error::CampaignExhausted)));
+        let result = s.client.try_accrue_reward(&gold, &1u32, &i128::MAX);
+        assert_eq!(result, Err(Ok(Error::Overflow)));
+
+        assert_eq!(
+            s.client.try_set_user_multiplier(&1u32, &gold, &0u32),
+            Err(Ok(Error::InvalidAmount))
+        );
+        s.client.set_user_multiplier(&1u32, &gold, &10_000u32);
+        s.client.accrue_reward(&gold, &1u32, &60i128);
+        assert_eq!(s.client.accrued_for(&gold, &1u32), 60);
+    }
+
+    // ── per_user_cap ──────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_accrual_up_to_cap_then_rejected() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &1_000i128, &0u64);
+        s.client.set_per_user_cap(&1u32, &100i128);
+        assert_eq!(s.client.campaign_state(&1u32).unwrap().per_user_cap, 100);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &60i128);
+        s.client.accrue_reward(&user, &1u32, &40i128);
+        assert_eq!(s.client.accrued_for(&user, &1u32), 100);
+
+        let result = s.client.try_accrue_reward(&user, &1u32, &1i128);
+        assert_eq!(result, Err(Ok(Error::UserCapExceeded)));
+        assert_eq!(s.client.campaign_state(&1u32).unwrap().remaining, 900);
+
+        // Claiming does not free headroom; other users are unaffected.
+        s.client.claim_reward(&user, &user, &1u32);
+        let result = s.client.try_accrue_reward(&user, &1u32, &1i128);
+        assert_eq!(result, Err(Ok(Error::UserCapExceeded)));
+        let other = Address::generate(&s.env);
+        s.client.accrue_reward(&other, &1u32, &100i128);
+    }
+
+    #[test]
+    fn test_cap_applies_within_batch_and_zero_is_unlimited() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &1_000i128, &0u64);
+        s.client.set_per_user_cap(&1u32, &50i128);
+
+        let user = Address::generate(&s.env);
+        let entries = vec![&s.env, (user.clone(), 30i128), (user.clone(), 30i128)];
+        let result = s.client.try_accrue_rewards_batch(&1u32, &entries);
+        assert_eq!(result, Err(Ok(Error::UserCapExceeded)));
+        assert_eq!(s.client.accrued_for(&user, &1u32), 0);
+
+        s.client.set_per_user_cap(&1u32, &0i128);
+        s.client.accrue_rewards_batch(&1u32, &entries);
+        assert_eq!(s.client.accrued_for(&user, &1u32), 60);
+        assert_eq!(
+            s.client.try_set_per_user_cap(&1u32, &-1i128),
+            Err(Ok(Error::InvalidAmount))
+        );
+    }
+
+    // ── vesting ───────────────────────────────────────────────────────────────
+
+    #[test]
+    fn test_claim_reward_pays_only_vested_part() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &1_000i128, &0u64);
+        let start = s.env.ledger().timestamp();
+        s.client
+            .set_campaign_vesting(&1u32, &(start + 100), &5_000u32);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &400i128);
+        assert_eq!(
+            s.client.try_claim_reward(&user, &user, &1u32),
+            Err(Ok(Error::NothingToClaim))
+        );
+
+        s.env.ledger().with_mut(|li| li.timestamp += 25);
+        assert_eq!(s.client.preview_claim(&user, &1u32), 100);
+        assert_eq!(s.client.claim_reward(&user, &user, &1u32), 100);
+        assert_eq!(s.client.accrued_for(&user, &1u32), 300);
+
+        s.env.ledger().with_mut(|li| li.timestamp += 100);
+        assert_eq!(s.client.claim_reward(&user, &user, &1u32), 300);
+        assert_eq!(s.client.claimed_total(&user, &1u32), 400);
+    }
+
+    #[test]
+    fn test_claim_early_forfeits_share_of_unvested() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &1_000i128, &0u64);
+        let start = s.env.ledger().timestamp();
+        s.client
+            .set_campaign_vesting(&1u32, &(start + 100), &2_500u32);
+        assert_eq!(s.client.early_forfeit_bps(&1u32), 2_500);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &400i128);
+        s.env.ledger().with_mut(|li| li.timestamp += 50);
+
+        // 200 vested + 200 unvested, 25% of which (50) is forfeited.
+        assert_eq!(s.client.claim_early(&user, &1u32), 350);
+        assert_eq!(
+            s.env.events().all().filter_by_contract(&s.client.address),
+            [EarlyClaimed {
+                campaign_id: 1,
+                user: user.clone(),
+                amount: 350,
+                forfeited: 50
+            }
+            .to_xdr(&s.env, &s.client.address)]
+        );
+
+        assert_eq!(s.client.accrued_for(&user, &1u32), 0);
+        assert_eq!(s.client.claimed_total(&user, &1u32), 350);
+        let balance = MockBalanceClient::new(&s.env, &s.balance);
+        assert_eq!(balance.balance_of(&user), 350);
+        let campaign = s.client.campaign_state(&1u32).unwrap();
+        assert_eq!(campaign.remaining, 650);
+        assert_eq!(campaign.total_accrued, 350);
+        assert_eq!(campaign.total_claimed, 350);
+    }
+
+    #[test]
+    fn test_claim_early_rejects_non_vesting_campaign() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &1_000i128, &0u64);
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &100i128);
+
+        assert_eq!(
+            s.client.try_claim_early(&user, &1u32),
+            Err(Ok(Error::NotVesting))
+        );
+        assert_eq!(
+            s.client.try_early_forfeit_bps(&1u32),
+            Err(Ok(Error::NotVesting))
+        );
+        assert_eq!(
+            s.client.try_set_campaign_vesting(&1u32, &0u64, &10_001u32),
+            Err(Ok(Error::InvalidAmount))
+        );
+    }
+
+    // ── top_up_campaign ───────────────────────────────────────────────────────
+
+    #[test]
+    fn test_top_up_revives_exhausted_campaign() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &100i128, &0u64);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &100i128);
+        assert_eq!(
+            s.client.campaign_state(&1u32).unwrap().status,
+            CampaignStatus::Exhausted
+        );
+
+        s.client.top_up_campaign(&1u32, &250i128);
+        assert_eq!(
+            s.env.events().all().filter_by_contract(&s.client.address),
+            [CampaignToppedUp {
+                campaign_id: 1,
+                additional: 250,
+                new_budget: 350,
+            }
+            .to_xdr(&s.env, &s.client.address)]
+        );
 
-        RewardClaimed { campaign_id, user, amount: accrued }.publish(&env);
+        let state = s.client.campaign_state(&1u32).unwrap();
+        assert_eq!(state.status, CampaignStatus::Active);
+        assert_eq!(state.budget, 350);
+        assert_eq!(state.remaining, 250);
 
-        Ok(accrued)
+        s.client.accrue_reward(&user, &1u32, &250i128);
+        assert_eq!(s.client.accrued_for(&user, &1u32), 350);
     }
 
-    // -----------------------------------------------------------------------
-    // Queries
-    // -----------------------------------------------------------------------
+    #[test]
+    fn test_top_up_rejects_bad_amount_and_closed_campaign() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &100i128, &0u64);
 
-    /// Return the current state of a campaign, or `None` if it does not exist.
-    pub fn campaign_state(env: Env, campaign_id: u32) -> Option<CampaignData> {
-        env.storage()
-            .persistent()
-            .get(&DataKey::Campaign(campaign_id))
-    }
+        assert_eq!(
+            s.client.try_top_up_campaign(&1u32, &0i128),
+            Err(Ok(Error::InvalidAmount))
+        );
+        assert_eq!(
+            s.client.try_top_up_campaign(&1u32, &-5i128),
+            Err(Ok(Error::InvalidAmount))
+        );
+        assert_eq!(
+            s.client.try_top_up_campaign(&9u32, &10i128),
+            Err(Ok(Error::CampaignNotFound))
+        );
 
-    /// Return the unclaimed accrued balance for `user` in a campaign.
-    pub fn accrued_for(env: Env, user: Address, campaign_id: u32) -> i128 {
-        env.storage()
-            .persistent()
-            .get(&DataKey::Accrued(campaign_id, user))
-            .unwrap_or(0i128)
+        s.client.close_campaign(&1u32);
+        assert_eq!(
+            s.client.try_top_up_campaign(&1u32, &10i128),
+            Err(Ok(Error::CampaignNotActive))
+        );
+        assert_eq!(s.client.campaign_state(&1u32).unwrap().budget, 100);
     }
 
-    /// Return whether `user` has already claimed from `campaign_id`.
-    pub fn has_claimed(env: Env, user: Address, campaign_id: u32) -> bool {
-        env.storage()
-            .persistent()
-            .has(&DataKey::Claimed(campaign_id, user))
-    }
+    // ── close_campaign ────────────────────────────────────────────────────────
 
-    // -----------------------------------------------------------------------
-    // Internal helpers
-    // -----------------------------------------------------------------------
+    #[test]
+    fn test_close_with_pending_claims() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &1_000i128, &0u64);
+
+        let claimer = Address::generate(&s.env);
+        let pending = Address::generate(&s.env);
+        s.client.accrue_reward(&claimer, &1u32, &300i128);
+        s.client.accrue_reward(&pending, &1u32, &200i128);
+        s.client.claim_reward(&claimer, &claimer, &1u32);
+
+        // Only the unaccrued budget comes back; `pending`'s 200 stays reserved.
+        let reclaimable = s.client.close_campaign(&1u32);
+        assert_eq!(reclaimable, 500);
+        assert_eq!(
+            s.env.events().all().filter_by_contract(&s.client.address),
+            [CampaignClosed {
+                campaign_id: 1,
+                reclaimable: 500,
+            }
+            .to_xdr(&s.env, &s.client.address)]
+        );
 
-    fn require_initialized(env: &Env) -> Result<Address, Error> {
-        env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)
-    }
-}
+        let state = s.client.campaign_state(&1u32).unwrap();
+        assert_eq!(state.status, CampaignStatus::Closed);
 
-// ===========================================================================
-// Tests
-// ===========================================================================
+        let result = s.client.try_accrue_reward(&pending, &1u32, &10i128);
+        assert_eq!(result, Err(Ok(Error::CampaignNotActive)));
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::{testutils::Address as _, Address, BytesN, Env};
+        assert_eq!(s.client.claim_reward(&pending, &pending, &1u32), 200);
+    }
 
-    // ── Helpers ─────────────────────────────────────────────────────────────
+    #[test]
+    fn test_close_twice_fails() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &100i128, &0u64);
+        s.client.close_campaign(&1u32);
 
-    fn rules_hash(env: &Env) -> BytesN<32> {
-        BytesN::from_array(env, &[0u8; 32])
+        let result = s.client.try_close_campaign(&1u32);
+        assert_eq!(result, Err(Ok(Error::CampaignNotActive)));
     }
 
-    struct Setup {
-        env: Env,
-        client: RewardDistributionClient<'static>,
-        admin: Address,
-        treasury: Address,
-        balance: Address,
+    #[test]
+    fn test_close_unknown_campaign_fails() {
+        let s = setup();
+        let result = s.client.try_close_campaign(&9u32);
+        assert_eq!(result, Err(Ok(Error::CampaignNotFound)));
     }
 
-    fn setup() -> Setup {
-        let env = Env::default();
-        env.mock_all_auths();
-
-        let contract_id = env.register(RewardDistribution, ());
-        let client = RewardDistributionClient::new(&env, &contract_id);
-
-        let admin = Address::generate(&env);
-        let treasury = Address::generate(&env);
-        let balance = Address::generate(&env);
+    // ── expiry ────────────────────────────────────────────────────────────────
 
-        client.init(&admin, &treasury, &balance);
+    #[test]
+    fn test_accrue_after_expiry_fails() {
+        let s = setup();
+        s.env.ledger().set_timestamp(100);
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &200u64);
 
-        // SAFETY: client borrows env by reference; env is moved into Setup and
-        // lives as long as all accesses through client.
-        let client: RewardDistributionClient<'static> = unsafe { core::mem::transmute(client) };
+        let user = Address::generate(&s.env);
+        s.env.ledger().set_timestamp(200);
+        s.client.accrue_reward(&user, &1u32, &50i128);
 
-        Setup {
-            env,
-            client,
-            admin,
-            treasury,
-            balance,
-        }
+        s.env.ledger().set_timestamp(201);
+        let result = s.client.try_accrue_reward(&user, &1u32, &50i128);
+        assert_eq!(result, Err(Ok(Error::CampaignExpired)));
     }
 
-    // ── init ────────────────────────────────────────────────────────────────
-
     #[test]
-    fn test_init_succeeds() {
-        let env = Env::default();
-        env.mock_all_auths();
-
-        let contract_id = env.register(RewardDistribution, ());
-        let client = RewardDistributionClient::new(&env, &contract_id);
+    fn test_sweep_expired_releases_remaining_budget() {
+        let s = setup();
+        s.env.ledger().set_timestamp(100);
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &200u64);
 
-        let admin = Address::generate(&env);
-        let treasury = Address::generate(&env);
-        let balance = Address::generate(&env);
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &120i128);
+
+        let result = s.client.try_sweep_expired(&1u32);
+        assert_eq!(result, Err(Ok(Error::CampaignNotExpired)));
+
+        s.env.ledger().set_timestamp(201);
+        assert_eq!(s.client.sweep_expired(&1u32), 380i128);
+        assert_eq!(
+            s.env.events().all().filter_by_contract(&s.client.address),
+            [CampaignSwept {
+                campaign_id: 1,
+                treasury: s.treasury.clone(),
+                amount: 380,
+            }
+            .to_xdr(&s.env, &s.client.address)]
+        );
 
-        client.init(&admin, &treasury, &balance);
+        // The budget was never taken from the treasury, so nothing is credited.
+        let balance = MockBalanceClient::new(&s.env, &s.balance);
+        assert_eq!(balance.balance_of(&s.treasury), 0i128);
+        let state = s.client.campaign_state(&1u32).unwrap();
+        assert_eq!(state.status, CampaignStatus::Closed);
+        assert_eq!(state.remaining, 0i128);
+        assert!(state.swept);
+
+        // Accruals made before expiry remain claimable; a second sweep fails.
+        assert_eq!(s.client.claim_reward(&user, &user, &1u32), 120i128);
+        let result = s.client.try_sweep_expired(&1u32);
+        assert_eq!(result, Err(Ok(Error::AlreadySwept)));
     }
 
     #[test]
-    fn test_init_twice_fails() {
+    fn test_closed_campaign_is_swept_once() {
         let s = setup();
-        let result = s.client.try_init(&s.admin, &s.treasury, &s.balance);
-        assert_eq!(result, Err(Ok(Error::AlreadyInitialized)));
-    }
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
 
-    // ── define_reward_campaign ───────────────────────────────────────────────
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &120i128);
+        assert_eq!(s.client.close_campaign(&1u32), 380i128);
+
+        // No expiry needed once the campaign is closed.
+        assert_eq!(s.client.sweep_expired(&1u32), 380i128);
+
+        // A revocation after the sweep refills `remaining`, but cannot be
+        // swept a second time.
+        s.client.revoke_accrual(&user, &1u32, &20i128);
+        assert_eq!(s.client.campaign_state(&1u32).unwrap().remaining, 20i128);
+        assert_eq!(
+            s.client.try_sweep_expired(&1u32),
+            Err(Ok(Error::AlreadySwept))
+        );
+        let balance = MockBalanceClient::new(&s.env, &s.balance);
+        assert_eq!(balance.balance_of(&s.treasury), 0i128);
+        assert_eq!(s.client.claim_reward(&user, &user, &1u32), 100i128);
+    }
 
     #[test]
-    fn test_define_campaign_succeeds() {
+    fn test_zero_expiry_never_expires() {
         let s = setup();
         let hash = rules_hash(&s.env);
-        s.client.define_reward_campaign(&1u32, &hash, &1_000i128);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
 
-        let state = s.client.campaign_state(&1u32).unwrap();
-        assert_eq!(state.budget, 1_000);
-        assert_eq!(state.remaining, 1_000);
-        assert_eq!(state.status, CampaignStatus::Active);
+        s.env.ledger().set_timestamp(u64::MAX);
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &50i128);
+        let result = s.client.try_sweep_expired(&1u32);
+        assert_eq!(result, Err(Ok(Error::CampaignNotExpired)));
     }
 
+    // ── queries ───────────────────────────────────────────────────────────────
+
     #[test]
-    fn test_define_campaign_duplicate_fails() {
+    fn test_campaign_state_unknown_returns_none() {
         let s = setup();
-        let hash = rules_hash(&s.env);
-        s.client.define_reward_campaign(&1u32, &hash, &500i128);
-        let result = s.client.try_define_reward_campaign(&1u32, &hash, &500i128);
-        assert_eq!(result, Err(Ok(Error::CampaignAlreadyExists)));
+        assert!(s.client.campaign_state(&999u32).is_none());
     }
 
     #[test]
-    fn test_define_campaign_zero_budget_fails() {
+    fn test_campaign_accounting_tracks_accrued_and_claimed() {
         let s = setup();
         let hash = rules_hash(&s.env);
-        let result = s.client.try_define_reward_campaign(&1u32, &hash, &0i128);
-        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+        s.client
+            .define_reward_campaign(&1u32, &hash, &1_000i128, &0u64);
+        assert_eq!(s.client.campaign_accounting(&1u32), Some((1_000, 0, 0)));
+
+        let alice = Address::generate(&s.env);
+        let bob = Address::generate(&s.env);
+        s.client.accrue_reward(&alice, &1u32, &300i128);
+        s.client
+            .accrue_rewards_batch(&1u32, &vec![&s.env, (bob.clone(), 200i128)]);
+        s.client.revoke_accrual(&bob, &1u32, &50i128);
+        assert_eq!(s.client.campaign_accounting(&1u32), Some((1_000, 450, 0)));
+
+        s.client.claim_reward(&alice, &alice, &1u32);
+        assert_eq!(s.client.campaign_accounting(&1u32), Some((1_000, 450, 300)));
+        assert_eq!(s.client.campaign_accounting(&2u32), None);
     }
 
     #[test]
-    fn test_define_campaign_negative_budget_fails() {
+    fn test_campaign_snapshot_reflects_finances() {
         let s = setup();
         let hash = rules_hash(&s.env);
-        let result = s.client.try_define_reward_campaign(&1u32, &hash, &(-1i128));
-        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
-    }
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
+        assert!(s.client.campaign_snapshot(&2u32).is_none());
 
-    // ── accrue_reward ────────────────────────────────────────────────────────
+        let alice = Address::generate(&s.env);
+        let bob = Address::generate(&s.env);
+        s.client.accrue_reward(&alice, &1u32, &200i128);
+        s.client.accrue_reward(&bob, &1u32, &300i128);
+        s.client.claim_reward(&alice, &alice, &1u32);
+
+        assert_eq!(
+            s.client.campaign_snapshot(&1u32),
+            Some(CampaignSnapshot {
+                budget: 500,
+                remaining: 0,
+                total_accrued: 500,
+                total_claimed: 200,
+                status: CampaignStatus::Exhausted,
+            })
+        );
+    }
 
     #[test]
-    fn test_accrue_succeeds_and_accumulates() {
+    fn test_campaign_timing_records_definition_time() {
         let s = setup();
+        s.env.ledger().set_timestamp(1_000);
         let hash = rules_hash(&s.env);
-        s.client.define_reward_campaign(&1u32, &hash, &1_000i128);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &100i128, &0u64);
 
-        let user = Address::generate(&s.env);
-        s.client.accrue_reward(&user, &1u32, &300i128);
-        s.client.accrue_reward(&user, &1u32, &200i128);
+        s.client
+            .define_reward_campaign(&2u32, &hash, &100i128, &8_000u64);
 
-        assert_eq!(s.client.accrued_for(&user, &1u32), 500i128);
+        s.env.ledger().set_timestamp(5_000);
+        assert_eq!(s.client.campaign_timing(&1u32), Some((1_000, 0, u64::MAX)));
+        assert_eq!(s.client.campaign_timing(&2u32), Some((1_000, 8_000, 3_000)));
 
-        let state = s.client.campaign_state(&1u32).unwrap();
-        assert_eq!(state.remaining, 500i128);
+        s.env.ledger().set_timestamp(9_000);
+        assert_eq!(s.client.campaign_timing(&2u32), Some((1_000, 8_000, 0)));
+        assert_eq!(s.client.campaign_timing(&3u32), None);
     }
 
     #[test]
-    fn test_accrue_exhausts_campaign() {
+    fn test_has_claimed_false_before_claim() {
         let s = setup();
         let hash = rules_hash(&s.env);
-        s.client.define_reward_campaign(&2u32, &hash, &100i128);
-
+        s.client
+            .define_reward_campaign(&1u32, &hash, &100i128, &0u64);
         let user = Address::generate(&s.env);
-        s.client.accrue_reward(&user, &2u32, &100i128);
-
-        let state = s.client.campaign_state(&2u32).unwrap();
-        assert_eq!(state.status, CampaignStatus::Exhausted);
-        assert_eq!(state.remaining, 0);
+        s.client.accrue_reward(&user, &1u32, &50i128);
+        assert!(!s.client.has_claimed(&user, &1u32));
     }
 
     #[test]
-    fn test_accrue_over_budget_fails() {
+    fn test_preview_claim_matches_claim() {
         let s = setup();
         let hash = rules_hash(&s.env);
-        s.client.define_reward_campaign(&3u32, &hash, &50i128);
-
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
         let user = Address::generate(&s.env);
-        let result = s.client.try_accrue_reward(&user, &3u32, &51i128);
-        assert_eq!(result, Err(Ok(Error::CampaignExhausted)));
+        assert_eq!(s.client.preview_claim(&user, &1u32), 0);
+        assert_eq!(s.client.preview_claim(&user, &9u32), 0);
+
+        s.client.set_user_multiplier(&1u32, &user, &15_000u32);
+        s.client.accrue_reward(&user, &1u32, &100i128);
+        assert_eq!(s.client.preview_claim(&user, &1u32), 150);
+
+        s.client.pause_campaign(&1u32, &true);
+        assert_eq!(s.client.preview_claim(&user, &1u32), 0);
+        s.client.unpause_campaign(&1u32);
+
+        s.client.pause(&s.admin);
+        assert_eq!(s.client.preview_claim(&user, &1u32), 0);
+        s.client.unpause(&s.admin);
+
+        assert_eq!(s.client.preview_claim(&user, &1u32), 150);
+        assert_eq!(s.client.claim_reward(&user, &user, &1u32), 150);
+        assert_eq!(s.client.preview_claim(&user, &1u32), 0);
     }
 
     #[test]
-    fn test_accrue_unknown_campaign_fails() {
+    fn test_user_state_tracks_accrue_and_claim() {
         let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &100i128, &0u64);
         let user = Address::generate(&s.env);
-        let result = s.client.try_accrue_reward(&user, &99u32, &10i128);
-        assert_eq!(result, Err(Ok(Error::CampaignNotFound)));
+        assert_eq!(s.client.user_state(&user, &1u32), (0, false));
+
+        s.client.accrue_reward(&user, &1u32, &50i128);
+        assert_eq!(s.client.user_state(&user, &1u32), (50, false));
+
+        s.client.claim_reward(&user, &user, &1u32);
+        assert_eq!(s.client.user_state(&user, &1u32), (0, true));
     }
 
+    // ── min_claim ─────────────────────────────────────────────────────────────
+
     #[test]
-    fn test_accrue_zero_amount_fails() {
+    fn test_min_claim_boundary() {
         let s = setup();
         let hash = rules_hash(&s.env);
-        s.client.define_reward_campaign(&4u32, &hash, &100i128);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &1_000i128, &0u64);
+        assert_eq!(s.client.min_claim(), 0);
+        s.client.set_min_claim(&s.admin, &50i128);
+        assert_eq!(s.client.min_claim(), 50);
+
         let user = Address::generate(&s.env);
-        let result = s.client.try_accrue_reward(&user, &4u32, &0i128);
-        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+        s.client.accrue_reward(&user, &1u32, &49i128);
+        assert_eq!(
+            s.client.try_claim_reward(&user, &user, &1u32),
+            Err(Ok(Error::BelowMinimumClaim))
+        );
+        assert_eq!(s.client.preview_claim(&user, &1u32), 0);
+
+        // Exactly the threshold is allowed.
+        s.client.accrue_reward(&user, &1u32, &1i128);
+        assert_eq!(s.client.claim_reward(&user, &user, &1u32), 50);
     }
 
     #[test]
-    fn test_accrue_on_exhausted_campaign_fails() {
+    fn test_min_claim_zero_disables_and_nothing_to_claim_wins() {
         let s = setup();
         let hash = rules_hash(&s.env);
-        s.client.define_reward_campaign(&5u32, &hash, &10i128);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &1_000i128, &0u64);
+        s.client.set_min_claim(&s.admin, &50i128);
 
         let user = Address::generate(&s.env);
-        s.client.accrue_reward(&user, &5u32, &10i128);
+        assert_eq!(
+            s.client.try_claim_reward(&user, &user, &1u32),
+            Err(Ok(Error::NothingToClaim))
+        );
+
+        s.client.accrue_reward(&user, &1u32, &1i128);
+        s.client.set_min_claim(&s.admin, &0i128);
+        assert_eq!(s.client.claim_reward(&user, &user, &1u32), 1);
 
+        assert_eq!(
+            s.client.try_set_min_claim(&s.admin, &-1i128),
+            Err(Ok(Error::InvalidAmount))
+        );
         let other = Address::generate(&s.env);
-        let result = s.client.try_accrue_reward(&other, &5u32, &1i128);
-        assert_eq!(result, Err(Ok(Error::CampaignNotActive)));
+        assert_eq!(
+            s.client.try_set_min_claim(&other, &10i128),
+            Err(Ok(Error::NotAuthorized))
+        );
     }
 
-    // ── claim_reward ─────────────────────────────────────────────────────────
+    // ── claim delegation ──────────────────────────────────────────────────────
 
     #[test]
-    fn test_claim_succeeds() {
+    fn test_delegate_claims_to_user() {
         let s = setup();
         let hash = rules_hash(&s.env);
-        s.client.define_reward_campaign(&1u32, &hash, &500i128);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
 
         let user = Address::generate(&s.env);
-        s.client.accrue_reward(&user, &1u32, &250i128);
+        let relayer = Address::generate(&s.env);
+        let stranger = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &120i128);
 
-        let claimed = s.client.claim_reward(&user, &1u32);
-        assert_eq!(claimed, 250i128);
+        assert_eq!(
+            s.client.try_claim_reward(&relayer, &user, &1u32),
+            Err(Ok(Error::NotAuthorized))
+        );
 
-        assert_eq!(s.client.accrued_for(&user, &1u32), 0i128);
-        assert!(s.client.has_claimed(&user, &1u32));
+        s.client.set_claim_delegate(&user, &relayer);
+        assert_eq!(s.client.claim_delegate(&user), Some(relayer.clone()));
+        assert_eq!(
+            s.client.try_claim_reward(&stranger, &user, &1u32),
+            Err(Ok(Error::NotAuthorized))
+        );
+
+        assert_eq!(s.client.claim_reward(&relayer, &user, &1u32), 120);
+        let balance = MockBalanceClient::new(&s.env, &s.balance);
+        assert_eq!(balance.balance_of(&user), 120);
+        assert_eq!(balance.balance_of(&relayer), 0);
+
+        s.client.clear_claim_delegate(&user);
+        assert_eq!(s.client.claim_delegate(&user), None);
+        s.client.accrue_reward(&user, &1u32, &10i128);
+        assert_eq!(
+            s.client.try_claim_reward(&relayer, &user, &1u32),
+            Err(Ok(Error::NotAuthorized))
+        );
     }
 
     #[test]
-    fn test_claim_twice_fails() {
+    fn test_delegate_claim_requires_delegate_auth() {
         let s = setup();
         let hash = rules_hash(&s.env);
-        s.client.define_reward_campaign(&1u32, &hash, &500i128);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
 
         let user = Address::generate(&s.env);
-        s.client.accrue_reward(&user, &1u32, &100i128);
-        s.client.claim_reward(&user, &1u32);
+        let relayer = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &50i128);
+        s.client.set_claim_delegate(&user, &relayer);
 
-        let result = s.client.try_claim_reward(&user, &1u32);
-        assert_eq!(result, Err(Ok(Error::AlreadyClaimed)));
+        s.env.set_auths(&[]);
+        assert!(s.client.try_claim_reward(&relayer, &user, &1u32).is_err());
+        assert_eq!(s.client.accrued_for(&user, &1u32), 50);
     }
 
+    // ── emergency controls ────────────────────────────────────────────────────
+
     #[test]
-    fn test_claim_nothing_accrued_fails() {
+    fn test_pause_blocks_mutations_but_not_reads() {
         let s = setup();
         let hash = rules_hash(&s.env);
-        s.client.define_reward_campaign(&1u32, &hash, &500i128);
-
         let user = Address::generate(&s.env);
-        let result = s.client.try_claim_reward(&user, &1u32);
-        assert_eq!(result, Err(Ok(Error::NothingToClaim)));
-    }
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
+        s.client.accrue_reward(&user, &1u32, &100i128);
 
-    // ── queries ───────────────────────────────────────────────────────────────
+        s.client.pause(&s.admin);
+        assert!(s.client.is_paused());
+        assert_eq!(s.client.try_pause(&s.admin), Err(Ok(Error::AlreadyPaused)));
+        assert_eq!(
+            s.client
+                .try_define_reward_campaign(&2u32, &hash, &500i128, &0u64),
+            Err(Ok(Error::ContractPaused))
+        );
+        assert_eq!(
+            s.client.try_accrue_reward(&user, &1u32, &10i128),
+            Err(Ok(Error::ContractPaused))
+        );
+        assert_eq!(
+            s.client.try_claim_reward(&user, &user, &1u32),
+            Err(Ok(Error::ContractPaused))
+        );
+        assert_eq!(s.client.campaign_state(&1u32).unwrap().remaining, 400);
+        assert_eq!(s.client.accrued_for(&user, &1u32), 100);
+
+        s.client.unpause(&s.admin);
+        assert_eq!(s.client.try_unpause(&s.admin), Err(Ok(Error::NotPaused)));
+        assert_eq!(s.client.claim_reward(&user, &user, &1u32), 100);
+    }
 
     #[test]
-    fn test_campaign_state_unknown_returns_none() {
+    fn test_pause_requires_admin() {
         let s = setup();
-        assert!(s.client.campaign_state(&999u32).is_none());
+        let stranger = Address::generate(&s.env);
+        assert_eq!(s.client.try_pause(&stranger), Err(Ok(Error::NotAuthorized)));
+        assert!(!s.client.is_paused());
     }
 
     #[test]
-    fn test_has_claimed_false_before_claim() {
+    fn test_kill_switch_is_permanent() {
         let s = setup();
         let hash = rules_hash(&s.env);
-        s.client.define_reward_campaign(&1u32, &hash, &100i128);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &500i128, &0u64);
+
+        let reason = BytesN::from_array(&s.env, &[9u8; 32]);
+        s.client.trigger_kill_switch(&s.admin, &reason);
+        assert!(s.client.is_killed());
+        assert!(s.client.is_paused());
+        assert_eq!(
+            s.client.try_unpause(&s.admin),
+            Err(Ok(Error::ContractKilled))
+        );
+
         let user = Address::generate(&s.env);
-        s.client.accrue_reward(&user, &1u32, &50i128);
-        assert!(!s.client.has_claimed(&user, &1u32));
+        assert_eq!(
+            s.client.try_accrue_reward(&user, &1u32, &10i128),
+            Err(Ok(Error::ContractKilled))
+        );
+        assert!(s.client.campaign_state(&1u32).is_some());
     }
 
     // ── not-initialized guard ─────────────────────────────────────────────────
@@ -633,7 +3832,7 @@ mod test {
         let contract_id = env.register(RewardDistribution, ());
         let client = RewardDistributionClient::new(&env, &contract_id);
         let hash = BytesN::from_array(&env, &[0u8; 32]);
-        let result = client.try_define_reward_campaign(&1u32, &hash, &100i128);
+        let result = client.try_define_reward_campaign(&1u32, &hash, &100i128, &0u64);
         assert_eq!(result, Err(Ok(Error::NotInitialized)));
     }
 
@@ -655,7 +3854,7 @@ mod test {
         let contract_id = env.register(RewardDistribution, ());
         let client = RewardDistributionClient::new(&env, &contract_id);
         let user = Address::generate(&env);
-        let result = client.try_claim_reward(&user, &1u32);
+        let result = client.try_claim_reward(&user, &user, &1u32);
         assert_eq!(result, Err(Ok(Error::NotInitialized)));
     }
 
@@ -665,7 +3864,8 @@ mod test {
     fn test_multiple_users_independent_accrual() {
         let s = setup();
         let hash = rules_hash(&s.env);
-        s.client.define_reward_campaign(&1u32, &hash, &1_000i128);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &1_000i128, &0u64);
 
         let alice = Address::generate(&s.env);
         let bob = Address::generate(&s.env);
@@ -676,7 +3876,7 @@ mod test {
         assert_eq!(s.client.accrued_for(&alice, &1u32), 400i128);
         assert_eq!(s.client.accrued_for(&bob, &1u32), 300i128);
 
-        let alice_claimed = s.client.claim_reward(&alice, &1u32);
+        let alice_claimed = s.client.claim_reward(&alice, &alice, &1u32);
         assert_eq!(alice_claimed, 400i128);
 
         // Bob's balance is unaffected by Alice's claim
@@ -687,4 +3887,96 @@ mod test {
         let campaign = s.client.campaign_state(&1u32).unwrap();
         assert_eq!(campaign.remaining, 300i128);
     }
+
+    #[test]
+    fn test_outstanding_liability_across_campaigns() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &1_000i128, &0u64);
+        s.client
+            .define_reward_campaign(&2u32, &hash, &1_000i128, &0u64);
+        assert_eq!(s.client.outstanding_liability(), 0);
+
+        let alice = Address::generate(&s.env);
+        let bob = Address::generate(&s.env);
+        s.client.accrue_reward(&alice, &1u32, &300i128);
+        s.client
+            .accrue_rewards_batch(&2u32, &vec![&s.env, (bob.clone(), 200i128)]);
+        assert_eq!(s.client.outstanding_liability(), 500);
+
+        s.client.claim_reward(&alice, &alice, &1u32);
+        assert_eq!(s.client.outstanding_liability(), 200);
+        s.client.revoke_accrual(&bob, &2u32, &50i128);
+        assert_eq!(s.client.outstanding_liability(), 150);
+        s.client.migrate_accrual(&s.admin, &bob, &2u32, &1u32);
+        assert_eq!(s.client.outstanding_liability(), 150);
+
+        // Early claims settle both the payout and the forfeited part.
+        let start = s.env.ledger().timestamp();
+        s.client
+            .set_campaign_vesting(&1u32, &(start + 100), &5_000u32);
+        s.client.claim_early(&bob, &1u32);
+        assert_eq!(s.client.outstanding_liability(), 0);
+    }
+
+    #[test]
+    fn test_outstanding_liability_is_kept_per_token() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        let (promo_token, funder) = promo_token(&s.env, 1_000);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &1_000i128, &0u64);
+        s.client.define_token_campaign(
+            &2u32,
+            &hash,
+            &1_000i128,
+            &0u64,
+            &DEFAULT_CATEGORY,
+            &promo_token,
+        );
+        s.client.fund_campaign(&2u32, &funder, &1_000i128);
+
+        let user = Address::generate(&s.env);
+        s.client.accrue_reward(&user, &1u32, &300i128);
+        s.client.accrue_reward(&user, &2u32, &40i128);
+        assert_eq!(s.client.outstanding_liability_for(&s.balance), 300);
+        assert_eq!(s.client.outstanding_liability_for(&promo_token), 40);
+        assert_eq!(s.client.outstanding_liability(), 340);
+
+        s.client.claim_reward(&user, &user, &2u32);
+        assert_eq!(s.client.outstanding_liability_for(&s.balance), 300);
+        assert_eq!(s.client.outstanding_liability_for(&promo_token), 0);
+        assert_eq!(s.client.outstanding_liability(), 300);
+    }
+
+    #[test]
+    fn test_recipient_count_tracks_distinct_users() {
+        let s = setup();
+        let hash = rules_hash(&s.env);
+        s.client
+            .define_reward_campaign(&1u32, &hash, &1_000i128, &0u64);
+        assert_eq!(s.client.recipient_count(&1u32), 0);
+
+        let alice = Address::generate(&s.env);
+        let bob = Address::generate(&s.env);
+        let carol = Address::generate(&s.env);
+        s.client.accrue_reward(&alice, &1u32, &100i128);
+        s.client.accrue_reward(&alice, &1u32, &50i128);
+        s.client.accrue_reward(&bob, &1u32, &100i128);
+        s.client
+            .accrue_rewards_batch(&1u32, &vec![&s.env, (carol.clone(), 10i128)]);
+        assert_eq!(s.client.recipient_count(&1u32), 3);
+
+        // Claiming and partial revocation keep the count.
+        s.client.claim_reward(&alice, &alice, &1u32);
+        s.client.revoke_accrual(&bob, &1u32, &40i128);
+        assert_eq!(s.client.recipient_count(&1u32), 3);
+
+        // Revoking a never-claimed balance to zero removes the recipient.
+        s.client.revoke_accrual(&carol, &1u32, &10i128);
+        assert_eq!(s.client.recipient_count(&1u32), 2);
+        s.client.accrue_reward(&alice, &1u32, &10i128);
+        assert_eq!(s.client.recipient_count(&1u32), 2);
+    }
 }
@@ -7,7 +7,7 @@ use soroban_sdk::{contracterror, contracttype, Address};
 // ─── Common Error Codes ───────────────────────────────────────────────────────
 
 /// Common error codes used across all contracts.
-#[contracttype]
+#[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 #[repr(u32)]
 pub enum Error {
@@ -26,6 +26,8 @@ pub enum Error {
     Overflow = 5,
     InsufficientBalance = 6,
     ClaimCooldownActive = 7,
+    StakeLocked = 8,
+    InvalidLockPeriod = 9,
 }
 
 // ---------------------------------------------------------------------------
@@ -40,6 +42,15 @@ pub enum DataKey {
     RewardToken,
     GlobalState,
     Position(Address),
+    LockedUntil(Address),
+    /// Truncated remainder of the last accumulator division, carried into
+    /// the next update's numerator. Kept apart from `GlobalState` so that
+    /// struct's stored shape is unchanged; absent means 0.
+    RewardRemainder,
+    /// The `n`th staked-amount checkpoint of a user, oldest first.
+    StakeCheckpoint(Address, u32),
+    /// Number of staked-amount checkpoints written for a user.
+    StakeCheckpointCount(Address),
 }
 
 // ---------------------------------------------------------------------------
@@ -64,6 +75,14 @@ pub struct UserPosition {
     pub last_claim_timestamp: u64,
 }
 
+/// A user's staked amount as of the end of `ledger`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeCheckpoint {
+    pub ledger: u32,
+    pub amount: i128,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RewardPreview {
@@ -101,6 +120,13 @@ pub struct Unstaked {
     pub amount: i128,
 }
 
+#[contractevent]
+pub struct LockExtended {
+    #[topic]
+    pub user: Address,
+    pub unlock_at: u64,
+}
+
 #[contractevent]
 pub struct RewardsClaimed {
     #[topic]
@@ -205,9 +231,10 @@ impl Staking {
             .get(&DataKey::StakingToken)
             .unwrap();
         let token_client = token::Client::new(&env, &staking_token);
-        token_client.transfer(&user, &env.current_contract_address(), &amount);
+        token_client.transfer(&user, env.current_contract_address(), &amount);
 
         // Update position and state
+        Self::record_stake(&env, &user, position.amount, position.amount + amount);
         position.amount += amount;
         position.reward_debt = position.amount * state.reward_per_share_acc / PRECISION;
         state.total_staked += amount;
@@ -240,6 +267,11 @@ impl Staking {
             return Err(Error::InvalidAmount);
         }
 
+        let (locked, _) = Self::lock_status(env.clone(), user.clone());
+        if locked {
+            return Err(Error::StakeLocked);
+        }
+
         Self::update_pool(&env)?;
 
         let mut state: GlobalState = env.storage().instance().get(&DataKey::GlobalState).unwrap();
@@ -250,6 +282,7 @@ impl Staking {
         position.pending_rewards += pending;
 
         // Update position and state
+        Self::record_stake(&env, &user, position.amount, position.amount - amount);
         position.amount -= amount;
         position.reward_debt = if position.amount > 0 {
             position.amount * state.reward_per_share_acc / PRECISION
@@ -281,6 +314,76 @@ impl Staking {
         Ok(())
     }
 
+    /// Lock the caller's stake until `unlock_at`; `unstake` is rejected
+    /// until then. A lock can only be extended, never shortened.
+    pub fn lock_stake(env: Env, user: Address, unlock_at: u64) -> Result<(), Error> {
+        user.require_auth();
+
+        let position = Self::position_or_default(&env, user.clone());
+        if position.amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let key = DataKey::LockedUntil(user.clone());
+        let current: u64 = env.storage().persistent().get(&key).unwrap_or(0);
+        if unlock_at <= env.ledger().timestamp() || unlock_at <= current {
+            return Err(Error::InvalidLockPeriod);
+        }
+
+        env.storage().persistent().set(&key, &unlock_at);
+
+        LockExtended { user, unlock_at }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Whether `user`'s stake is locked and the timestamp it unlocks at.
+    /// Returns `(false, 0)` for unlocked positions. Intended for
+    /// cross-contract reads (e.g. governance vote eligibility).
+    pub fn lock_status(env: Env, user: Address) -> (bool, u64) {
+        let unlock_at: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LockedUntil(user))
+            .unwrap_or(0);
+        if unlock_at > env.ledger().timestamp() {
+            (true, unlock_at)
+        } else {
+            (false, 0)
+        }
+    }
+
+    /// Amount `user` had staked at the end of `ledger`, for vote-weight
+    /// snapshots. The current ledger's amount can still change, so callers
+    /// should ask about a ledger that has already closed.
+    pub fn staked_at(env: Env, user: Address, ledger: u32) -> i128 {
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::StakeCheckpointCount(user.clone()))
+            .unwrap_or(0);
+        if count == 0 {
+            // Never changed since checkpointing began
+            return Self::position_or_default(&env, user).amount;
+        }
+
+        // Find the first checkpoint written after `ledger`; the one before it
+        // holds the amount at `ledger`.
+        let (mut low, mut high) = (0u32, count);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if Self::stake_checkpoint(&env, &user, mid).ledger <= ledger {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        if low == 0 {
+            return 0;
+        }
+        Self::stake_checkpoint(&env, &user, low - 1).amount
+    }
+
     /// Claim accrued rewards.
     pub fn claim_rewards(env: Env, user: Address) -> Result<i128, Error> {
         user.require_auth();
@@ -351,12 +454,8 @@ impl Staking {
             .instance()
             .get::<_, GlobalState>(&DataKey::GlobalState)
         {
-            let timestamp = env.ledger().timestamp();
-            if timestamp > state.last_update_timestamp && state.total_staked > 0 {
-                let duration = (timestamp - state.last_update_timestamp) as i128;
-                let rewards = duration * state.reward_rate;
-                state.reward_per_share_acc += rewards * PRECISION / state.total_staked;
-            }
+            let mut remainder = Self::reward_remainder(&env);
+            Self::accrue(&mut state, &mut remainder, env.ledger().timestamp());
             let pending =
                 (position.amount * state.reward_per_share_acc / PRECISION) - position.reward_debt;
             position.pending_rewards += pending;
@@ -410,23 +509,36 @@ impl Staking {
             return Ok(());
         }
 
+        let mut remainder = Self::reward_remainder(env);
+        Self::accrue(&mut state, &mut remainder, timestamp);
+        env.storage().instance().set(&DataKey::GlobalState, &state);
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardRemainder, &remainder);
+
+        Ok(())
+    }
+
+    /// Advance the reward accumulator to `timestamp`.
+    ///
+    /// The division by `total_staked` truncates, so the remainder is kept in
+    /// `remainder` and added to the next numerator. Over many updates the
+    /// accumulator therefore tracks the exact `rate * elapsed` integral
+    /// instead of drifting below it.
+    fn accrue(state: &mut GlobalState, remainder: &mut i128, timestamp: u64) {
+        if timestamp <= state.last_update_timestamp {
+            return;
+        }
+
         if state.total_staked > 0 {
             let duration = (timestamp - state.last_update_timestamp) as i128;
             let rewards = duration * state.reward_rate;
-            state.reward_per_share_acc += rewards * PRECISION / state.total_staked;
+            let numerator = rewards * PRECISION + *remainder;
+            state.reward_per_share_acc += numerator / state.total_staked;
+            *remainder = numerator % state.total_staked;
         }
 
         state.last_update_timestamp = timestamp;
-        env.storage().instance().set(&DataKey::GlobalState, &state);
-
-        Ok(())
-    }
-
-    fn require_initialized(env: &Env) -> Result<Address, Error> {
-        env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .ok_or(Error::NotInitialized)
     }
 
     fn state_at_current_ledger(env: &Env) -> Result<GlobalState, Error> {
@@ -435,18 +547,60 @@ impl Staking {
             .instance()
             .get(&DataKey::GlobalState)
             .ok_or(Error::NotInitialized)?;
-        let timestamp = env.ledger().timestamp();
-
-        if timestamp > state.last_update_timestamp && state.total_staked > 0 {
-            let duration = (timestamp - state.last_update_timestamp) as i128;
-            let rewards = duration * state.reward_rate;
-            state.reward_per_share_acc += rewards * PRECISION / state.total_staked;
-            state.last_update_timestamp = timestamp;
+        if state.total_staked > 0 {
+            let mut remainder = Self::reward_remainder(env);
+            Self::accrue(&mut state, &mut remainder, env.ledger().timestamp());
         }
 
         Ok(state)
     }
 
+    fn reward_remainder(env: &Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RewardRemainder)
+            .unwrap_or(0)
+    }
+
+    /// Checkpoint `user`'s staked amount at the current ledger. Several
+    /// changes within one ledger share a single checkpoint.
+    fn record_stake(env: &Env, user: &Address, previous: i128, amount: i128) {
+        let ledger = env.ledger().sequence();
+        let count_key = DataKey::StakeCheckpointCount(user.clone());
+        let mut count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        if count == 0 && previous != 0 {
+            // A stake held before checkpointing began counts as held since ledger 0
+            env.storage().persistent().set(
+                &DataKey::StakeCheckpoint(user.clone(), 0),
+                &StakeCheckpoint {
+                    ledger: 0,
+                    amount: previous,
+                },
+            );
+            count = 1;
+        }
+
+        // Overwrite this ledger's checkpoint if there is one, else append
+        let mut index = count;
+        if count > 0 && Self::stake_checkpoint(env, user, count - 1).ledger == ledger {
+            index = count - 1;
+        } else {
+            count += 1;
+        }
+        env.storage().persistent().set(
+            &DataKey::StakeCheckpoint(user.clone(), index),
+            &StakeCheckpoint { ledger, amount },
+        );
+        env.storage().persistent().set(&count_key, &count);
+    }
+
+    fn stake_checkpoint(env: &Env, user: &Address, index: u32) -> StakeCheckpoint {
+        env.storage()
+            .persistent()
+            .get(&DataKey::StakeCheckpoint(user.clone(), index))
+            .unwrap()
+    }
+
     fn position_or_default(env: &Env, user: Address) -> UserPosition {
         env.storage()
             .persistent()
@@ -481,9 +635,7 @@ impl Staking {
             };
         }
 
-        let next_claim_timestamp = last_claim_timestamp
-            .checked_add(CLAIM_COOLDOWN_SECONDS)
-            .unwrap_or(u64::MAX);
+        let next_claim_timestamp = last_claim_timestamp.saturating_add(CLAIM_COOLDOWN_SECONDS);
         if now >= next_claim_timestamp {
             ClaimEligibility {
                 eligible_now: true,
@@ -534,8 +686,12 @@ mod test {
         let user1 = Address::generate(&env);
         let user2 = Address::generate(&env);
 
-        let staking_token_addr = env.register_stellar_asset_contract(Address::generate(&env));
-        let reward_token_addr = env.register_stellar_asset_contract(Address::generate(&env));
+        let staking_token_addr = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
+        let reward_token_addr = env
+            .register_stellar_asset_contract_v2(Address::generate(&env))
+            .address();
 
         let staking_token = token::StellarAssetClient::new(&env, &staking_token_addr);
         let reward_token = token::StellarAssetClient::new(&env, &reward_token_addr);
@@ -679,6 +835,45 @@ mod test {
         assert_eq!(pos2.pending_rewards, 500i128);
     }
 
+    #[test]
+    fn test_rounding_remainder_carries_forward() {
+        let s = setup();
+        // A pool larger than PRECISION makes `rate * PRECISION / total_staked`
+        // truncate to zero on every one-second update without carry-forward.
+        let large_stake = 2 * PRECISION;
+        let rate = 1i128;
+        let intervals = 100u64;
+
+        s.client.set_reward_rate(&s.admin, &rate);
+        s.staking_token.mint(&s.user1, &large_stake);
+        s.client.stake(&s.user1, &large_stake);
+
+        s.staking_token.mint(&s.user2, &(intervals as i128));
+        for _ in 0..intervals {
+            s.env.ledger().set_timestamp(s.env.ledger().timestamp() + 1);
+            s.client.stake(&s.user2, &1i128);
+        }
+
+        let total = s.client.position_of(&s.user1).pending_rewards
+            + s.client.position_of(&s.user2).pending_rewards;
+        let expected = rate * intervals as i128;
+        // Still-undistributed remainder is worth under total_staked / PRECISION
+        // (2 units); each user's final floor division may lose one more.
+        assert!(total <= expected);
+        assert!(expected - total <= 4, "total rewards {total} drifted from {expected}");
+
+        // The carry lives under its own key, leaving `GlobalState` unchanged
+        s.env.as_contract(&s.client.address, || {
+            let remainder: i128 = s
+                .env
+                .storage()
+                .instance()
+                .get(&DataKey::RewardRemainder)
+                .unwrap();
+            assert!(remainder > 0);
+        });
+    }
+
     #[test]
     #[should_panic(expected = "HostError: Error(Contract, #4)")]
     fn test_unstake_excessive_amount() {
@@ -688,6 +883,60 @@ mod test {
         s.client.unstake(&s.user1, &101i128);
     }
 
+    #[test]
+    fn test_lock_status_and_unstake_enforcement() {
+        let s = setup();
+        s.staking_token.mint(&s.user1, &500i128);
+        s.client.stake(&s.user1, &500i128);
+
+        assert_eq!(s.client.lock_status(&s.user1), (false, 0));
+
+        let unlock_at = s.env.ledger().timestamp() + 1_000;
+        s.client.lock_stake(&s.user1, &unlock_at);
+        assert_eq!(s.client.lock_status(&s.user1), (true, unlock_at));
+
+        let result = s.client.try_unstake(&s.user1, &100i128);
+        assert_eq!(result, Err(Ok(Error::StakeLocked)));
+
+        // Locks cannot be shortened
+        let result = s.client.try_lock_stake(&s.user1, &(unlock_at - 1));
+        assert_eq!(result, Err(Ok(Error::InvalidLockPeriod)));
+
+        s.env.ledger().set_timestamp(unlock_at);
+        assert_eq!(s.client.lock_status(&s.user1), (false, 0));
+        s.client.unstake(&s.user1, &500i128);
+    }
+
+    #[test]
+    fn test_staked_at_reads_past_ledgers() {
+        let s = setup();
+        s.staking_token.mint(&s.user1, &1_000i128);
+
+        s.env.ledger().set_sequence_number(10);
+        s.client.stake(&s.user1, &600i128);
+        s.env.ledger().set_sequence_number(20);
+        s.client.stake(&s.user1, &400i128);
+        // Same-ledger changes collapse into one checkpoint
+        s.client.unstake(&s.user1, &300i128);
+        s.env.ledger().set_sequence_number(30);
+        s.client.unstake(&s.user1, &700i128);
+
+        assert_eq!(s.client.staked_at(&s.user1, &9), 0);
+        assert_eq!(s.client.staked_at(&s.user1, &10), 600);
+        assert_eq!(s.client.staked_at(&s.user1, &19), 600);
+        assert_eq!(s.client.staked_at(&s.user1, &20), 700);
+        assert_eq!(s.client.staked_at(&s.user1, &29), 700);
+        assert_eq!(s.client.staked_at(&s.user1, &30), 0);
+        assert_eq!(s.client.staked_at(&s.user2, &30), 0);
+    }
+
+    #[test]
+    fn test_lock_without_stake_fails() {
+        let s = setup();
+        let result = s.client.try_lock_stake(&s.user2, &1_000u64);
+        assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+    }
+
     #[test]
     fn test_preview_rewards_for_missing_staker() {
         let s = setup();
@@ -2,10 +2,16 @@
 #![allow(unexpected_cfgs)]
 
 use soroban_sdk::{
-    contract, contracterror, contractevent, contractimpl, contracttype, vec, Address, BytesN,
-    Env, IntoVal, Symbol,
+    contract, contracterror, contractevent, contractimpl, contracttype, symbol_short, vec, Address,
+    BytesN, Env, IntoVal, Symbol, Vec,
 };
 
+/// Seconds after staging at which `health` reports a pending upgrade as expired.
+const PENDING_UPGRADE_MAX_AGE: u64 = 7 * 24 * 60 * 60;
+
+/// Number of superseded releases kept on the rollback stack.
+const MAX_ROLLBACK_POINTS: u32 = 5;
+
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -22,6 +28,15 @@ pub enum Error {
     NotPaused = 10,
     ContractKilled = 11,
     TestGateFailed = 12,
+    AlreadyApproved = 13,
+    ApprovalsPending = 14,
+    TimelockNotElapsed = 15,
+    MigrationNotConfirmed = 16,
+    MigrationAlreadyConfirmed = 17,
+    GuardianAuthRequired = 18,
+    TestGateNotFound = 19,
+    IdenticalWasm = 20,
+    UpgradeNotApproved = 21,
 }
 
 #[contracttype]
@@ -33,6 +48,10 @@ pub struct ReleaseRecord {
     pub migration_hash: BytesN<32>,
     pub changelog_hash: BytesN<32>,
     pub activated_at: u64,
+    /// Set when the release skipped schema versions via `force_schema_jump`
+    pub forced_schema_jump: bool,
+    /// Admin who initialized the contract or executed this release
+    pub actor: Address,
 }
 
 #[contracttype]
@@ -44,6 +63,10 @@ pub struct PendingUpgrade {
     pub migration_hash: BytesN<32>,
     pub changelog_hash: BytesN<32>,
     pub staged_at: u64,
+    /// `staged_at` plus the activation delay in force when staged; later
+    /// `set_activation_delay` calls do not move it
+    pub unlocks_at: u64,
+    pub forced_schema_jump: bool,
 }
 
 #[contracttype]
@@ -52,13 +75,29 @@ pub struct TestGateConfig {
     pub enabled: bool,
     pub gate_contract: Address,
     pub suite: Symbol,
+    /// Oldest acceptable `last_successful_run_age` result, in seconds;
+    /// 0 = no check
+    pub gate_max_age: u64,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct UpgradeState {
     pub admin: Address,
+    /// Address proposed via `propose_admin` that has not accepted yet
+    pub pending_admin: Option<Address>,
+    /// Co-signer required by `trigger_kill_switch`
+    pub kill_switch_guardian: Address,
+    /// Signs off on each staged upgrade via `approve_upgrade`
+    pub approver: Address,
+    /// Whether the approver has signed off on the staged upgrade
+    pub pending_approved: bool,
+    /// Whether a pause is currently in effect (an expired pause reads false)
     pub paused: bool,
+    /// Ledger timestamp of the last `pause`; 0 once unpaused
+    pub paused_at: u64,
+    /// Timestamp after which the pause lapses on its own; 0 = indefinite
+    pub pause_expiry: u64,
     pub killed: bool,
     pub current_version: u32,
     pub current_schema_version: u32,
@@ -66,29 +105,54 @@ pub struct UpgradeState {
     pub has_pending_upgrade: bool,
     pub pending_version: u32,
     pub has_rollback_point: bool,
+    /// Version `rollback` would restore (top of the rollback stack)
     pub rollback_version: u32,
-    pub has_test_gate: bool,
+    /// Number of registered test gates, enabled or not
+    pub test_gate_count: u32,
+    /// Whether at least one registered gate is enabled
     pub test_gate_enabled: bool,
+    /// Whether the current release's migration has been confirmed
+    pub migration_confirmed: bool,
+    /// Seconds a staged upgrade must wait before it can execute
+    pub activation_delay: u64,
+    /// Earliest execution time of the staged upgrade; 0 if nothing is staged
+    pub activation_unlocks_at: u64,
 }
 
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
     Admin,
+    PendingAdmin,
+    KillSwitchGuardian,
+    Approver,
+    /// Version of the staged upgrade the approver signed off on
+    UpgradeApproved,
     Paused,
+    PausedAt,
+    PauseExpiry,
     Killed,
     CurrentVersion,
     CurrentSchemaVersion,
     CurrentWasmHash,
     PendingUpgrade,
-    RollbackPoint,
-    TestGate,
+    RollbackStack,
+    TestGate(Symbol),
+    TestGateSuites,
     Release(u32),
+    Guardians,
+    ApprovalThreshold,
+    Approvals(u32),
+    ActivationDelay,
+    MigrationConfirmed(u32),
+    /// Every executed version in ascending order; unaffected by rollbacks
+    ReleaseVersions,
 }
 
 #[contractevent]
 pub struct Initialized {
     pub admin: Address,
+    pub guardian: Address,
     pub version: u32,
     pub schema_version: u32,
     pub wasm_hash: BytesN<32>,
@@ -109,21 +173,53 @@ pub struct UpgradeExecuted {
     pub activated_at: u64,
 }
 
+#[contractevent]
+pub struct UpgradeCancelled {
+    pub version: u32,
+}
+
+#[contractevent]
+pub struct MigrationConfirmed {
+    pub version: u32,
+    pub result_hash: BytesN<32>,
+}
+
 #[contractevent]
 pub struct RollbackExecuted {
+    #[topic]
+    pub admin: Address,
     pub from_version: u32,
     pub to_version: u32,
     pub reason_hash: BytesN<32>,
+    /// Whether the restored release has a lower schema version
+    pub schema_downgraded: bool,
+}
+
+#[contractevent]
+pub struct AdminProposed {
+    #[topic]
+    pub admin: Address,
+    pub proposed: Address,
+}
+
+#[contractevent]
+pub struct AdminTransferred {
+    #[topic]
+    pub previous_admin: Address,
+    pub new_admin: Address,
 }
 
 #[contractevent]
 pub struct PauseChanged {
     pub paused: bool,
     pub admin: Address,
+    /// When the pause lapses on its own; 0 = indefinite or unpaused
+    pub expires_at: u64,
 }
 
 #[contractevent]
 pub struct KillSwitchTriggered {
+    #[topic]
     pub admin: Address,
     pub reason_hash: BytesN<32>,
 }
@@ -133,6 +229,39 @@ pub struct TestGateConfigured {
     pub enabled: bool,
     pub gate_contract: Address,
     pub suite: Symbol,
+    pub gate_max_age: u64,
+}
+
+#[contractevent]
+pub struct TestGateRemoved {
+    pub suite: Symbol,
+}
+
+#[contractevent]
+pub struct GuardiansConfigured {
+    pub guardian_count: u32,
+    pub threshold: u32,
+}
+
+#[contractevent]
+pub struct UpgradeApprovedByGuardian {
+    #[topic]
+    pub guardian: Address,
+    pub version: u32,
+    pub approvals: u32,
+}
+
+#[contractevent]
+pub struct UpgradeSignedOff {
+    #[topic]
+    pub approver: Address,
+    pub version: u32,
+}
+
+#[contractevent]
+pub struct ActivationDelayChanged {
+    pub delay: u64,
+    pub admin: Address,
 }
 
 #[contract]
@@ -140,16 +269,21 @@ pub struct UpgradeMechanism;
 
 #[contractimpl]
 impl UpgradeMechanism {
+    /// Initialize the contract. `guardian` must co-sign
+    /// `trigger_kill_switch` and `approver` must sign off on every staged
+    /// upgrade; neither may be the admin.
     pub fn init(
         env: Env,
         admin: Address,
+        guardian: Address,
+        approver: Address,
         initial_wasm_hash: BytesN<32>,
         initial_schema_version: u32,
     ) -> Result<(), Error> {
         if env.storage().instance().has(&DataKey::Admin) {
             return Err(Error::AlreadyInitialized);
         }
-        if initial_schema_version == 0 {
+        if initial_schema_version == 0 || guardian == admin || approver == admin {
             return Err(Error::InvalidInput);
         }
 
@@ -163,9 +297,15 @@ impl UpgradeMechanism {
             migration_hash: initial_wasm_hash.clone(),
             changelog_hash: initial_wasm_hash.clone(),
             activated_at: env.ledger().timestamp(),
+            forced_schema_jump: false,
+            actor: admin.clone(),
         };
 
         env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::KillSwitchGuardian, &guardian);
+        env.storage().instance().set(&DataKey::Approver, &approver);
         env.storage().instance().set(&DataKey::Paused, &false);
         env.storage().instance().set(&DataKey::Killed, &false);
         env.storage()
@@ -180,9 +320,14 @@ impl UpgradeMechanism {
         env.storage()
             .persistent()
             .set(&DataKey::Release(initial_version), &record);
+        record_release_version(&env, initial_version);
+        env.storage()
+            .persistent()
+            .set(&DataKey::MigrationConfirmed(initial_version), &true);
 
         Initialized {
             admin,
+            guardian,
             version: initial_version,
             schema_version: initial_schema_version,
             wasm_hash: initial_wasm_hash,
@@ -192,7 +337,60 @@ impl UpgradeMechanism {
         Ok(())
     }
 
-    pub fn pause(env: Env, admin: Address) -> Result<(), Error> {
+    /// First step of an admin rotation: nominate `new_admin`, who must then
+    /// call `accept_admin`. A later proposal replaces an earlier one.
+    pub fn propose_admin(env: Env, admin: Address, new_admin: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        require_not_killed(&env)?;
+
+        // The kill switch and upgrade sign-off need signers distinct from
+        // the admin.
+        let guardian: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::KillSwitchGuardian)
+            .ok_or(Error::NotInitialized)?;
+        if new_admin == admin || new_admin == guardian || new_admin == get_approver(&env)? {
+            return Err(Error::InvalidInput);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::PendingAdmin, &new_admin);
+        AdminProposed {
+            admin,
+            proposed: new_admin,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Second step of an admin rotation; must be authorized by the proposed
+    /// address itself.
+    pub fn accept_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        require_initialized(&env)?;
+        new_admin.require_auth();
+
+        let pending: Option<Address> = env.storage().instance().get(&DataKey::PendingAdmin);
+        if pending != Some(new_admin.clone()) {
+            return Err(Error::NotAuthorized);
+        }
+
+        let previous_admin = get_admin(&env)?;
+        env.storage().instance().set(&DataKey::Admin, &new_admin);
+        env.storage().instance().remove(&DataKey::PendingAdmin);
+        AdminTransferred {
+            previous_admin,
+            new_admin,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Pause upgrade and rollback mutations. A non-zero `max_duration`
+    /// makes the pause lapse on its own once `max_duration` seconds have
+    /// passed; zero pauses until `unpause` is called.
+    pub fn pause(env: Env, admin: Address, max_duration: u64) -> Result<(), Error> {
         require_admin(&env, &admin)?;
         require_not_killed(&env)?;
 
@@ -200,8 +398,23 @@ impl UpgradeMechanism {
             return Err(Error::AlreadyPaused);
         }
 
+        let now = env.ledger().timestamp();
+        let expires_at = if max_duration == 0 {
+            0
+        } else {
+            now.saturating_add(max_duration)
+        };
         env.storage().instance().set(&DataKey::Paused, &true);
-        PauseChanged { paused: true, admin }.publish(&env);
+        env.storage().instance().set(&DataKey::PausedAt, &now);
+        env.storage()
+            .instance()
+            .set(&DataKey::PauseExpiry, &expires_at);
+        PauseChanged {
+            paused: true,
+            admin,
+            expires_at,
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -213,35 +426,56 @@ impl UpgradeMechanism {
             return Err(Error::NotPaused);
         }
 
-        env.storage().instance().set(&DataKey::Paused, &false);
+        clear_pause(&env);
         PauseChanged {
             paused: false,
             admin,
+            expires_at: 0,
         }
         .publish(&env);
         Ok(())
     }
 
+    /// Irreversibly freeze the contract. Requires both the admin's and the
+    /// kill-switch guardian's authorization.
     pub fn trigger_kill_switch(
         env: Env,
         admin: Address,
+        guardian: Address,
         reason_hash: BytesN<32>,
     ) -> Result<(), Error> {
         require_admin(&env, &admin)?;
 
+        let expected_guardian: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::KillSwitchGuardian)
+            .ok_or(Error::NotInitialized)?;
+        if guardian != expected_guardian {
+            return Err(Error::GuardianAuthRequired);
+        }
+        guardian.require_auth();
+
         env.storage().instance().set(&DataKey::Killed, &true);
         env.storage().instance().set(&DataKey::Paused, &true);
+        // A kill switch pause never lapses.
+        env.storage().instance().set(&DataKey::PauseExpiry, &0u64);
 
         KillSwitchTriggered { admin, reason_hash }.publish(&env);
         Ok(())
     }
 
+    /// Register or update the gate for `suite`. Every enabled gate must
+    /// report ready before `execute_upgrade` proceeds. With a non-zero
+    /// `gate_max_age`, the gate's `last_successful_run_age(suite)` must also
+    /// be at most that many seconds; a gate with no successful run is stale.
     pub fn configure_test_gate(
         env: Env,
         admin: Address,
         gate_contract: Address,
         suite: Symbol,
         enabled: bool,
+        gate_max_age: u64,
     ) -> Result<(), Error> {
         require_admin(&env, &admin)?;
         require_not_killed(&env)?;
@@ -250,18 +484,159 @@ impl UpgradeMechanism {
             enabled,
             gate_contract: gate_contract.clone(),
             suite: suite.clone(),
+            gate_max_age,
         };
 
-        env.storage().instance().set(&DataKey::TestGate, &cfg);
+        let mut suites = get_test_gate_suites(&env);
+        if !suites.contains(&suite) {
+            suites.push_back(suite.clone());
+            env.storage()
+                .instance()
+                .set(&DataKey::TestGateSuites, &suites);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::TestGate(suite.clone()), &cfg);
         TestGateConfigured {
             enabled,
             gate_contract,
             suite,
+            gate_max_age,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn remove_test_gate(env: Env, admin: Address, suite: Symbol) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        require_not_killed(&env)?;
+
+        let mut suites = get_test_gate_suites(&env);
+        let index = suites
+            .first_index_of(&suite)
+            .ok_or(Error::TestGateNotFound)?;
+        suites.remove(index);
+        env.storage()
+            .instance()
+            .set(&DataKey::TestGateSuites, &suites);
+        env.storage()
+            .instance()
+            .remove(&DataKey::TestGate(suite.clone()));
+
+        TestGateRemoved { suite }.publish(&env);
+        Ok(())
+    }
+
+    /// Set the guardians whose sign-off is required before `execute_upgrade`.
+    /// A `threshold` of zero disables the approval requirement.
+    pub fn configure_guardians(
+        env: Env,
+        admin: Address,
+        guardians: Vec<Address>,
+        threshold: u32,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        require_not_killed(&env)?;
+
+        if threshold > guardians.len() {
+            return Err(Error::InvalidInput);
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Guardians, &guardians);
+        env.storage()
+            .instance()
+            .set(&DataKey::ApprovalThreshold, &threshold);
+
+        GuardiansConfigured {
+            guardian_count: guardians.len(),
+            threshold,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Record a guardian's approval of the currently staged upgrade.
+    pub fn guardian_approve(env: Env, guardian: Address) -> Result<(), Error> {
+        require_ready_for_mutation(&env)?;
+        guardian.require_auth();
+
+        if !get_guardians(&env).contains(&guardian) {
+            return Err(Error::NotAuthorized);
+        }
+
+        let pending = get_pending_upgrade(&env)?;
+        let mut approvals = get_approvals(&env, pending.version);
+        if approvals.contains(&guardian) {
+            return Err(Error::AlreadyApproved);
+        }
+        approvals.push_back(guardian.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Approvals(pending.version), &approvals);
+
+        UpgradeApprovedByGuardian {
+            guardian,
+            version: pending.version,
+            approvals: approvals.len(),
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Approver sign-off on the staged upgrade, required by
+    /// `execute_upgrade`. Cleared when the upgrade executes, is cancelled or
+    /// is discarded by a rollback.
+    pub fn approve_upgrade(env: Env, approver: Address) -> Result<(), Error> {
+        require_ready_for_mutation(&env)?;
+        approver.require_auth();
+
+        if approver != get_approver(&env)? {
+            return Err(Error::NotAuthorized);
+        }
+
+        let pending = get_pending_upgrade(&env)?;
+        if is_upgrade_approved(&env, pending.version) {
+            return Err(Error::AlreadyApproved);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::UpgradeApproved, &pending.version);
+
+        UpgradeSignedOff {
+            approver,
+            version: pending.version,
         }
         .publish(&env);
         Ok(())
     }
 
+    /// Set how many seconds a staged upgrade must wait before
+    /// `execute_upgrade` will activate it. Zero (the default) disables the
+    /// delay. Only upgrades staged afterwards are affected; a staged
+    /// upgrade keeps the unlock time fixed when it was staged.
+    pub fn set_activation_delay(env: Env, admin: Address, delay: u64) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        require_not_killed(&env)?;
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ActivationDelay, &delay);
+        ActivationDelayChanged { delay, admin }.publish(&env);
+        Ok(())
+    }
+
+    /// Stage an upgrade candidate.
+    ///
+    /// `target_schema_version` must equal the current schema or current + 1.
+    /// With `force_schema_jump` set, any schema above the current one is
+    /// accepted instead, for consolidations whose combined migrations ran
+    /// off-chain; the jump is recorded in the resulting `ReleaseRecord`.
+    ///
+    /// `wasm_hash` must differ from the deployed one unless `force_same_wasm`
+    /// is set, for state-only migrations that redeploy the same bytecode.
+    #[allow(clippy::too_many_arguments)]
     pub fn stage_upgrade(
         env: Env,
         admin: Address,
@@ -270,6 +645,8 @@ impl UpgradeMechanism {
         target_schema_version: u32,
         migration_hash: BytesN<32>,
         changelog_hash: BytesN<32>,
+        force_schema_jump: bool,
+        force_same_wasm: bool,
     ) -> Result<(), Error> {
         require_admin(&env, &admin)?;
         require_ready_for_mutation(&env)?;
@@ -284,13 +661,29 @@ impl UpgradeMechanism {
         if version <= current_version {
             return Err(Error::InvalidStateTransition);
         }
+        if !is_migration_confirmed(&env, current_version) {
+            return Err(Error::MigrationNotConfirmed);
+        }
 
-        if target_schema_version != current_schema_version
-            && target_schema_version != current_schema_version + 1
-        {
+        let schema_allowed = if force_schema_jump {
+            target_schema_version > current_schema_version
+        } else {
+            target_schema_version == current_schema_version
+                || target_schema_version == current_schema_version + 1
+        };
+        if !schema_allowed {
             return Err(Error::InvalidStateTransition);
         }
 
+        let current_wasm_hash: BytesN<32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::CurrentWasmHash)
+            .ok_or(Error::NotInitialized)?;
+        if wasm_hash == current_wasm_hash && !force_same_wasm {
+            return Err(Error::IdenticalWasm);
+        }
+
         if env.storage().persistent().has(&DataKey::Release(version)) {
             return Err(Error::DuplicateVersion);
         }
@@ -299,13 +692,16 @@ impl UpgradeMechanism {
             return Err(Error::InvalidStateTransition);
         }
 
+        let staged_at = env.ledger().timestamp();
         let pending = PendingUpgrade {
             version,
             wasm_hash,
             target_schema_version,
             migration_hash,
             changelog_hash,
-            staged_at: env.ledger().timestamp(),
+            staged_at,
+            unlocks_at: staged_at.saturating_add(get_activation_delay(&env)),
+            forced_schema_jump: force_schema_jump,
         };
 
         env.storage().instance().set(&DataKey::PendingUpgrade, &pending);
@@ -326,17 +722,20 @@ impl UpgradeMechanism {
 
         let pending = get_pending_upgrade(&env)?;
 
-        if let Some(cfg) = get_test_gate(&env) {
-            if cfg.enabled {
-                let ready = env.invoke_contract::<bool>(
-                    &cfg.gate_contract,
-                    &Symbol::new(&env, "is_release_ready"),
-                    vec![&env, cfg.suite.clone().into_val(&env)],
-                );
-                if !ready {
-                    return Err(Error::TestGateFailed);
-                }
-            }
+        if remaining_approvals(&env, pending.version) > 0 {
+            return Err(Error::ApprovalsPending);
+        }
+
+        if !is_upgrade_approved(&env, pending.version) {
+            return Err(Error::UpgradeNotApproved);
+        }
+
+        if env.ledger().timestamp() < pending.unlocks_at {
+            return Err(Error::TimelockNotElapsed);
+        }
+
+        if !test_gates_ready(&env) {
+            return Err(Error::TestGateFailed);
         }
 
         let previous = current_release(&env)?;
@@ -349,9 +748,18 @@ impl UpgradeMechanism {
             migration_hash: pending.migration_hash,
             changelog_hash: pending.changelog_hash,
             activated_at,
+            forced_schema_jump: pending.forced_schema_jump,
+            actor: admin,
         };
 
-        env.storage().instance().set(&DataKey::RollbackPoint, &previous);
+        let mut stack = get_rollback_stack(&env);
+        stack.push_back(previous.clone());
+        while stack.len() > MAX_ROLLBACK_POINTS {
+            stack.pop_front();
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::RollbackStack, &stack);
         env.storage()
             .instance()
             .set(&DataKey::CurrentVersion, &new_release.version);
@@ -364,7 +772,14 @@ impl UpgradeMechanism {
         env.storage()
             .persistent()
             .set(&DataKey::Release(new_release.version), &new_release);
+        record_release_version(&env, new_release.version);
         env.storage().instance().remove(&DataKey::PendingUpgrade);
+        env.storage().instance().remove(&DataKey::UpgradeApproved);
+
+        // Swap the contract's code last: if the Wasm is missing or the update
+        // fails, the whole invocation reverts, including the version bump.
+        env.deployer()
+            .update_current_contract_wasm(new_release.wasm_hash.clone());
 
         UpgradeExecuted {
             from_version: previous.version,
@@ -377,54 +792,146 @@ impl UpgradeMechanism {
         Ok(())
     }
 
-    pub fn rollback(env: Env, admin: Address, reason_hash: BytesN<32>) -> Result<(), Error> {
+    /// Discard the staged upgrade so a corrected one can be staged.
+    /// Guardian approvals collected for it are discarded as well.
+    pub fn cancel_upgrade(env: Env, admin: Address) -> Result<(), Error> {
         require_admin(&env, &admin)?;
         require_not_killed(&env)?;
 
-        let rollback_point: ReleaseRecord = env
-            .storage()
-            .instance()
-            .get(&DataKey::RollbackPoint)
-            .ok_or(Error::InvalidStateTransition)?;
+        let pending = get_pending_upgrade(&env)?;
 
-        let current_version = get_u32(&env, &DataKey::CurrentVersion)?;
-        if current_version == rollback_point.version {
+        env.storage().instance().remove(&DataKey::PendingUpgrade);
+        env.storage().instance().remove(&DataKey::UpgradeApproved);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Approvals(pending.version));
+
+        UpgradeCancelled {
+            version: pending.version,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Confirm that the off-chain migration for the current release ran.
+    /// `result_hash` references the migration's output and is emitted in
+    /// `MigrationConfirmed` for auditing. Until this is called, no further
+    /// upgrade can be staged.
+    pub fn confirm_migration(
+        env: Env,
+        admin: Address,
+        version: u32,
+        result_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        require_ready_for_mutation(&env)?;
+
+        if version != get_u32(&env, &DataKey::CurrentVersion)? {
             return Err(Error::InvalidStateTransition);
         }
+        if is_migration_confirmed(&env, version) {
+            return Err(Error::MigrationAlreadyConfirmed);
+        }
 
         env.storage()
-            .instance()
-            .set(&DataKey::CurrentVersion, &rollback_point.version);
-        env.storage().instance().set(
-            &DataKey::CurrentSchemaVersion,
-            &rollback_point.schema_version,
-        );
-        env.storage()
-            .instance()
-            .set(&DataKey::CurrentWasmHash, &rollback_point.wasm_hash);
-
-        env.storage().instance().remove(&DataKey::PendingUpgrade);
+            .persistent()
+            .set(&DataKey::MigrationConfirmed(version), &true);
 
-        RollbackExecuted {
-            from_version: current_version,
-            to_version: rollback_point.version,
-            reason_hash,
+        MigrationConfirmed {
+            version,
+            result_hash,
         }
         .publish(&env);
 
         Ok(())
     }
 
+    pub fn is_migration_confirmed(env: Env, version: u32) -> bool {
+        is_migration_confirmed(&env, version)
+    }
+
+    /// Roll back to the most recent superseded release and drop it from the
+    /// rollback stack, so repeated calls step further back.
+    pub fn rollback(
+        env: Env,
+        admin: Address,
+        reason_hash: BytesN<32>,
+        allow_schema_downgrade: bool,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        require_not_killed(&env)?;
+
+        let stack = get_rollback_stack(&env);
+        if stack.is_empty() {
+            return Err(Error::InvalidStateTransition);
+        }
+        let index = stack.len() - 1;
+        restore_release(
+            &env,
+            admin,
+            stack,
+            index,
+            reason_hash,
+            allow_schema_downgrade,
+        )
+    }
+
+    /// Roll back to `version`, which must still be on the rollback stack.
+    /// It and every newer rollback point are dropped from the stack.
+    pub fn rollback_to_version(
+        env: Env,
+        admin: Address,
+        version: u32,
+        reason_hash: BytesN<32>,
+        allow_schema_downgrade: bool,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        require_not_killed(&env)?;
+
+        let stack = get_rollback_stack(&env);
+        let index = stack
+            .iter()
+            .position(|record| record.version == version)
+            .ok_or(Error::InvalidStateTransition)?;
+        restore_release(
+            &env,
+            admin,
+            stack,
+            index as u32,
+            reason_hash,
+            allow_schema_downgrade,
+        )
+    }
+
+    /// Superseded releases available to roll back to, oldest first.
+    pub fn rollback_points(env: Env) -> Vec<ReleaseRecord> {
+        get_rollback_stack(&env)
+    }
+
     pub fn state(env: Env) -> Result<UpgradeState, Error> {
         require_initialized(&env)?;
 
-        let pending_upgrade: Option<PendingUpgrade> = env.storage().instance().get(&DataKey::PendingUpgrade);
-        let rollback_point: Option<ReleaseRecord> = env.storage().instance().get(&DataKey::RollbackPoint);
-        let test_gate: Option<TestGateConfig> = env.storage().instance().get(&DataKey::TestGate);
+        let pending_upgrade: Option<PendingUpgrade> =
+            env.storage().instance().get(&DataKey::PendingUpgrade);
+        let rollback_point: Option<ReleaseRecord> = get_rollback_stack(&env).last();
+        let test_gates = get_test_gates(&env);
 
         Ok(UpgradeState {
             admin: get_admin(&env)?,
+            pending_admin: env.storage().instance().get(&DataKey::PendingAdmin),
+            approver: get_approver(&env)?,
+            pending_approved: pending_upgrade
+                .as_ref()
+                .is_some_and(|p| is_upgrade_approved(&env, p.version)),
+            kill_switch_guardian: env
+                .storage()
+                .instance()
+                .get(&DataKey::KillSwitchGuardian)
+                .ok_or(Error::NotInitialized)?,
             paused: is_paused(&env),
+            paused_at: get_u64(&env, &DataKey::PausedAt),
+            pause_expiry: get_u64(&env, &DataKey::PauseExpiry),
             killed: is_killed(&env),
             current_version: get_u32(&env, &DataKey::CurrentVersion)?,
             current_schema_version: get_u32(&env, &DataKey::CurrentSchemaVersion)?,
@@ -434,33 +941,147 @@ impl UpgradeMechanism {
                 .get(&DataKey::CurrentWasmHash)
                 .ok_or(Error::NotInitialized)?,
             has_pending_upgrade: pending_upgrade.is_some(),
-            pending_version: pending_upgrade.map_or(0, |p| p.version),
+            pending_version: pending_upgrade.as_ref().map_or(0, |p| p.version),
             has_rollback_point: rollback_point.is_some(),
             rollback_version: rollback_point.map_or(0, |r| r.version),
-            has_test_gate: test_gate.is_some(),
-            test_gate_enabled: test_gate.map_or(false, |t| t.enabled),
+            test_gate_count: test_gates.len(),
+            test_gate_enabled: test_gates.iter().any(|t| t.enabled),
+            migration_confirmed: is_migration_confirmed(
+                &env,
+                get_u32(&env, &DataKey::CurrentVersion)?,
+            ),
+            activation_delay: get_activation_delay(&env),
+            activation_unlocks_at: pending_upgrade.map_or(0, |p| p.unlocks_at),
         })
     }
 
-    pub fn get_release(env: Env, version: u32) -> Result<Option<ReleaseRecord>, Error> {
+    /// Dry run of `execute_upgrade`'s preconditions, without mutating state.
+    ///
+    /// Returns `Ok(false)` when nothing is staged, the contract is paused or
+    /// killed, approvals, the approver sign-off or the activation delay are
+    /// outstanding, or an enabled test gate is not ready. Errors only if the
+    /// contract is not initialized.
+    pub fn can_execute(env: Env) -> Result<bool, Error> {
         require_initialized(&env)?;
-        Ok(env.storage().persistent().get(&DataKey::Release(version)))
-    }
-}
+        if is_killed(&env) || is_paused(&env) {
+            return Ok(false);
+        }
 
-fn require_initialized(env: &Env) -> Result<(), Error> {
-    if !env.storage().instance().has(&DataKey::Admin) {
-        return Err(Error::NotInitialized);
-    }
-    Ok(())
-}
+        let pending: PendingUpgrade = match env.storage().instance().get(&DataKey::PendingUpgrade) {
+            Some(pending) => pending,
+            None => return Ok(false),
+        };
+        if remaining_approvals(&env, pending.version) > 0
+            || !is_upgrade_approved(&env, pending.version)
+            || env.ledger().timestamp() < pending.unlocks_at
+        {
+            return Ok(false);
+        }
 
-fn require_not_paused(env: &Env) -> Result<(), Error> {
-    if is_paused(env) {
-        return Err(Error::ContractPaused);
+        Ok(test_gates_ready(&env))
     }
-    Ok(())
-}
+
+    /// Single alerting-friendly health check.
+    ///
+    /// Returns `(false, reason)` with reason `uninit`, `killed`, `paused` or
+    /// `expired` (a staged upgrade older than seven days), checked in that
+    /// order, and `(true, ok)` otherwise.
+    pub fn health(env: Env) -> (bool, Symbol) {
+        if require_initialized(&env).is_err() {
+            return (false, symbol_short!("uninit"));
+        }
+        if is_killed(&env) {
+            return (false, symbol_short!("killed"));
+        }
+        if is_paused(&env) {
+            return (false, symbol_short!("paused"));
+        }
+        if let Ok(pending) = get_pending_upgrade(&env) {
+            let age = env.ledger().timestamp().saturating_sub(pending.staged_at);
+            if age > PENDING_UPGRADE_MAX_AGE {
+                return (false, symbol_short!("expired"));
+            }
+        }
+        (true, symbol_short!("ok"))
+    }
+
+    /// The full staged upgrade record, or `None` if nothing is staged.
+    pub fn pending_upgrade(env: Env) -> Result<Option<PendingUpgrade>, Error> {
+        require_initialized(&env)?;
+        Ok(env.storage().instance().get(&DataKey::PendingUpgrade))
+    }
+
+    pub fn get_release(env: Env, version: u32) -> Result<Option<ReleaseRecord>, Error> {
+        require_initialized(&env)?;
+        Ok(env.storage().persistent().get(&DataKey::Release(version)))
+    }
+
+    /// Every executed release in ascending version order, including ones
+    /// that were later rolled back. Version numbers never used are skipped.
+    pub fn list_releases(env: Env) -> Result<Vec<ReleaseRecord>, Error> {
+        require_initialized(&env)?;
+        Ok(all_releases(&env))
+    }
+
+    /// `(version, changelog_hash)` for every release in ascending version
+    /// order, so off-chain changelogs can be hashed and compared against it.
+    /// Empty before `init`.
+    pub fn changelog_chain(env: Env) -> Vec<(u32, BytesN<32>)> {
+        let mut chain = Vec::new(&env);
+        for record in all_releases(&env).iter() {
+            chain.push_back((record.version, record.changelog_hash));
+        }
+        chain
+    }
+
+    pub fn get_test_gate(env: Env, suite: Symbol) -> Option<TestGateConfig> {
+        env.storage().instance().get(&DataKey::TestGate(suite))
+    }
+
+    /// Every registered test gate, in registration order.
+    pub fn test_gates(env: Env) -> Vec<TestGateConfig> {
+        get_test_gates(&env)
+    }
+
+    /// Guardians who have approved the staged upgrade; empty if nothing is staged.
+    pub fn pending_approvals(env: Env) -> Vec<Address> {
+        match env
+            .storage()
+            .instance()
+            .get::<_, PendingUpgrade>(&DataKey::PendingUpgrade)
+        {
+            Some(pending) => get_approvals(&env, pending.version),
+            None => Vec::new(&env),
+        }
+    }
+
+    /// Further approvals required before the staged upgrade can execute;
+    /// zero if nothing is staged.
+    pub fn approvals_needed(env: Env) -> u32 {
+        match env
+            .storage()
+            .instance()
+            .get::<_, PendingUpgrade>(&DataKey::PendingUpgrade)
+        {
+            Some(pending) => remaining_approvals(&env, pending.version),
+            None => 0,
+        }
+    }
+}
+
+fn require_initialized(env: &Env) -> Result<(), Error> {
+    if !env.storage().instance().has(&DataKey::Admin) {
+        return Err(Error::NotInitialized);
+    }
+    Ok(())
+}
+
+fn require_not_paused(env: &Env) -> Result<(), Error> {
+    if is_paused(env) {
+        return Err(Error::ContractPaused);
+    }
+    Ok(())
+}
 
 fn require_not_killed(env: &Env) -> Result<(), Error> {
     if is_killed(env) {
@@ -492,8 +1113,29 @@ fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
     Ok(())
 }
 
+/// A pause is in effect while the flag is set and its expiry, if any, has
+/// not passed yet.
 fn is_paused(env: &Env) -> bool {
-    env.storage().instance().get(&DataKey::Paused).unwrap_or(false)
+    let paused: bool = env
+        .storage()
+        .instance()
+        .get(&DataKey::Paused)
+        .unwrap_or(false);
+    if !paused {
+        return false;
+    }
+    let expiry = get_u64(env, &DataKey::PauseExpiry);
+    expiry == 0 || env.ledger().timestamp() <= expiry
+}
+
+fn clear_pause(env: &Env) {
+    env.storage().instance().set(&DataKey::Paused, &false);
+    env.storage().instance().remove(&DataKey::PausedAt);
+    env.storage().instance().remove(&DataKey::PauseExpiry);
+}
+
+fn get_u64(env: &Env, key: &DataKey) -> u64 {
+    env.storage().instance().get(key).unwrap_or(0)
 }
 
 fn is_killed(env: &Env) -> bool {
@@ -504,6 +1146,24 @@ fn get_u32(env: &Env, key: &DataKey) -> Result<u32, Error> {
     env.storage().instance().get(key).ok_or(Error::NotInitialized)
 }
 
+fn get_approver(env: &Env) -> Result<Address, Error> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Approver)
+        .ok_or(Error::NotInitialized)
+}
+
+fn is_upgrade_approved(env: &Env, version: u32) -> bool {
+    env.storage().instance().get(&DataKey::UpgradeApproved) == Some(version)
+}
+
+fn is_migration_confirmed(env: &Env, version: u32) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MigrationConfirmed(version))
+        .unwrap_or(false)
+}
+
 fn get_pending_upgrade(env: &Env) -> Result<PendingUpgrade, Error> {
     env.storage()
         .instance()
@@ -519,16 +1179,197 @@ fn current_release(env: &Env) -> Result<ReleaseRecord, Error> {
         .ok_or(Error::NotInitialized)
 }
 
-fn get_test_gate(env: &Env) -> Option<TestGateConfig> {
-    env.storage().instance().get(&DataKey::TestGate)
+fn get_release_versions(env: &Env) -> Vec<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ReleaseVersions)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Insert `version` into `ReleaseVersions`, keeping it sorted. After a
+/// rollback a release can be executed below the highest version, so this
+/// is not always an append.
+fn record_release_version(env: &Env, version: u32) {
+    let mut versions = get_release_versions(env);
+    if let Err(index) = versions.binary_search(version) {
+        versions.insert(index, version);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReleaseVersions, &versions);
+    }
+}
+
+/// Every stored release in ascending version order.
+fn all_releases(env: &Env) -> Vec<ReleaseRecord> {
+    let mut releases = Vec::new(env);
+    for version in get_release_versions(env).iter() {
+        if let Some(record) = env.storage().persistent().get(&DataKey::Release(version)) {
+            releases.push_back(record);
+        }
+    }
+    releases
+}
+
+fn get_rollback_stack(env: &Env) -> Vec<ReleaseRecord> {
+    env.storage()
+        .instance()
+        .get(&DataKey::RollbackStack)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Make `stack[index]` the current release and reinstall its Wasm,
+/// truncating the stack to the entries older than it. Any staged upgrade is
+/// discarded along with its approvals, as in `cancel_upgrade`.
+fn restore_release(
+    env: &Env,
+    admin: Address,
+    mut stack: Vec<ReleaseRecord>,
+    index: u32,
+    reason_hash: BytesN<32>,
+    allow_schema_downgrade: bool,
+) -> Result<(), Error> {
+    let target = stack.get(index).ok_or(Error::InvalidStateTransition)?;
+    let current_version = get_u32(env, &DataKey::CurrentVersion)?;
+    if current_version == target.version {
+        return Err(Error::InvalidStateTransition);
+    }
+
+    // Data may already have been migrated to the newer schema; reading it
+    // with an older one must be an explicit decision.
+    let schema_downgraded = target.schema_version < get_u32(env, &DataKey::CurrentSchemaVersion)?;
+    if schema_downgraded && !allow_schema_downgrade {
+        return Err(Error::InvalidStateTransition);
+    }
+
+    while stack.len() > index {
+        stack.pop_back();
+    }
+    env.storage()
+        .instance()
+        .set(&DataKey::RollbackStack, &stack);
+
+    env.storage()
+        .instance()
+        .set(&DataKey::CurrentVersion, &target.version);
+    env.storage()
+        .instance()
+        .set(&DataKey::CurrentSchemaVersion, &target.schema_version);
+    env.storage()
+        .instance()
+        .set(&DataKey::CurrentWasmHash, &target.wasm_hash);
+
+    if let Some(pending) = env
+        .storage()
+        .instance()
+        .get::<_, PendingUpgrade>(&DataKey::PendingUpgrade)
+    {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Approvals(pending.version));
+    }
+    env.storage().instance().remove(&DataKey::PendingUpgrade);
+    env.storage().instance().remove(&DataKey::UpgradeApproved);
+
+    // As in `execute_upgrade`, swap the code last so a missing Wasm reverts
+    // the whole rollback.
+    env.deployer()
+        .update_current_contract_wasm(target.wasm_hash.clone());
+
+    RollbackExecuted {
+        admin,
+        from_version: current_version,
+        to_version: target.version,
+        reason_hash,
+        schema_downgraded,
+    }
+    .publish(env);
+
+    Ok(())
+}
+
+fn get_test_gate_suites(env: &Env) -> Vec<Symbol> {
+    env.storage()
+        .instance()
+        .get(&DataKey::TestGateSuites)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Whether every enabled test gate reports `is_release_ready(suite)`, with
+/// a `last_successful_run_age(suite)` within its `gate_max_age` when one is
+/// set.
+fn test_gates_ready(env: &Env) -> bool {
+    get_test_gates(env)
+        .iter()
+        .filter(|cfg| cfg.enabled)
+        .all(|cfg| {
+            let args = vec![env, cfg.suite.clone().into_val(env)];
+            if cfg.gate_max_age > 0 {
+                let age = env.invoke_contract::<Option<u64>>(
+                    &cfg.gate_contract,
+                    &Symbol::new(env, "last_successful_run_age"),
+                    args.clone(),
+                );
+                match age {
+                    Some(age) if age <= cfg.gate_max_age => {}
+                    _ => return false,
+                }
+            }
+            env.invoke_contract::<bool>(
+                &cfg.gate_contract,
+                &Symbol::new(env, "is_release_ready"),
+                args,
+            )
+        })
+}
+
+fn get_test_gates(env: &Env) -> Vec<TestGateConfig> {
+    let mut gates = Vec::new(env);
+    for suite in get_test_gate_suites(env).iter() {
+        if let Some(cfg) = env.storage().instance().get(&DataKey::TestGate(suite)) {
+            gates.push_back(cfg);
+        }
+    }
+    gates
+}
+
+fn get_guardians(env: &Env) -> Vec<Address> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Guardians)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn get_approvals(env: &Env, version: u32) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Approvals(version))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+fn get_activation_delay(env: &Env) -> u64 {
+    env.storage()
+        .instance()
+        .get(&DataKey::ActivationDelay)
+        .unwrap_or(0)
+}
+
+fn remaining_approvals(env: &Env, version: u32) -> u32 {
+    let threshold: u32 = env
+        .storage()
+        .instance()
+        .get(&DataKey::ApprovalThreshold)
+        .unwrap_or(0);
+    threshold.saturating_sub(get_approvals(env, version).len())
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
     use soroban_sdk::{
-        testutils::{Address as _, BytesN as _},
-        Address, Env,
+        testutils::{
+            Address as _, BytesN as _, Events as _, Ledger as _, MockAuth, MockAuthInvoke,
+        },
+        Address, Bytes, Env, Event, IntoVal,
     };
 
     #[contract]
@@ -555,17 +1396,55 @@ mod test {
         BytesN::random(env)
     }
 
+    /// Hash of the placeholder Wasm the test host gives natively registered
+    /// contracts; upgrading to it keeps dispatching to `UpgradeMechanism`.
+    /// Tests that execute several releases restage it with `force_same_wasm`.
+    fn native_wasm_hash(env: &Env) -> BytesN<32> {
+        env.crypto().sha256(&Bytes::new(env)).into()
+    }
+
     fn setup(env: &Env) -> (UpgradeMechanismClient<'_>, Address) {
+        let (client, admin, _guardian) = setup_with_guardian(env);
+        (client, admin)
+    }
+
+    fn setup_with_guardian(env: &Env) -> (UpgradeMechanismClient<'_>, Address, Address) {
+        let admin = Address::generate(env);
+        let guardian = Address::generate(env);
+        let approver = Address::generate(env);
+        let contract_id = env.register(UpgradeMechanism, ());
+        let client = UpgradeMechanismClient::new(env, &contract_id);
+
+        env.mock_all_auths();
+        client.init(&admin, &guardian, &approver, &random_hash(env), &1u32);
+
+        (client, admin, guardian)
+    }
+
+    /// Like `setup`, but release 1 records the native placeholder Wasm, so
+    /// rolling back to it can reinstall real code.
+    fn setup_native(env: &Env) -> (UpgradeMechanismClient<'_>, Address) {
         let admin = Address::generate(env);
         let contract_id = env.register(UpgradeMechanism, ());
         let client = UpgradeMechanismClient::new(env, &contract_id);
 
         env.mock_all_auths();
-        client.init(&admin, &random_hash(env), &1u32);
+        client.init(
+            &admin,
+            &Address::generate(env),
+            &Address::generate(env),
+            &native_wasm_hash(env),
+            &1u32,
+        );
 
         (client, admin)
     }
 
+    /// Approver sign-off on the staged upgrade (auths must be mocked).
+    fn approve(client: &UpgradeMechanismClient<'_>) {
+        client.approve_upgrade(&client.state().approver);
+    }
+
     #[test]
     fn init_and_state_are_consistent() {
         let env = Env::default();
@@ -579,6 +1458,38 @@ mod test {
         assert!(!state.killed);
     }
 
+    #[test]
+    fn health_reports_each_unhealthy_state() {
+        let env = Env::default();
+        let unset = UpgradeMechanismClient::new(&env, &env.register(UpgradeMechanism, ()));
+        assert_eq!(unset.health(), (false, symbol_short!("uninit")));
+
+        let (client, admin, guardian) = setup_with_guardian(&env);
+        assert_eq!(client.health(), (true, symbol_short!("ok")));
+
+        client.stage_upgrade(
+            &admin,
+            &2u32,
+            &native_wasm_hash(&env),
+            &2u32,
+            &random_hash(&env),
+            &random_hash(&env),
+            &false,
+            &false,
+        );
+        env.ledger()
+            .with_mut(|li| li.timestamp += PENDING_UPGRADE_MAX_AGE);
+        assert_eq!(client.health(), (true, symbol_short!("ok")));
+        env.ledger().with_mut(|li| li.timestamp += 1);
+        assert_eq!(client.health(), (false, symbol_short!("expired")));
+
+        client.pause(&admin, &0u64);
+        assert_eq!(client.health(), (false, symbol_short!("paused")));
+
+        client.trigger_kill_switch(&admin, &guardian, &random_hash(&env));
+        assert_eq!(client.health(), (false, symbol_short!("killed")));
+    }
+
     #[test]
     fn unauthorized_stage_is_rejected() {
         let env = Env::default();
@@ -593,6 +1504,8 @@ mod test {
             &2u32,
             &random_hash(&env),
             &random_hash(&env),
+            &false,
+            &false,
         );
         assert!(result.is_err());
     }
@@ -606,130 +1519,1135 @@ mod test {
         client.stage_upgrade(
             &admin,
             &2u32,
-            &random_hash(&env),
+            &native_wasm_hash(&env),
             &2u32,
             &random_hash(&env),
             &random_hash(&env),
+            &false,
+            &false,
         );
+        approve(&client);
         client.execute_upgrade(&admin);
 
         let state = client.state();
         assert_eq!(state.current_version, 2);
         assert_eq!(state.current_schema_version, 2);
-        assert!(client.get_release(&2u32).is_some());
+        assert_eq!(client.get_release(&2u32).unwrap().actor, admin);
+        assert_eq!(client.get_release(&1u32).unwrap().actor, admin);
     }
 
     #[test]
-    fn duplicate_version_is_rejected() {
+    fn staging_the_deployed_wasm_requires_force() {
         let env = Env::default();
         let (client, admin) = setup(&env);
         env.mock_all_auths();
+        let current_wasm = client.state().current_wasm_hash;
 
-        client.stage_upgrade(
+        let result = client.try_stage_upgrade(
             &admin,
             &2u32,
-            &random_hash(&env),
-            &1u32,
+            &current_wasm,
+            &2u32,
             &random_hash(&env),
             &random_hash(&env),
+            &false,
+            &false,
         );
-        client.execute_upgrade(&admin);
+        assert_eq!(result, Err(Ok(Error::IdenticalWasm)));
+        assert!(!client.state().has_pending_upgrade);
 
-        let result = client.try_stage_upgrade(
+        client.stage_upgrade(
             &admin,
             &2u32,
-            &random_hash(&env),
-            &1u32,
+            &current_wasm,
+            &2u32,
             &random_hash(&env),
             &random_hash(&env),
+            &false,
+            &true,
         );
-        assert!(result.is_err());
+        assert_eq!(client.state().pending_version, 2);
     }
 
     #[test]
-    fn pause_blocks_mutations() {
+    fn pending_upgrade_returns_staged_record() {
         let env = Env::default();
         let (client, admin) = setup(&env);
         env.mock_all_auths();
+        env.ledger().with_mut(|li| li.timestamp = 500);
 
-        client.pause(&admin);
-        let result = client.try_stage_upgrade(
+        assert_eq!(client.pending_upgrade(), None);
+
+        let wasm_hash = native_wasm_hash(&env);
+        let migration_hash = random_hash(&env);
+        let changelog_hash = random_hash(&env);
+        client.stage_upgrade(
+            &admin,
+            &2u32,
+            &wasm_hash,
+            &2u32,
+            &migration_hash,
+            &changelog_hash,
+            &false,
+            &false,
+        );
+        assert_eq!(
+            client.pending_upgrade(),
+            Some(PendingUpgrade {
+                version: 2,
+                wasm_hash,
+                target_schema_version: 2,
+                migration_hash,
+                changelog_hash,
+                staged_at: 500,
+                unlocks_at: 500,
+                forced_schema_jump: false,
+            })
+        );
+
+        client.cancel_upgrade(&admin);
+        assert_eq!(client.pending_upgrade(), None);
+    }
+
+    #[test]
+    fn duplicate_version_is_rejected() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+        env.mock_all_auths();
+
+        client.stage_upgrade(
             &admin,
             &2u32,
+            &native_wasm_hash(&env),
+            &1u32,
+            &random_hash(&env),
             &random_hash(&env),
+            &false,
+            &false,
+        );
+        approve(&client);
+        client.execute_upgrade(&admin);
+
+        let result = client.try_stage_upgrade(
+            &admin,
+            &2u32,
+            &native_wasm_hash(&env),
             &1u32,
             &random_hash(&env),
             &random_hash(&env),
+            &false,
+            &false,
         );
         assert!(result.is_err());
     }
 
     #[test]
-    fn rollback_restores_previous_release() {
+    fn schema_jump_requires_force_flag() {
         let env = Env::default();
         let (client, admin) = setup(&env);
         env.mock_all_auths();
 
-        client.stage_upgrade(
+        let result = client.try_stage_upgrade(
             &admin,
             &2u32,
+            &native_wasm_hash(&env),
+            &5u32,
+            &random_hash(&env),
             &random_hash(&env),
+            &false,
+            &false,
+        );
+        assert_eq!(result, Err(Ok(Error::InvalidStateTransition)));
+
+        // Forcing still refuses to stay on or go below the current schema.
+        let result = client.try_stage_upgrade(
+            &admin,
             &2u32,
+            &native_wasm_hash(&env),
+            &1u32,
             &random_hash(&env),
             &random_hash(&env),
+            &true,
+            &false,
         );
-        client.execute_upgrade(&admin);
+        assert_eq!(result, Err(Ok(Error::InvalidStateTransition)));
 
-        client.rollback(&admin, &random_hash(&env));
+        client.stage_upgrade(
+            &admin,
+            &2u32,
+            &native_wasm_hash(&env),
+            &5u32,
+            &random_hash(&env),
+            &random_hash(&env),
+            &true,
+            &false,
+        );
+        approve(&client);
+        client.execute_upgrade(&admin);
 
-        let state = client.state();
-        assert_eq!(state.current_version, 1);
-        assert_eq!(state.current_schema_version, 1);
+        assert_eq!(client.state().current_schema_version, 5);
+        let release = client.get_release(&2u32).unwrap();
+        assert_eq!(release.schema_version, 5);
+        assert!(release.forced_schema_jump);
+        assert!(!client.get_release(&1u32).unwrap().forced_schema_jump);
     }
 
     #[test]
-    fn test_gate_can_block_execute() {
+    fn pause_blocks_mutations() {
         let env = Env::default();
         let (client, admin) = setup(&env);
-
-        let gate_id = env.register(MockGate, ());
-        let gate_client = MockGateClient::new(&env, &gate_id);
-
         env.mock_all_auths();
-        gate_client.set_ready(&false);
-        client.configure_test_gate(&admin, &gate_id, &Symbol::new(&env, "core_suite"), &true);
 
-        client.stage_upgrade(
+        client.pause(&admin, &0u64);
+        let result = client.try_stage_upgrade(
             &admin,
             &2u32,
-            &random_hash(&env),
+            &native_wasm_hash(&env),
             &1u32,
             &random_hash(&env),
             &random_hash(&env),
+            &false,
+            &false,
         );
-
-        let blocked = client.try_execute_upgrade(&admin);
-        assert!(blocked.is_err());
-
-        gate_client.set_ready(&true);
-        client.execute_upgrade(&admin);
-
-        let state = client.state();
-        assert_eq!(state.current_version, 2);
+        assert!(result.is_err());
     }
 
     #[test]
-    fn kill_switch_freezes_contract() {
+    fn timed_pause_lapses_without_unpause() {
         let env = Env::default();
         let (client, admin) = setup(&env);
         env.mock_all_auths();
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
 
-        client.trigger_kill_switch(&admin, &random_hash(&env));
-        let res = client.try_unpause(&admin);
+        client.pause(&admin, &3_600u64);
+        let state = client.state();
+        assert!(state.paused);
+        assert_eq!(state.paused_at, 1_000);
+        assert_eq!(state.pause_expiry, 4_600);
+
+        env.ledger().with_mut(|li| li.timestamp = 4_600);
+        let result = client.try_stage_upgrade(
+            &admin,
+            &2u32,
+            &native_wasm_hash(&env),
+            &1u32,
+            &random_hash(&env),
+            &random_hash(&env),
+            &false,
+            &false,
+        );
+        assert_eq!(result, Err(Ok(Error::ContractPaused)));
+
+        env.ledger().with_mut(|li| li.timestamp = 4_601);
+        assert!(!client.state().paused);
+        assert_eq!(client.health(), (true, symbol_short!("ok")));
+        client.stage_upgrade(
+            &admin,
+            &2u32,
+            &native_wasm_hash(&env),
+            &1u32,
+            &random_hash(&env),
+            &random_hash(&env),
+            &false,
+            &false,
+        );
+        assert_eq!(client.state().pending_version, 2);
+    }
+
+    #[test]
+    fn rollback_restores_previous_release() {
+        let env = Env::default();
+        let (client, admin) = setup_native(&env);
+        env.mock_all_auths();
+
+        client.stage_upgrade(
+            &admin,
+            &2u32,
+            &native_wasm_hash(&env),
+            &2u32,
+            &random_hash(&env),
+            &random_hash(&env),
+            &false,
+            &true,
+        );
+        approve(&client);
+        client.execute_upgrade(&admin);
+
+        client.rollback(&admin, &random_hash(&env), &true);
+
+        let state = client.state();
+        assert_eq!(state.current_version, 1);
+        assert_eq!(state.current_schema_version, 1);
+    }
+
+    #[test]
+    fn rollback_schema_downgrade_requires_opt_in() {
+        let env = Env::default();
+        let (client, admin) = setup_native(&env);
+        env.mock_all_auths();
+
+        client.stage_upgrade(
+            &admin,
+            &2u32,
+            &native_wasm_hash(&env),
+            &2u32,
+            &random_hash(&env),
+            &random_hash(&env),
+            &false,
+            &true,
+        );
+        approve(&client);
+        client.execute_upgrade(&admin);
+
+        let reason = random_hash(&env);
+        assert_eq!(
+            client.try_rollback(&admin, &reason, &false),
+            Err(Ok(Error::InvalidStateTransition))
+        );
+        assert_eq!(client.state().current_schema_version, 2);
+
+        client.rollback(&admin, &reason, &true);
+        assert_eq!(
+            env.events().all().filter_by_contract(&client.address),
+            [RollbackExecuted {
+                admin: admin.clone(),
+                from_version: 2,
+                to_version: 1,
+                reason_hash: reason,
+                schema_downgraded: true,
+            }
+            .to_xdr(&env, &client.address)]
+        );
+        assert_eq!(client.state().current_schema_version, 1);
+    }
+
+    #[test]
+    fn rollback_stack_steps_back_through_releases() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+        env.mock_all_auths();
+
+        for version in 2u32..=4 {
+            client.stage_upgrade(
+                &admin,
+                &version,
+                &native_wasm_hash(&env),
+                &version,
+                &random_hash(&env),
+                &random_hash(&env),
+                &false,
+                &true,
+            );
+            approve(&client);
+            client.execute_upgrade(&admin);
+            client.confirm_migration(&admin, &version, &random_hash(&env));
+        }
+        assert_eq!(client.rollback_points().len(), 3);
+        assert_eq!(client.state().rollback_version, 3);
+
+        client.rollback(&admin, &random_hash(&env), &true);
+        let state = client.state();
+        assert_eq!(state.current_version, 3);
+        assert_eq!(state.current_schema_version, 3);
+        assert_eq!(state.rollback_version, 2);
+
+        client.rollback(&admin, &random_hash(&env), &true);
+        let state = client.state();
+        assert_eq!(state.current_version, 2);
+        assert_eq!(state.current_schema_version, 2);
+        assert_eq!(state.rollback_version, 1);
+    }
+
+    #[test]
+    fn list_releases_returns_full_history_in_order() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+        env.mock_all_auths();
+
+        let release = |version: u32| {
+            client.stage_upgrade(
+                &admin,
+                &version,
+                &native_wasm_hash(&env),
+                &1u32,
+                &random_hash(&env),
+                &random_hash(&env),
+                &false,
+                &true,
+            );
+            approve(&client);
+            client.execute_upgrade(&admin);
+            client.confirm_migration(&admin, &version, &random_hash(&env));
+        };
+
+        // A huge version number must not make the listing scan every
+        // number below it.
+        for version in [2u32, 4, 4_000_000_000] {
+            release(version);
+        }
+        client.rollback(&admin, &random_hash(&env), &false);
+        // Executed after the rollback, below the highest version.
+        release(5);
+
+        let releases = client.list_releases();
+        assert_eq!(releases.len(), 5);
+        let versions: Vec<u32> = Vec::from_iter(&env, releases.iter().map(|r| r.version));
+        assert_eq!(versions, vec![&env, 1u32, 2, 4, 5, 4_000_000_000]);
+    }
+
+    #[test]
+    fn changelog_chain_lists_hashes_in_version_order() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+        env.mock_all_auths();
+
+        let mut expected = vec![
+            &env,
+            (1u32, client.get_release(&1u32).unwrap().changelog_hash),
+        ];
+        for version in [2u32, 5] {
+            let changelog = random_hash(&env);
+            client.stage_upgrade(
+                &admin,
+                &version,
+                &native_wasm_hash(&env),
+                &1u32,
+                &random_hash(&env),
+                &changelog,
+                &false,
+                &true,
+            );
+            approve(&client);
+            client.execute_upgrade(&admin);
+            client.confirm_migration(&admin, &version, &random_hash(&env));
+            expected.push_back((version, changelog));
+        }
+
+        assert_eq!(client.changelog_chain(), expected);
+    }
+
+    #[test]
+    fn rollback_to_version_jumps_within_stack() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+        env.mock_all_auths();
+
+        for version in 2u32..=4 {
+            client.stage_upgrade(
+                &admin,
+                &version,
+                &native_wasm_hash(&env),
+                &version,
+                &random_hash(&env),
+                &random_hash(&env),
+                &false,
+                &true,
+            );
+            approve(&client);
+            client.execute_upgrade(&admin);
+            client.confirm_migration(&admin, &version, &random_hash(&env));
+        }
+
+        assert_eq!(
+            client.try_rollback_to_version(&admin, &4u32, &random_hash(&env), &false),
+            Err(Ok(Error::InvalidStateTransition))
+        );
+        assert_eq!(
+            client.try_rollback_to_version(&admin, &9u32, &random_hash(&env), &false),
+            Err(Ok(Error::InvalidStateTransition))
+        );
+
+        client.rollback_to_version(&admin, &2u32, &random_hash(&env), &true);
+        let state = client.state();
+        assert_eq!(state.current_version, 2);
+        assert_eq!(state.rollback_version, 1);
+        assert_eq!(client.rollback_points().len(), 1);
+
+        // Version 3 was discarded with the jump
+        assert_eq!(
+            client.try_rollback_to_version(&admin, &3u32, &random_hash(&env), &false),
+            Err(Ok(Error::InvalidStateTransition))
+        );
+    }
+
+    #[test]
+    fn rollback_stack_is_bounded() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+        env.mock_all_auths();
+
+        for version in 2u32..=8 {
+            client.stage_upgrade(
+                &admin,
+                &version,
+                &native_wasm_hash(&env),
+                &1u32,
+                &random_hash(&env),
+                &random_hash(&env),
+                &false,
+                &true,
+            );
+            approve(&client);
+            client.execute_upgrade(&admin);
+            client.confirm_migration(&admin, &version, &random_hash(&env));
+        }
+
+        let points = client.rollback_points();
+        assert_eq!(points.len(), MAX_ROLLBACK_POINTS);
+        assert_eq!(points.first().unwrap().version, 3);
+        assert_eq!(points.last().unwrap().version, 7);
+    }
+
+    #[test]
+    fn test_gate_can_block_execute() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+
+        let gate_id = env.register(MockGate, ());
+        let gate_client = MockGateClient::new(&env, &gate_id);
+
+        env.mock_all_auths();
+        gate_client.set_ready(&false);
+        client.configure_test_gate(
+            &admin,
+            &gate_id,
+            &Symbol::new(&env, "core_suite"),
+            &true,
+            &0u64,
+        );
+
+        client.stage_upgrade(
+            &admin,
+            &2u32,
+            &native_wasm_hash(&env),
+            &1u32,
+            &random_hash(&env),
+            &random_hash(&env),
+            &false,
+            &false,
+        );
+
+        approve(&client);
+        let blocked = client.try_execute_upgrade(&admin);
+        assert!(blocked.is_err());
+
+        gate_client.set_ready(&true);
+        client.execute_upgrade(&admin);
+
+        let state = client.state();
+        assert_eq!(state.current_version, 2);
+    }
+
+    #[test]
+    fn can_execute_reports_each_precondition() {
+        let env = Env::default();
+        let contract_id = env.register(UpgradeMechanism, ());
+        let uninit = UpgradeMechanismClient::new(&env, &contract_id);
+        assert_eq!(uninit.try_can_execute(), Err(Ok(Error::NotInitialized)));
+
+        let (client, admin) = setup(&env);
+        env.mock_all_auths();
+
+        // Nothing staged
+        assert!(!client.can_execute());
+
+        let gate_id = env.register(MockGate, ());
+        let gate_client = MockGateClient::new(&env, &gate_id);
+        client.configure_test_gate(
+            &admin,
+            &gate_id,
+            &Symbol::new(&env, "core_suite"),
+            &true,
+            &0u64,
+        );
+        client.set_activation_delay(&admin, &60u64);
+        client.stage_upgrade(
+            &admin,
+            &2u32,
+            &native_wasm_hash(&env),
+            &1u32,
+            &random_hash(&env),
+            &random_hash(&env),
+            &false,
+            &false,
+        );
+
+        // Activation delay outstanding
+        gate_client.set_ready(&true);
+        approve(&client);
+        assert!(!client.can_execute());
+        env.ledger().with_mut(|li| li.timestamp += 60);
+        assert!(client.can_execute());
+
+        // Gate not ready
+        gate_client.set_ready(&false);
+        assert!(!client.can_execute());
+        gate_client.set_ready(&true);
+        assert!(client.can_execute());
+
+        // Approver has not signed off
+        client.cancel_upgrade(&admin);
+        client.set_activation_delay(&admin, &0u64);
+        client.stage_upgrade(
+            &admin,
+            &2u32,
+            &native_wasm_hash(&env),
+            &1u32,
+            &random_hash(&env),
+            &random_hash(&env),
+            &false,
+            &false,
+        );
+        assert!(!client.can_execute());
+        approve(&client);
+        assert!(client.can_execute());
+
+        // Paused
+        client.pause(&admin, &0u64);
+        assert!(!client.can_execute());
+        client.unpause(&admin);
+        assert!(client.can_execute());
+
+        // Nothing was mutated by the checks.
+        assert_eq!(client.state().current_version, 1);
+        client.execute_upgrade(&admin);
+        assert!(!client.can_execute());
+    }
+
+    #[test]
+    fn get_test_gate_reflects_configuration() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+        env.mock_all_auths();
+
+        let suite = Symbol::new(&env, "core_suite");
+        assert_eq!(client.get_test_gate(&suite), None);
+
+        let gate_id = env.register(MockGate, ());
+        client.configure_test_gate(&admin, &gate_id, &suite, &true, &0u64);
+
+        assert_eq!(
+            client.get_test_gate(&suite),
+            Some(TestGateConfig {
+                enabled: true,
+                gate_contract: gate_id,
+                suite: suite.clone(),
+                gate_max_age: 0,
+            })
+        );
+
+        client.remove_test_gate(&admin, &suite);
+        assert_eq!(client.get_test_gate(&suite), None);
+        assert_eq!(client.state().test_gate_count, 0);
+        assert_eq!(
+            client.try_remove_test_gate(&admin, &suite),
+            Err(Ok(Error::TestGateNotFound))
+        );
+    }
+
+    #[test]
+    fn every_enabled_test_gate_must_pass() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+        env.mock_all_auths();
+
+        let security_gate = env.register(MockGate, ());
+        let perf_gate = env.register(MockGate, ());
+        MockGateClient::new(&env, &security_gate).set_ready(&true);
+        MockGateClient::new(&env, &perf_gate).set_ready(&false);
+        let perf = Symbol::new(&env, "perf");
+        client.configure_test_gate(
+            &admin,
+            &security_gate,
+            &Symbol::new(&env, "security"),
+            &true,
+            &0u64,
+        );
+        client.configure_test_gate(&admin, &perf_gate, &perf, &true, &0u64);
+
+        let state = client.state();
+        assert_eq!(state.test_gate_count, 2);
+        assert!(state.test_gate_enabled);
+
+        client.stage_upgrade(
+            &admin,
+            &2u32,
+            &native_wasm_hash(&env),
+            &1u32,
+            &random_hash(&env),
+            &random_hash(&env),
+            &false,
+            &false,
+        );
+        approve(&client);
+        assert_eq!(
+            client.try_execute_upgrade(&admin),
+            Err(Ok(Error::TestGateFailed))
+        );
+
+        // A disabled gate is skipped.
+        client.configure_test_gate(&admin, &perf_gate, &perf, &false, &0u64);
+        assert_eq!(client.state().test_gate_count, 2);
+        client.execute_upgrade(&admin);
+        assert_eq!(client.state().current_version, 2);
+    }
+
+    #[test]
+    fn guardian_approvals_are_tracked_and_required() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+        env.mock_all_auths();
+
+        let g1 = Address::generate(&env);
+        let g2 = Address::generate(&env);
+        let g3 = Address::generate(&env);
+        client.configure_guardians(&admin, &vec![&env, g1.clone(), g2.clone(), g3], &2u32);
+
+        // Nothing staged yet
+        assert_eq!(client.pending_approvals().len(), 0);
+        assert_eq!(client.approvals_needed(), 0);
+
+        client.stage_upgrade(
+            &admin,
+            &2u32,
+            &native_wasm_hash(&env),
+            &1u32,
+            &random_hash(&env),
+            &random_hash(&env),
+            &false,
+            &false,
+        );
+        assert_eq!(client.approvals_needed(), 2);
+        assert_eq!(
+            client.try_execute_upgrade(&admin),
+            Err(Ok(Error::ApprovalsPending))
+        );
+
+        client.guardian_approve(&g1);
+        assert_eq!(client.pending_approvals(), vec![&env, g1.clone()]);
+        assert_eq!(client.approvals_needed(), 1);
+        assert_eq!(
+            client.try_guardian_approve(&g1),
+            Err(Ok(Error::AlreadyApproved))
+        );
+
+        let outsider = Address::generate(&env);
+        assert_eq!(
+            client.try_guardian_approve(&outsider),
+            Err(Ok(Error::NotAuthorized))
+        );
+
+        client.guardian_approve(&g2);
+        assert_eq!(client.approvals_needed(), 0);
+        approve(&client);
+        client.execute_upgrade(&admin);
+
+        // Executed upgrade leaves nothing pending
+        assert_eq!(client.pending_approvals().len(), 0);
+        assert_eq!(client.approvals_needed(), 0);
+    }
+
+    #[test]
+    fn rollback_discards_guardian_approvals_of_staged_upgrade() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+        env.mock_all_auths();
+
+        for version in 2u32..=3 {
+            client.stage_upgrade(
+                &admin,
+                &version,
+                &native_wasm_hash(&env),
+                &1u32,
+                &random_hash(&env),
+                &random_hash(&env),
+                &false,
+                &true,
+            );
+            approve(&client);
+            client.execute_upgrade(&admin);
+            client.confirm_migration(&admin, &version, &random_hash(&env));
+        }
+
+        let g1 = Address::generate(&env);
+        client.configure_guardians(&admin, &vec![&env, g1.clone()], &1u32);
+        let stage_v4 = || {
+            client.stage_upgrade(
+                &admin,
+                &4u32,
+                &native_wasm_hash(&env),
+                &1u32,
+                &random_hash(&env),
+                &random_hash(&env),
+                &false,
+                &true,
+            );
+        };
+        stage_v4();
+        client.guardian_approve(&g1);
+        assert_eq!(client.approvals_needed(), 0);
+
+        client.rollback(&admin, &random_hash(&env), &false);
+        assert_eq!(client.state().current_version, 2);
+
+        // Restaging the same version starts from zero approvals.
+        stage_v4();
+        assert_eq!(client.pending_approvals().len(), 0);
+        assert_eq!(client.approvals_needed(), 1);
+    }
+
+    #[test]
+    fn execute_requires_approver_sign_off() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+        env.mock_all_auths();
+        let approver = client.state().approver;
+
+        assert_eq!(
+            client.try_approve_upgrade(&approver),
+            Err(Ok(Error::UpgradeNotStaged))
+        );
+        client.stage_upgrade(
+            &admin,
+            &2u32,
+            &native_wasm_hash(&env),
+            &1u32,
+            &random_hash(&env),
+            &random_hash(&env),
+            &false,
+            &false,
+        );
+        assert_eq!(
+            client.try_execute_upgrade(&admin),
+            Err(Ok(Error::UpgradeNotApproved))
+        );
+        assert_eq!(
+            client.try_approve_upgrade(&admin),
+            Err(Ok(Error::NotAuthorized))
+        );
+
+        // Cancelling drops the sign-off along with the staged upgrade.
+        client.approve_upgrade(&approver);
+        assert!(client.state().pending_approved);
+        client.cancel_upgrade(&admin);
+        client.stage_upgrade(
+            &admin,
+            &2u32,
+            &native_wasm_hash(&env),
+            &1u32,
+            &random_hash(&env),
+            &random_hash(&env),
+            &false,
+            &false,
+        );
+        assert!(!client.state().pending_approved);
+        assert_eq!(
+            client.try_execute_upgrade(&admin),
+            Err(Ok(Error::UpgradeNotApproved))
+        );
+
+        client.approve_upgrade(&approver);
+        assert_eq!(
+            client.try_approve_upgrade(&approver),
+            Err(Ok(Error::AlreadyApproved))
+        );
+        client.execute_upgrade(&admin);
+        assert_eq!(client.state().current_version, 2);
+        assert!(!client.state().pending_approved);
+    }
+
+    #[test]
+    fn activation_delay_holds_execute_until_elapsed() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+        env.mock_all_auths();
+        env.ledger().with_mut(|li| li.timestamp = 1_000);
+
+        client.set_activation_delay(&admin, &3_600u64);
+        client.stage_upgrade(
+            &admin,
+            &2u32,
+            &native_wasm_hash(&env),
+            &1u32,
+            &random_hash(&env),
+            &random_hash(&env),
+            &false,
+            &false,
+        );
+
+        let state = client.state();
+        assert_eq!(state.activation_delay, 3_600);
+        assert_eq!(state.activation_unlocks_at, 4_600);
+        approve(&client);
+        assert_eq!(
+            client.try_execute_upgrade(&admin),
+            Err(Ok(Error::TimelockNotElapsed))
+        );
+
+        // Lowering the delay does not shorten the staged upgrade's window.
+        client.set_activation_delay(&admin, &0u64);
+        assert_eq!(client.state().activation_unlocks_at, 4_600);
+        env.ledger().with_mut(|li| li.timestamp = 4_599);
+        assert_eq!(
+            client.try_execute_upgrade(&admin),
+            Err(Ok(Error::TimelockNotElapsed))
+        );
+        assert!(!client.can_execute());
+
+        env.ledger().with_mut(|li| li.timestamp = 4_600);
+        client.execute_upgrade(&admin);
+        assert_eq!(client.state().current_version, 2);
+        assert_eq!(client.state().activation_unlocks_at, 0);
+    }
+
+    #[test]
+    fn cancelled_upgrade_can_be_restaged() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+        env.mock_all_auths();
+
+        assert_eq!(
+            client.try_cancel_upgrade(&admin),
+            Err(Ok(Error::UpgradeNotStaged))
+        );
+
+        let guardian = Address::generate(&env);
+        client.configure_guardians(&admin, &vec![&env, guardian.clone()], &1u32);
+        client.stage_upgrade(
+            &admin,
+            &2u32,
+            &native_wasm_hash(&env),
+            &1u32,
+            &random_hash(&env),
+            &random_hash(&env),
+            &false,
+            &false,
+        );
+        client.guardian_approve(&guardian);
+
+        client.cancel_upgrade(&admin);
+        assert_eq!(
+            env.events().all().filter_by_contract(&client.address),
+            [UpgradeCancelled { version: 2 }.to_xdr(&env, &client.address)]
+        );
+        assert!(!client.state().has_pending_upgrade);
+        assert_eq!(
+            client.try_execute_upgrade(&admin),
+            Err(Ok(Error::UpgradeNotStaged))
+        );
+
+        // Re-staging the same version starts from a clean approval set
+        let wasm_hash = native_wasm_hash(&env);
+        client.stage_upgrade(
+            &admin,
+            &2u32,
+            &wasm_hash,
+            &2u32,
+            &random_hash(&env),
+            &random_hash(&env),
+            &false,
+            &false,
+        );
+        assert_eq!(client.approvals_needed(), 1);
+        client.guardian_approve(&guardian);
+        approve(&client);
+        client.execute_upgrade(&admin);
+
+        let state = client.state();
+        assert_eq!(state.current_version, 2);
+        assert_eq!(state.current_schema_version, 2);
+        assert_eq!(state.current_wasm_hash, wasm_hash);
+    }
+
+    #[test]
+    fn next_stage_waits_for_migration_confirmation() {
+        let env = Env::default();
+        let (client, admin) = setup(&env);
+        env.mock_all_auths();
+
+        // The initial release needs no migration
+        assert!(client.is_migration_confirmed(&1u32));
+        assert!(client.state().migration_confirmed);
+
+        client.stage_upgrade(
+            &admin,
+            &2u32,
+            &native_wasm_hash(&env),
+            &2u32,
+            &random_hash(&env),
+            &random_hash(&env),
+            &false,
+            &false,
+        );
+        approve(&client);
+        client.execute_upgrade(&admin);
+        assert!(!client.is_migration_confirmed(&2u32));
+        assert!(!client.state().migration_confirmed);
+
+        assert_eq!(
+            client.try_stage_upgrade(
+                &admin,
+                &3u32,
+                &random_hash(&env),
+                &2u32,
+                &random_hash(&env),
+                &random_hash(&env),
+                &false,
+                &false,
+            ),
+            Err(Ok(Error::MigrationNotConfirmed))
+        );
+
+        // Only the current release can be confirmed, and only once
+        assert_eq!(
+            client.try_confirm_migration(&admin, &3u32, &random_hash(&env)),
+            Err(Ok(Error::InvalidStateTransition))
+        );
+        let result_hash = random_hash(&env);
+        client.confirm_migration(&admin, &2u32, &result_hash);
+        assert_eq!(
+            env.events().all().filter_by_contract(&client.address),
+            [MigrationConfirmed {
+                version: 2,
+                result_hash: result_hash.clone(),
+            }
+            .to_xdr(&env, &client.address)]
+        );
+        assert_eq!(
+            client.try_confirm_migration(&admin, &2u32, &result_hash),
+            Err(Ok(Error::MigrationAlreadyConfirmed))
+        );
+        assert!(client.state().migration_confirmed);
+
+        client.stage_upgrade(
+            &admin,
+            &3u32,
+            &random_hash(&env),
+            &2u32,
+            &random_hash(&env),
+            &random_hash(&env),
+            &false,
+            &false,
+        );
+    }
+
+    #[test]
+    fn admin_rotation_requires_acceptance() {
+        let env = Env::default();
+        let (client, admin, guardian) = setup_with_guardian(&env);
+        env.mock_all_auths();
+        let new_admin = Address::generate(&env);
+        let stranger = Address::generate(&env);
+
+        assert_eq!(
+            client.try_accept_admin(&new_admin),
+            Err(Ok(Error::NotAuthorized))
+        );
+        assert_eq!(
+            client.try_propose_admin(&admin, &guardian),
+            Err(Ok(Error::InvalidInput))
+        );
+
+        client.propose_admin(&admin, &new_admin);
+        let state = client.state();
+        assert_eq!(state.admin, admin);
+        assert_eq!(state.pending_admin, Some(new_admin.clone()));
+
+        assert_eq!(
+            client.try_accept_admin(&stranger),
+            Err(Ok(Error::NotAuthorized))
+        );
+
+        client.accept_admin(&new_admin);
+        let state = client.state();
+        assert_eq!(state.admin, new_admin);
+        assert_eq!(state.pending_admin, None);
+
+        // The old admin has lost control.
+        assert_eq!(
+            client.try_pause(&admin, &0u64),
+            Err(Ok(Error::NotAuthorized))
+        );
+        client.pause(&new_admin, &0u64);
+    }
+
+    #[test]
+    fn admin_cannot_be_proposed_after_kill_switch() {
+        let env = Env::default();
+        let (client, admin, guardian) = setup_with_guardian(&env);
+        env.mock_all_auths();
+
+        client.trigger_kill_switch(&admin, &guardian, &random_hash(&env));
+        assert_eq!(
+            client.try_propose_admin(&admin, &Address::generate(&env)),
+            Err(Ok(Error::ContractKilled))
+        );
+    }
+
+    #[test]
+    fn kill_switch_freezes_contract() {
+        let env = Env::default();
+        let (client, admin, guardian) = setup_with_guardian(&env);
+        env.mock_all_auths();
+
+        client.trigger_kill_switch(&admin, &guardian, &random_hash(&env));
+        let res = client.try_unpause(&admin);
         assert!(res.is_err());
 
         let state = client.state();
         assert!(state.killed);
         assert!(state.paused);
     }
+
+    #[test]
+    fn kill_switch_requires_guardian_cosign() {
+        let env = Env::default();
+        let (client, admin, guardian) = setup_with_guardian(&env);
+        let reason = random_hash(&env);
+
+        // Naming anyone other than the registered guardian is rejected.
+        env.mock_all_auths();
+        assert_eq!(
+            client.try_trigger_kill_switch(&admin, &admin, &reason),
+            Err(Ok(Error::GuardianAuthRequired))
+        );
+
+        // The admin's signature alone is not enough.
+        env.mock_auths(&[MockAuth {
+            address: &admin,
+            invoke: &MockAuthInvoke {
+                contract: &client.address,
+                fn_name: "trigger_kill_switch",
+                args: (&admin, &guardian, &reason).into_val(&env),
+                sub_invokes: &[],
+            },
+        }]);
+        assert!(client
+            .try_trigger_kill_switch(&admin, &guardian, &reason)
+            .is_err());
+        assert!(!client.state().killed);
+
+        env.mock_auths(&[
+            MockAuth {
+                address: &admin,
+                invoke: &MockAuthInvoke {
+                    contract: &client.address,
+                    fn_name: "trigger_kill_switch",
+                    args: (&admin, &guardian, &reason).into_val(&env),
+                    sub_invokes: &[],
+                },
+            },
+            MockAuth {
+                address: &guardian,
+                invoke: &MockAuthInvoke {
+                    contract: &client.address,
+                    fn_name: "trigger_kill_switch",
+                    args: (&admin, &guardian, &reason).into_val(&env),
+                    sub_invokes: &[],
+                },
+            },
+        ]);
+        client.trigger_kill_switch(&admin, &guardian, &reason);
+        assert!(client.state().killed);
+    }
 }
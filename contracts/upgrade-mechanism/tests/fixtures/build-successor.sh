@@ -0,0 +1,26 @@
+#!/usr/bin/env bash
+
+# build-successor.sh
+# Rebuilds successor.wasm from ./successor and checks it against the hash
+# recorded in successor.wasm.sha256.
+#
+# The committed bytes are identical to soroban-sdk 25.x's
+# doctest_fixtures/contract.wasm (a copy of its `test_add_u64` contract),
+# built with rustc 1.74.0 against soroban-sdk rev 116c35bc; both are
+# recorded in the Wasm's contractmetav0 section. The successor crate pins
+# the same toolchain and revision.
+#
+# Usage: ./build-successor.sh [--check]
+#   --check  Only verify the committed successor.wasm against the hash
+
+set -euo pipefail
+
+FIXTURES_DIR="$(cd "$(dirname "$0")" && pwd)"
+cd "$FIXTURES_DIR"
+
+if [[ "${1:-}" != "--check" ]]; then
+  (cd successor && cargo build --release --target wasm32-unknown-unknown)
+  cp successor/target/wasm32-unknown-unknown/release/stellarcade_upgrade_successor_fixture.wasm successor.wasm
+fi
+
+sha256sum --check successor.wasm.sha256
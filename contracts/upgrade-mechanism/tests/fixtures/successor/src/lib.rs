@@ -0,0 +1,17 @@
+//! Successor contract installed by the upgrade-mechanism integration tests.
+//!
+//! Same source as soroban-sdk's `test_add_u64` test contract. Its only
+//! entrypoint is `add`, so after `execute_upgrade` the tests can tell that
+//! the address runs different code.
+#![no_std]
+use soroban_sdk::{contract, contractimpl};
+
+#[contract]
+pub struct Contract;
+
+#[contractimpl]
+impl Contract {
+    pub fn add(a: u64, b: u64) -> u64 {
+        a + b
+    }
+}
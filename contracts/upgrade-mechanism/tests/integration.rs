@@ -0,0 +1,235 @@
+#![cfg(test)]
+
+use soroban_sdk::{
+    testutils::{Address as _, BytesN as _, Ledger as _},
+    vec, Address, Bytes, BytesN, Env, IntoVal, Symbol,
+};
+use stellarcade_comprehensive_test_suite::{ComprehensiveTestSuite, ComprehensiveTestSuiteClient};
+use stellarcade_upgrade_mechanism::{Error, UpgradeMechanism, UpgradeMechanismClient};
+
+/// Minimal successor contract exposing only `add(a: u64, b: u64) -> u64`
+/// (the soroban-sdk `test_add_u64` test contract). Built from
+/// `fixtures/successor/` by `fixtures/build-successor.sh`.
+const SUCCESSOR_WASM: &[u8] = include_bytes!("fixtures/successor.wasm");
+
+/// Must match `fixtures/successor.wasm.sha256`.
+const SUCCESSOR_WASM_HASH: [u8; 32] = [
+    0x33, 0xd1, 0x2f, 0xec, 0x8f, 0x6f, 0x3d, 0xdf, 0x2e, 0xb0, 0xec, 0x76, 0xee, 0x9a, 0x75,
+    0xa9, 0xe3, 0x7d, 0x1f, 0xa2, 0x0a, 0xf3, 0x59, 0x08, 0xd9, 0x0d, 0x27, 0x8a, 0xf8, 0x26,
+    0x43, 0x11,
+];
+
+fn setup(env: &Env) -> (UpgradeMechanismClient<'_>, Address, Address) {
+    let admin = Address::generate(env);
+    let approver = Address::generate(env);
+    let contract_id = env.register(UpgradeMechanism, ());
+    let client = UpgradeMechanismClient::new(env, &contract_id);
+
+    env.mock_all_auths();
+    client.init(
+        &admin,
+        &Address::generate(env),
+        &approver,
+        &BytesN::random(env),
+        &1u32,
+    );
+
+    (client, admin, approver)
+}
+
+#[test]
+fn execute_upgrade_replaces_contract_code() {
+    let env = Env::default();
+    let (client, admin, approver) = setup(&env);
+
+    let successor_hash = env.deployer().upload_contract_wasm(SUCCESSOR_WASM);
+    assert_eq!(successor_hash, BytesN::from_array(&env, &SUCCESSOR_WASM_HASH));
+    client.stage_upgrade(
+        &admin,
+        &2u32,
+        &successor_hash,
+        &1u32,
+        &BytesN::random(&env),
+        &BytesN::random(&env),
+        &false,
+        &false,
+    );
+    client.approve_upgrade(&approver);
+    client.execute_upgrade(&admin);
+
+    // The address now runs the successor: its entrypoint answers and the
+    // upgrade-mechanism interface is gone.
+    let sum: u64 = env.invoke_contract(
+        &client.address,
+        &Symbol::new(&env, "add"),
+        vec![&env, 2u64.into_val(&env), 3u64.into_val(&env)],
+    );
+    assert_eq!(sum, 5);
+    assert!(client.try_state().is_err());
+}
+
+#[test]
+fn failed_wasm_update_reverts_the_version_bump() {
+    let env = Env::default();
+    let (client, admin, approver) = setup(&env);
+
+    // Never uploaded, so the code swap fails
+    client.stage_upgrade(
+        &admin,
+        &2u32,
+        &BytesN::random(&env),
+        &1u32,
+        &BytesN::random(&env),
+        &BytesN::random(&env),
+        &false,
+        &false,
+    );
+    client.approve_upgrade(&approver);
+    assert!(client.try_execute_upgrade(&admin).is_err());
+
+    let state = client.state();
+    assert_eq!(state.current_version, 1);
+    assert!(state.has_pending_upgrade);
+    assert_eq!(client.get_release(&2u32), None);
+    assert_eq!(
+        client.try_rollback(&admin, &BytesN::random(&env), &false),
+        Err(Ok(Error::InvalidStateTransition))
+    );
+}
+
+#[test]
+fn rollback_reinstalls_previous_code() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let approver = Address::generate(&env);
+    let contract_id = env.register(UpgradeMechanism, ());
+    let client = UpgradeMechanismClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    // Release 1 records the successor, while the address still runs
+    // `UpgradeMechanism`; release 2 installs the native placeholder, which
+    // keeps it that way.
+    let successor_hash = env.deployer().upload_contract_wasm(SUCCESSOR_WASM);
+    client.init(
+        &admin,
+        &Address::generate(&env),
+        &approver,
+        &successor_hash,
+        &1u32,
+    );
+    let native_hash: BytesN<32> = env.crypto().sha256(&Bytes::new(&env)).into();
+    client.stage_upgrade(
+        &admin,
+        &2u32,
+        &native_hash,
+        &1u32,
+        &BytesN::random(&env),
+        &BytesN::random(&env),
+        &false,
+        &false,
+    );
+    client.approve_upgrade(&approver);
+    client.execute_upgrade(&admin);
+    assert_eq!(client.state().current_wasm_hash, native_hash);
+
+    client.rollback(&admin, &BytesN::random(&env), &false);
+
+    // The address runs release 1's code again.
+    let sum: u64 = env.invoke_contract(
+        &client.address,
+        &Symbol::new(&env, "add"),
+        vec![&env, 2u64.into_val(&env), 3u64.into_val(&env)],
+    );
+    assert_eq!(sum, 5);
+    assert!(client.try_state().is_err());
+}
+
+#[test]
+fn failed_rollback_wasm_update_keeps_current_release() {
+    let env = Env::default();
+    let (client, admin, approver) = setup(&env);
+
+    // Release 1's random hash was never uploaded, so it cannot be reinstalled.
+    let native_hash: BytesN<32> = env.crypto().sha256(&Bytes::new(&env)).into();
+    client.stage_upgrade(
+        &admin,
+        &2u32,
+        &native_hash,
+        &1u32,
+        &BytesN::random(&env),
+        &BytesN::random(&env),
+        &false,
+        &false,
+    );
+    client.approve_upgrade(&approver);
+    client.execute_upgrade(&admin);
+
+    assert!(client.try_rollback(&admin, &BytesN::random(&env), &false).is_err());
+    let state = client.state();
+    assert_eq!(state.current_version, 2);
+    assert_eq!(state.current_wasm_hash, native_hash);
+    assert_eq!(state.rollback_version, 1);
+}
+
+#[test]
+fn stale_gate_result_blocks_execute() {
+    let env = Env::default();
+    let (client, admin, approver) = setup(&env);
+
+    let suite = Symbol::new(&env, "security");
+    let reporter = Address::generate(&env);
+    let gate_id = env.register(ComprehensiveTestSuite, ());
+    let gate = ComprehensiveTestSuiteClient::new(&env, &gate_id);
+    gate.init(&admin, &reporter, &8_000u32);
+    gate.register_suite(&admin, &suite, &1u32, &false, &false);
+    client.configure_test_gate(&admin, &gate_id, &suite, &true, &3_600u64);
+    let record_run = |build: u8| {
+        gate.record_run(
+            &reporter,
+            &suite,
+            &BytesN::from_array(&env, &[build; 32]),
+            &1u32,
+            &0u32,
+            &9_000u32,
+            &false,
+            &false,
+        );
+    };
+
+    // Same placeholder Wasm the test host gives native contracts, so the
+    // contract keeps running as `UpgradeMechanism` after the swap.
+    let native_hash: BytesN<32> = env.crypto().sha256(&Bytes::new(&env)).into();
+    client.stage_upgrade(
+        &admin,
+        &2u32,
+        &native_hash,
+        &1u32,
+        &BytesN::random(&env),
+        &BytesN::random(&env),
+        &false,
+        &false,
+    );
+    client.approve_upgrade(&approver);
+
+    // No successful run yet: `last_successful_run_age` is `None`.
+    assert_eq!(
+        client.try_execute_upgrade(&admin),
+        Err(Ok(Error::TestGateFailed))
+    );
+
+    env.ledger().with_mut(|li| li.timestamp = 10_000);
+    record_run(1);
+    assert!(client.can_execute());
+
+    // Ready, but the last run is more than an hour old.
+    env.ledger().with_mut(|li| li.timestamp = 10_000 + 3_601);
+    assert_eq!(
+        client.try_execute_upgrade(&admin),
+        Err(Ok(Error::TestGateFailed))
+    );
+    assert!(!client.can_execute());
+
+    record_run(2);
+    client.execute_upgrade(&admin);
+    assert_eq!(client.state().current_version, 2);
+}